@@ -2,20 +2,686 @@
 // deno-lint-ignore-file
 // deno-fmt-ignore-file
 
+export function check_uniqueness(board: Board, variants?: Variants | null): any;
 export function launch_algorithm_x(
   starting_board?: Board | null,
   decision_strategy?: DecisionStrategy | null,
   desired_solutions?: number | null,
+  column_heuristic?: ColumnHeuristic | null,
+  seed?: bigint | null,
+  variants?: Variants | null,
 ): Board[];
+/**
+ * Counts the solutions of the board under the variants, up to `limit`.
+ * Runs the same search as `launch_algorithm_x`, but never builds a board
+ * for a solution.
+ */
+export function count_solutions(
+  board: Board,
+  limit: number,
+  variants?: Variants | null,
+): number;
 export function add(a: number, b: number): number;
+/**
+ * Counts the solutions of a Samurai board, stopping once `limit` have been
+ * found
+ */
+export function count_samurai_solutions(
+  board: SamuraiBoard,
+  limit: number,
+): number;
+/**
+ * Generates a Samurai puzzle with exactly one solution. Starts from a
+ * random solution and empties its cells in random order, keeping a cell
+ * empty only if every grid it is on still has one solution by itself.
+ * Grids that can each be solved alone can only be solved one way together,
+ * and checking a 9x9 grid stays quick where searching the whole board can
+ * take minutes once few givens are left. The puzzles keep a few more
+ * givens than a search of the whole board would leave.
+ */
+export function generate_samurai_puzzle(seed?: bigint | null): SamuraiPuzzle;
+/**
+ * Finds up to `desired_solutions` solutions of a Samurai board (1 by
+ * default), starting from an empty board by default. Like
+ * `launch_algorithm_x`, values are tried at random unless told otherwise,
+ * and the same seed always gives the same boards. Searching a board with
+ * few givens at random can take a long time, `generate_samurai_puzzle`
+ * fills its solutions one grid at a time instead.
+ */
+export function solve_samurai(
+  starting_board?: SamuraiBoard | null,
+  decision_strategy?: DecisionStrategy | null,
+  desired_solutions?: number | null,
+  seed?: bigint | null,
+): SamuraiBoard[];
+/**
+ * Generates a sandwich puzzle from a random solution of the starting board
+ * (an empty 9x9 board by default). Every row and column gets its sandwich
+ * clue, then givens from the solution are added where the clues leave a
+ * choice, until the solution is unique. Givens that turn out not to be
+ * needed are taken away again, so only a few are left.
+ */
+export function generate_sandwich_puzzle(
+  starting_board?: Board | null,
+  seed?: bigint | null,
+): SandwichPuzzle;
+/**
+ * Generates a puzzle with exactly one solution. Starts from a random
+ * solution of the starting board and empties the orbits of the symmetry in
+ * random order, keeping an orbit empty only if the puzzle still has one
+ * solution under the variants. Without a target, every orbit of givens
+ * left is needed, emptying any one of them would let the puzzle be solved
+ * another way. With one, returns an error if no more orbits can be emptied
+ * before the puzzle is down to the target.
+ */
+export function generate_puzzle(options: GeneratorOptions): Puzzle;
+/**
+ * How the next unsatisfied constraint is chosen at every step of the
+ * search.
+ */
+export enum ColumnHeuristic {
+  /**
+   * The unsatisfied constraint with the lowest index
+   */
+  FirstUnsatisfied = 0,
+  /**
+   * The unsatisfied constraint with the fewest rows left that could
+   * satisfy it, ties go to the lowest index
+   */
+  MinimumRemaining = 1,
+  /**
+   * Like `MinimumRemaining`, but ties are broken at random
+   */
+  MinimumRemainingRandom = 2,
+}
+/**
+ * The kinds of constraint every Sudoku board must satisfy, followed by the
+ * regions variants add to it
+ */
+export enum ConstraintType {
+  Row = 0,
+  Column = 1,
+  SubGrid = 2,
+  Existence = 3,
+  /**
+   * One of the main diagonals of Sudoku-X
+   */
+  Diagonal = 4,
+  /**
+   * One of the windows of Windoku
+   */
+  Window = 5,
+  /**
+   * A killer cage
+   */
+  Cage = 6,
+  /**
+   * Two cells a knight's move apart, for anti-knight
+   */
+  Knight = 7,
+  /**
+   * Two cells diagonally next to each other, for anti-king
+   */
+  King = 8,
+  /**
+   * A thermometer, it adds no region and only prunes the search
+   */
+  Thermometer = 9,
+  /**
+   * An arrow, it adds no region and only prunes the search
+   */
+  Arrow = 10,
+  /**
+   * A sandwich clue, it adds no region and only prunes the search
+   */
+  Sandwich = 11,
+  /**
+   * A kropki dot, or two cells that share a side without one when every
+   * dot is given. It adds no region and only prunes the search
+   */
+  Dot = 12,
+}
 export enum DecisionStrategy {
   First = 0,
   Random = 1,
 }
+/**
+ * What a kropki dot says about the values of its two cells
+ */
+export enum DotColor {
+  /**
+   * The values are consecutive, they differ by 1
+   */
+  White = 0,
+  /**
+   * One value is double the other
+   */
+  Black = 1,
+}
+/**
+ * The symmetry of a puzzle's clues. Clues are removed an orbit at a time,
+ * the cells the symmetry's turns and mirrors map onto each other.
+ */
+export enum Symmetry {
+  /**
+   * Each cell is its own orbit
+   */
+  None = 0,
+  /**
+   * The clues look the same after half a turn
+   */
+  HalfTurn = 1,
+  /**
+   * The clues look the same after a quarter turn
+   */
+  QuarterTurn = 2,
+  /**
+   * The clues mirror across the middle row, top to bottom
+   */
+  HorizontalMirror = 3,
+  /**
+   * The clues mirror across the middle column, left to right
+   */
+  VerticalMirror = 4,
+  /**
+   * The clues mirror across the main diagonal, from the top left corner
+   */
+  Diagonal = 5,
+  /**
+   * Every turn and mirror of the board at once
+   */
+  Dihedral = 6,
+}
+/**
+ * An arrow, the value in its circle must be the sum of the values along it.
+ * Values on the arrow may repeat unless another rule forbids it.
+ */
+export class Arrow {
+  free(): void;
+  /**
+   * The arrow starts next to the circle and runs through the cells in
+   * order
+   */
+  constructor(circle: Cell, cells: Cell[]);
+  cells(): Cell[];
+  circle(): Cell;
+}
+/**
+ * A square board made of boxes. A board with boxes of 3x3 cells is the
+ * standard 9x9 board, boxes of 2x2, 4x4 and 5x5 cells give 4x4, 16x16 and
+ * 25x25 boards. Boxes may also be rectangles, boxes 2 cells high and 3
+ * wide give a 6x6 board. Cells hold 1 up to the board's size, or 0 when
+ * empty.
+ *
+ * A region map replaces the boxes with irregular regions, for jigsaw
+ * Sudoku.
+ */
 export class Board {
   free(): void;
-  constructor();
+  /**
+   * The number of cells down each box
+   */
+  box_height(): number;
   static from_board(board: Board): Board;
+  /**
+   * Returns the region of every cell in row order, `undefined` when the
+   * boxes are the regions
+   */
+  region_map(): Uint32Array | undefined;
+  /**
+   * Returns every cell whose value differs from the other board's, in
+   * row order. Boards of different sizes differ in every cell.
+   */
+  differences(other: Board): Cell[];
+  /**
+   * Like `validate`, followed by every pair of cells that hold the same
+   * value in one of the regions the variants add, then every pair whose
+   * values break a kropki dot or, when every dot is given, sit side by side
+   * without a dot but could take one. Returns an error if the variants do
+   * not fit on the board.
+   */
+  validate_with(variants: Variants): Conflict[];
+  /**
+   * Creates an empty board whose boxes are `box_size` cells on each side,
+   * the board is `box_size * box_size` cells on each side. Returns an error
+   * if `box_size` is 0.
+   */
+  static with_box_size(box_size: number): Board;
+  /**
+   * Replaces the boxes with the regions of a jigsaw board. The map labels
+   * every cell in row order with its region, from 0 up to one less than
+   * the board's size. Each region must be `size` cells joined by their
+   * sides. Returns an error and leaves the board unchanged if the map is
+   * not such a partition of the board.
+   */
+  set_region_map(region_map: Uint32Array): void;
+  /**
+   * Goes back to using the boxes as regions
+   */
+  clear_region_map(): void;
+  /**
+   * Returns every killer cage, thermometer, arrow, sandwich clue and kropki
+   * dot of the variants whose cells are all filled but break its rule:
+   * cages that repeat a value or miss their sum, thermometers that don't
+   * increase from the bulb, arrows that don't add up to their circle, rows
+   * or columns whose sandwich doesn't add up to the clue and dots whose
+   * values don't fit their colour. Pairs without a dot that break the
+   * negative constraint have no index, `validate_with` reports them.
+   * Returns an error if the variants do not fit on the board.
+   */
+  broken_constraints(variants: Variants): BrokenConstraint[];
+  /**
+   * Creates an empty board whose boxes are `box_height` cells high and
+   * `box_width` cells wide. The board is `box_height * box_width` cells on
+   * each side, with `box_width` boxes stacked in each column of boxes and
+   * `box_height` boxes side by side in each row of boxes. Returns an error
+   * if either dimension is 0.
+   */
+  static with_box_dimensions(box_height: number, box_width: number): Board;
+  get(x: number, y: number): number;
+  /**
+   * Creates an empty 9x9 board
+   */
+  constructor();
   set(x: number, y: number, value: number): void;
+  /**
+   * The number of cells on each side of the board, which is also the
+   * largest value a cell can hold
+   */
+  size(): number;
+  /**
+   * Returns every pair of cells that hold the same value in a row, column
+   * or sub grid. A pair that shares a row and a sub grid is reported once
+   * for each. Values outside of 1 to the board's size are not compared,
+   * the solver reports those as invalid values.
+   */
+  validate(): Conflict[];
+  /**
+   * Returns the index of the box holding the cell, boxes are numbered left
+   * to right then top to bottom. On a board with a region map this is the
+   * cell's region instead.
+   */
+  box_index(x: number, y: number): number;
+  /**
+   * The number of cells across each box
+   */
+  box_width(): number;
+}
+/**
+ * A cage, thermometer, arrow, sandwich clue or dot of a variant whose cells
+ * are all filled but break its rule. The index is its position among the
+ * variant's constraints of that type, in the order they were added.
+ */
+export class BrokenConstraint {
+  private constructor();
+  free(): void;
+  constraint_type: ConstraintType;
+  index: number;
+}
+/**
+ * A killer cage, its cells must add up to the sum without repeating a
+ * value
+ */
+export class Cage {
+  free(): void;
+  constructor(cells: Cell[], sum: number);
+  sum(): number;
+  cells(): Cell[];
+}
+/**
+ * A cell on the board. The top left corner is 0,0
+ */
+export class Cell {
+  free(): void;
+  constructor(x: number, y: number);
+  x: number;
+  y: number;
+}
+/**
+ * Two cells that hold the same value in the same row, column, sub grid or
+ * region of a variant
+ */
+export class Conflict {
+  private constructor();
+  free(): void;
+  first_x: number;
+  first_y: number;
+  second_x: number;
+  second_y: number;
+  constraint_type: ConstraintType;
+}
+/**
+ * A kropki dot on the edge between two cells that share a side
+ */
+export class Dot {
+  private constructor();
+  free(): void;
+  /**
+   * A black dot, one cell holds double the value of the other
+   */
+  static black(first: Cell, second: Cell): Dot;
+  color(): DotColor;
+  first(): Cell;
+  /**
+   * A white dot, the cells hold consecutive values
+   */
+  static white(first: Cell, second: Cell): Dot;
+  second(): Cell;
+}
+/**
+ * A general exact cover problem: a set of rows over a number of columns,
+ * where a solution is a set of rows that covers every column exactly once.
+ * Sudoku is one such problem, but nothing here knows about boards.
+ *
+ * Secondary columns come after the primary ones, and only have to be
+ * covered at most once. A solution may leave them uncovered.
+ *
+ * Solutions are searched for with Dancing Links, using the same decision
+ * strategies and column heuristics as `launch_algorithm_x`. They are
+ * returned as the indexes of their rows, smallest first.
+ */
+export class ExactCover {
+  free(): void;
+  /**
+   * The number of primary and secondary columns together
+   */
+  column_count(): number;
+  /**
+   * Returns the first solution that includes every given row, or `None`
+   * if there is no such solution
+   */
+  first_solution(given_rows: Uint32Array): Uint32Array | undefined;
+  /**
+   * Counts the solutions that include every given row, stopping once
+   * `limit` have been found. No solution is collected while counting.
+   */
+  count_solutions(given_rows: Uint32Array, limit: number): number;
+  /**
+   * Like `all_solutions`, with each solution as a `Uint32Array`
+   */
+  all_solutions(given_rows: Uint32Array, limit: number): Array<any>;
+  primary_column_count(): number;
+  set_column_heuristic(column_heuristic: ColumnHeuristic): void;
+  set_decision_strategy(decision_strategy: DecisionStrategy): void;
+  /**
+   * Creates a problem with primary columns `0..primary_column_count`,
+   * followed by secondary columns that may be covered at most once
+   */
+  static with_secondary_columns(
+    primary_column_count: number,
+    secondary_column_count: number,
+  ): ExactCover;
+  /**
+   * Creates a problem with the given number of columns and no rows. The
+   * search picks the first row and the column with the fewest remaining
+   * rows until told otherwise.
+   */
+  constructor(column_count: number);
+  /**
+   * Adds a row covering the given columns and returns its index. A column
+   * listed more than once is only covered once.
+   */
+  add_row(columns: Uint32Array): number;
+  /**
+   * Seeds the random choices, so the same seed always finds the same
+   * solutions. Without a seed every search is seeded at random.
+   */
+  set_seed(seed?: bigint | null): void;
+  row_count(): number;
+}
+/**
+ * What `generate_puzzle` generates. By default a classic 9x9 puzzle from a
+ * fresh random seed, with no symmetry and as few clues as it can get to.
+ */
+export class GeneratorOptions {
+  free(): void;
+  set_symmetry(symmetry: Symmetry): void;
+  set_variants(variants: Variants): void;
+  /**
+   * The number of clues to stop removing clues at. Orbits of several cells
+   * can take the puzzle a few clues below it.
+   */
+  target_clues(): number | undefined;
+  /**
+   * The board the puzzle is generated on. Its size, boxes and region map
+   * shape the puzzle, and its givens are kept as givens of the puzzle.
+   */
+  starting_board(): Board;
+  set_target_clues(target_clues?: number | null): void;
+  set_starting_board(starting_board: Board): void;
+  constructor();
+  /**
+   * The same seed always gives the same puzzle
+   */
+  seed(): bigint | undefined;
+  set_seed(seed?: bigint | null): void;
+  /**
+   * The symmetry of the puzzle's clues. An orbit with a cell given on the
+   * starting board keeps all of its clues.
+   */
+  symmetry(): Symmetry;
+  /**
+   * The rules the puzzle's solution follows on top of classic Sudoku
+   */
+  variants(): Variants;
+}
+/**
+ * A generated puzzle with the only solution its givens lead to
+ */
+export class Puzzle {
+  private constructor();
+  free(): void;
+  puzzle(): Board;
+  solution(): Board;
+}
+/**
+ * A Samurai board: five 9x9 grids laid out on 21x21 cells, one in each
+ * corner and one in the centre overlapping a corner box of each of the
+ * others. The cells of an overlapping box belong to both grids. The cells
+ * of the layout outside every grid always hold 0.
+ */
+export class SamuraiBoard {
+  free(): void;
+  /**
+   * Returns true if the cell of the layout is on one of the grids. The top
+   * left corner of the layout is 0,0
+   */
+  is_on_grid(x: number, y: number): boolean;
+  /**
+   * Reads a board from 21 lines of 21 characters, one per cell of the
+   * layout. Cells of the grids hold a digit, or `.` or `0` when empty.
+   * Cells outside every grid are spaces, `.` or `0`, and lines may end
+   * early when the rest of their cells are outside every grid.
+   */
+  static from_layout(layout: string): SamuraiBoard;
   get(x: number, y: number): number;
+  /**
+   * Creates an empty Samurai board
+   */
+  constructor();
+  set(x: number, y: number, value: number): void;
+  /**
+   * Returns a copy of one of the grids as a 9x9 board. Grids are numbered
+   * left to right then top to bottom, so the centre grid is 2. Returns an
+   * error if there is no such grid.
+   */
+  grid(grid_idx: number): Board;
+  /**
+   * Copies a 9x9 board into one of the grids, overwriting the cells it
+   * shares with the centre grid or the outer grids. Returns an error and
+   * leaves the board unchanged if there is no such grid or the board is
+   * not 9x9.
+   */
+  set_grid(grid_idx: number, grid: Board): void;
+  /**
+   * Returns every pair of cells that hold the same value in a row, column
+   * or sub grid of one of the grids, at their positions on the layout. A
+   * pair in a box two grids share is reported once.
+   */
+  validate(): Conflict[];
+  /**
+   * Writes the board as 21 lines of 21 characters, the inverse of
+   * `from_layout`. Empty cells of the grids are `.`, and cells outside
+   * every grid are spaces.
+   */
+  to_layout(): string;
+}
+/**
+ * A generated Samurai puzzle with the only solution its givens lead to
+ */
+export class SamuraiPuzzle {
+  private constructor();
+  free(): void;
+  puzzle(): SamuraiBoard;
+  solution(): SamuraiBoard;
+}
+/**
+ * A sandwich clue outside a row or column, the sum of the values between
+ * its smallest and largest values. On a 9x9 board those are the 1 and the
+ * 9, wherever they are in the line.
+ */
+export class Sandwich {
+  private constructor();
+  free(): void;
+  /**
+   * `Row` or `Column`
+   */
+  constraint_type(): ConstraintType;
+  /**
+   * The clue left of the row `index`, counting rows from the top
+   */
+  static row(index: number, sum: number): Sandwich;
+  sum(): number;
+  index(): number;
+  /**
+   * The clue above the column `index`, counting columns from the left
+   */
+  static column(index: number, sum: number): Sandwich;
+}
+/**
+ * A generated puzzle: the givens, the variants holding its clues, and the
+ * only solution they lead to
+ */
+export class SandwichPuzzle {
+  private constructor();
+  free(): void;
+  puzzle(): Board;
+  solution(): Board;
+  /**
+   * The sandwich clue of every row and column
+   */
+  variants(): Variants;
+}
+/**
+ * Lazily yields the solutions of a board. The search pauses after every
+ * solution and resumes on the next call, so only the solution being looked
+ * at is held in memory and the caller can stop at any point.
+ */
+export class SolutionIter {
+  free(): void;
+  /**
+   * Takes the same options as `launch_algorithm_x`, with the same defaults
+   */
+  constructor(
+    starting_board?: Board | null,
+    decision_strategy?: DecisionStrategy | null,
+    column_heuristic?: ColumnHeuristic | null,
+    seed?: bigint | null,
+    variants?: Variants | null,
+  );
+  /**
+   * Follows JS's iterator protocol, returns `{ value: Board, done: false }`
+   * for every solution and then `{ value: undefined, done: true }`
+   */
+  next(): any;
+}
+/**
+ * A thermometer, its values must strictly increase from the bulb, the
+ * first cell, to the end of the tube
+ */
+export class Thermometer {
+  free(): void;
+  constructor(cells: Cell[]);
+  cells(): Cell[];
+}
+/**
+ * Rules a board follows on top of the rows, columns and boxes of classic
+ * Sudoku. Each rule adds regions that, like a box, must hold every value
+ * once. Killer cages add regions that must not repeat a value, and prune
+ * the search by their sums. Thermometers, arrows, sandwich clues and kropki
+ * dots add no regions, they only prune the search.
+ */
+export class Variants {
+  free(): void;
+  clear_dots(): void;
+  sandwiches(): Sandwich[];
+  /**
+   * Anti-knight, cells a knight's move apart must not hold the same value
+   */
+  anti_knight(): boolean;
+  clear_cages(): void;
+  set_windows(windows: boolean): void;
+  /**
+   * Sandwich Sudoku, the values between the smallest and largest values of
+   * a row or column must add up to its clue
+   */
+  add_sandwich(sandwich: Sandwich): void;
+  clear_arrows(): void;
+  thermometers(): Thermometer[];
+  set_anti_king(anti_king: boolean): void;
+  set_diagonals(diagonals: boolean): void;
+  /**
+   * The negative constraint of kropki Sudoku: every dot is given, so two
+   * cells that share a side without a dot are neither consecutive nor
+   * double one another
+   */
+  all_dots_given(): boolean;
+  /**
+   * Thermo Sudoku, the values on each thermometer must strictly increase
+   * from its bulb
+   */
+  add_thermometer(thermometer: Thermometer): void;
+  set_anti_knight(anti_knight: boolean): void;
+  clear_sandwiches(): void;
+  clear_thermometers(): void;
+  set_all_dots_given(all_dots_given: boolean): void;
+  /**
+   * Creates a set of variants with every rule turned off, which is classic
+   * Sudoku
+   */
+  constructor();
+  dots(): Dot[];
+  cages(): Cage[];
+  arrows(): Arrow[];
+  /**
+   * Kropki Sudoku, the values either side of a white dot are consecutive
+   * and one value beside a black dot is double the other
+   */
+  add_dot(dot: Dot): void;
+  /**
+   * Windoku, the windows must hold every value once. Windows are the size
+   * of a box, with one fewer across and down than there are boxes. They
+   * are one cell apart from each other and from the edges of the board,
+   * so a 9x9 board has four 3x3 windows.
+   */
+  windows(): boolean;
+  /**
+   * Killer Sudoku, the cells of each cage must add up to its sum without
+   * repeating a value. Cages may not share cells.
+   */
+  add_cage(cage: Cage): void;
+  /**
+   * Arrow Sudoku, the value in the circle of each arrow must be the sum of
+   * the values along it
+   */
+  add_arrow(arrow: Arrow): void;
+  /**
+   * Anti-king, cells a king's move apart must not hold the same value. Only
+   * diagonal neighbours add anything, the others share a row or column.
+   */
+  anti_king(): boolean;
+  /**
+   * Sudoku-X, both main diagonals must hold every value once
+   */
+  diagonals(): boolean;
 }
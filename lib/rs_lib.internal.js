@@ -67,6 +67,23 @@ function getStringFromWasm0(ptr, len) {
   return decodeText(ptr, len);
 }
 
+let cachedUint32ArrayMemory0 = null;
+
+function getUint32ArrayMemory0() {
+  if (
+    cachedUint32ArrayMemory0 === null ||
+    cachedUint32ArrayMemory0.byteLength === 0
+  ) {
+    cachedUint32ArrayMemory0 = new Uint32Array(wasm.memory.buffer);
+  }
+  return cachedUint32ArrayMemory0;
+}
+
+function getArrayU32FromWasm0(ptr, len) {
+  ptr = ptr >>> 0;
+  return getUint32ArrayMemory0().subarray(ptr / 4, ptr / 4 + len);
+}
+
 function getArrayU8FromWasm0(ptr, len) {
   ptr = ptr >>> 0;
   return getUint8ArrayMemory0().subarray(ptr / 1, ptr / 1 + len);
@@ -76,138 +93,2235 @@ function isLikeNone(x) {
   return x === undefined || x === null;
 }
 
+function debugString(val) {
+  // primitive types
+  const type = typeof val;
+  if (type == "number" || type == "boolean" || val == null) {
+    return `${val}`;
+  }
+  if (type == "string") {
+    return `"${val}"`;
+  }
+  if (type == "symbol") {
+    const description = val.description;
+    if (description == null) {
+      return "Symbol";
+    } else {
+      return `Symbol(${description})`;
+    }
+  }
+  if (type == "function") {
+    const name = val.name;
+    if (typeof name == "string" && name.length > 0) {
+      return `Function(${name})`;
+    } else {
+      return "Function";
+    }
+  }
+  // objects
+  if (Array.isArray(val)) {
+    const length = val.length;
+    let debug = "[";
+    if (length > 0) {
+      debug += debugString(val[0]);
+    }
+    for (let i = 1; i < length; i++) {
+      debug += ", " + debugString(val[i]);
+    }
+    debug += "]";
+    return debug;
+  }
+  // Test for built-in
+  const builtInMatches = /\[object ([^\]]+)\]/.exec(toString.call(val));
+  let className;
+  if (builtInMatches && builtInMatches.length > 1) {
+    className = builtInMatches[1];
+  } else {
+    // Failed to match the standard '[object ClassName]'
+    return toString.call(val);
+  }
+  if (className == "Object") {
+    // we're a user defined class or Object
+    // JSON.stringify avoids problems with cycles, and is generally much
+    // easier than looping through ownProperties of `val`.
+    try {
+      return "Object(" + JSON.stringify(val) + ")";
+    } catch (_) {
+      return "Object";
+    }
+  }
+  // errors
+  if (val instanceof Error) {
+    return `${val.name}: ${val.message}\n${val.stack}`;
+  }
+  // TODO we could test for more things here, like `Set`s and `Map`s.
+  return className;
+}
+
+let WASM_VECTOR_LEN = 0;
+
+const lTextEncoder = typeof TextEncoder === "undefined"
+  ? (0, module.require)("util").TextEncoder
+  : TextEncoder;
+
+const cachedTextEncoder = new lTextEncoder("utf-8");
+
+const encodeString = typeof cachedTextEncoder.encodeInto === "function"
+  ? function (arg, view) {
+    return cachedTextEncoder.encodeInto(arg, view);
+  }
+  : function (arg, view) {
+    const buf = cachedTextEncoder.encode(arg);
+    view.set(buf);
+    return {
+      read: arg.length,
+      written: buf.length,
+    };
+  };
+
+function passStringToWasm0(arg, malloc, realloc) {
+  if (realloc === undefined) {
+    const buf = cachedTextEncoder.encode(arg);
+    const ptr = malloc(buf.length, 1) >>> 0;
+    getUint8ArrayMemory0().subarray(ptr, ptr + buf.length).set(buf);
+    WASM_VECTOR_LEN = buf.length;
+    return ptr;
+  }
+
+  let len = arg.length;
+  let ptr = malloc(len, 1) >>> 0;
+
+  const mem = getUint8ArrayMemory0();
+
+  let offset = 0;
+
+  for (; offset < len; offset++) {
+    const code = arg.charCodeAt(offset);
+    if (code > 0x7F) break;
+    mem[ptr + offset] = code;
+  }
+
+  if (offset !== len) {
+    if (offset !== 0) {
+      arg = arg.slice(offset);
+    }
+    ptr = realloc(ptr, len, len = offset + arg.length * 3, 1) >>> 0;
+    const view = getUint8ArrayMemory0().subarray(ptr + offset, ptr + len);
+    const ret = encodeString(arg, view);
+
+    offset += ret.written;
+    ptr = realloc(ptr, len, offset, 1) >>> 0;
+  }
+
+  WASM_VECTOR_LEN = offset;
+  return ptr;
+}
+
+let cachedDataViewMemory0 = null;
+
+function getDataViewMemory0() {
+  if (
+    cachedDataViewMemory0 === null ||
+    cachedDataViewMemory0.buffer.detached === true ||
+    (cachedDataViewMemory0.buffer.detached === undefined &&
+      cachedDataViewMemory0.buffer !== wasm.memory.buffer)
+  ) {
+    cachedDataViewMemory0 = new DataView(wasm.memory.buffer);
+  }
+  return cachedDataViewMemory0;
+}
+
 function _assertClass(instance, klass) {
   if (!(instance instanceof klass)) {
     throw new Error(`expected instance of ${klass.name}`);
   }
 }
 
-let cachedDataViewMemory0 = null;
+function takeFromExternrefTable0(idx) {
+  const value = wasm.__wbindgen_export_2.get(idx);
+  wasm.__externref_table_dealloc(idx);
+  return value;
+}
+/**
+ * @param {Board} board
+ * @param {Variants | null} [variants]
+ * @returns {any}
+ */
+export function check_uniqueness(board, variants) {
+  _assertClass(board, Board);
+  let ptr0 = 0;
+  if (!isLikeNone(variants)) {
+    _assertClass(variants, Variants);
+    ptr0 = variants.__destroy_into_raw();
+  }
+  const ret = wasm.check_uniqueness(board.__wbg_ptr, ptr0);
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return takeFromExternrefTable0(ret[0]);
+}
+
+function getArrayJsValueFromWasm0(ptr, len) {
+  ptr = ptr >>> 0;
+  const mem = getDataViewMemory0();
+  const result = [];
+  for (let i = ptr; i < ptr + 4 * len; i += 4) {
+    result.push(wasm.__wbindgen_export_2.get(mem.getUint32(i, true)));
+  }
+  wasm.__externref_drop_slice(ptr, len);
+  return result;
+}
+/**
+ * @param {Board | null} [starting_board]
+ * @param {DecisionStrategy | null} [decision_strategy]
+ * @param {number | null} [desired_solutions]
+ * @param {ColumnHeuristic | null} [column_heuristic]
+ * @param {bigint | null} [seed]
+ * @param {Variants | null} [variants]
+ * @returns {Board[]}
+ */
+export function launch_algorithm_x(
+  starting_board,
+  decision_strategy,
+  desired_solutions,
+  column_heuristic,
+  seed,
+  variants,
+) {
+  let ptr0 = 0;
+  if (!isLikeNone(starting_board)) {
+    _assertClass(starting_board, Board);
+    ptr0 = starting_board.__destroy_into_raw();
+  }
+  let ptr1 = 0;
+  if (!isLikeNone(variants)) {
+    _assertClass(variants, Variants);
+    ptr1 = variants.__destroy_into_raw();
+  }
+  const ret = wasm.launch_algorithm_x(
+    ptr0,
+    isLikeNone(decision_strategy) ? 2 : decision_strategy,
+    isLikeNone(desired_solutions) ? 0x100000001 : desired_solutions >>> 0,
+    isLikeNone(column_heuristic) ? 3 : column_heuristic,
+    !isLikeNone(seed),
+    isLikeNone(seed) ? BigInt(0) : seed,
+    ptr1,
+  );
+  if (ret[3]) {
+    throw takeFromExternrefTable0(ret[2]);
+  }
+  var v3 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+  wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+  return v3;
+}
+
+/**
+ * Counts the solutions of the board under the variants, up to `limit`.
+ * Runs the same search as `launch_algorithm_x`, but never builds a board
+ * for a solution.
+ * @param {Board} board
+ * @param {number} limit
+ * @param {Variants | null} [variants]
+ * @returns {number}
+ */
+export function count_solutions(board, limit, variants) {
+  _assertClass(board, Board);
+  let ptr0 = 0;
+  if (!isLikeNone(variants)) {
+    _assertClass(variants, Variants);
+    ptr0 = variants.__destroy_into_raw();
+  }
+  const ret = wasm.count_solutions(board.__wbg_ptr, limit, ptr0);
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return ret[0] >>> 0;
+}
+
+function passArray32ToWasm0(arg, malloc) {
+  const ptr = malloc(arg.length * 4, 4) >>> 0;
+  getUint32ArrayMemory0().set(arg, ptr / 4);
+  WASM_VECTOR_LEN = arg.length;
+  return ptr;
+}
+/**
+ * @param {number} a
+ * @param {number} b
+ * @returns {number}
+ */
+export function add(a, b) {
+  const ret = wasm.add(a, b);
+  return ret;
+}
+
+function passArrayJsValueToWasm0(array, malloc) {
+  const ptr = malloc(array.length * 4, 4) >>> 0;
+  for (let i = 0; i < array.length; i++) {
+    const add = addToExternrefTable0(array[i]);
+    getDataViewMemory0().setUint32(ptr + 4 * i, add, true);
+  }
+  WASM_VECTOR_LEN = array.length;
+  return ptr;
+}
+/**
+ * Counts the solutions of a Samurai board, stopping once `limit` have been
+ * found
+ * @param {SamuraiBoard} board
+ * @param {number} limit
+ * @returns {number}
+ */
+export function count_samurai_solutions(board, limit) {
+  _assertClass(board, SamuraiBoard);
+  const ret = wasm.count_samurai_solutions(board.__wbg_ptr, limit);
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return ret[0] >>> 0;
+}
+
+/**
+ * Generates a Samurai puzzle with exactly one solution. Starts from a
+ * random solution and empties its cells in random order, keeping a cell
+ * empty only if every grid it is on still has one solution by itself.
+ * Grids that can each be solved alone can only be solved one way together,
+ * and checking a 9x9 grid stays quick where searching the whole board can
+ * take minutes once few givens are left. The puzzles keep a few more
+ * givens than a search of the whole board would leave.
+ * @param {bigint | null} [seed]
+ * @returns {SamuraiPuzzle}
+ */
+export function generate_samurai_puzzle(seed) {
+  const ret = wasm.generate_samurai_puzzle(
+    !isLikeNone(seed),
+    isLikeNone(seed) ? BigInt(0) : seed,
+  );
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return SamuraiPuzzle.__wrap(ret[0]);
+}
+
+/**
+ * Finds up to `desired_solutions` solutions of a Samurai board (1 by
+ * default), starting from an empty board by default. Like
+ * `launch_algorithm_x`, values are tried at random unless told otherwise,
+ * and the same seed always gives the same boards. Searching a board with
+ * few givens at random can take a long time, `generate_samurai_puzzle`
+ * fills its solutions one grid at a time instead.
+ * @param {SamuraiBoard | null} [starting_board]
+ * @param {DecisionStrategy | null} [decision_strategy]
+ * @param {number | null} [desired_solutions]
+ * @param {bigint | null} [seed]
+ * @returns {SamuraiBoard[]}
+ */
+export function solve_samurai(
+  starting_board,
+  decision_strategy,
+  desired_solutions,
+  seed,
+) {
+  let ptr0 = 0;
+  if (!isLikeNone(starting_board)) {
+    _assertClass(starting_board, SamuraiBoard);
+    ptr0 = starting_board.__destroy_into_raw();
+  }
+  const ret = wasm.solve_samurai(
+    ptr0,
+    isLikeNone(decision_strategy) ? 2 : decision_strategy,
+    isLikeNone(desired_solutions) ? 0x100000001 : desired_solutions >>> 0,
+    !isLikeNone(seed),
+    isLikeNone(seed) ? BigInt(0) : seed,
+  );
+  if (ret[3]) {
+    throw takeFromExternrefTable0(ret[2]);
+  }
+  var v2 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+  wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+  return v2;
+}
+
+/**
+ * Generates a sandwich puzzle from a random solution of the starting board
+ * (an empty 9x9 board by default). Every row and column gets its sandwich
+ * clue, then givens from the solution are added where the clues leave a
+ * choice, until the solution is unique. Givens that turn out not to be
+ * needed are taken away again, so only a few are left.
+ * @param {Board | null} [starting_board]
+ * @param {bigint | null} [seed]
+ * @returns {SandwichPuzzle}
+ */
+export function generate_sandwich_puzzle(starting_board, seed) {
+  let ptr0 = 0;
+  if (!isLikeNone(starting_board)) {
+    _assertClass(starting_board, Board);
+    ptr0 = starting_board.__destroy_into_raw();
+  }
+  const ret = wasm.generate_sandwich_puzzle(
+    ptr0,
+    !isLikeNone(seed),
+    isLikeNone(seed) ? BigInt(0) : seed,
+  );
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return SandwichPuzzle.__wrap(ret[0]);
+}
+
+/**
+ * Generates a puzzle with exactly one solution. Starts from a random
+ * solution of the starting board and empties the orbits of the symmetry in
+ * random order, keeping an orbit empty only if the puzzle still has one
+ * solution under the variants. Without a target, every orbit of givens
+ * left is needed, emptying any one of them would let the puzzle be solved
+ * another way. With one, returns an error if no more orbits can be emptied
+ * before the puzzle is down to the target.
+ * @param {GeneratorOptions} options
+ * @returns {Puzzle}
+ */
+export function generate_puzzle(options) {
+  _assertClass(options, GeneratorOptions);
+  var ptr0 = options.__destroy_into_raw();
+  const ret = wasm.generate_puzzle(ptr0);
+  if (ret[2]) {
+    throw takeFromExternrefTable0(ret[1]);
+  }
+  return Puzzle.__wrap(ret[0]);
+}
+
+/**
+ * How the next unsatisfied constraint is chosen at every step of the
+ * search.
+ * @enum {0 | 1 | 2}
+ */
+export const ColumnHeuristic = Object.freeze({
+  /**
+   * The unsatisfied constraint with the lowest index
+   */
+  FirstUnsatisfied: 0,
+  "0": "FirstUnsatisfied",
+  /**
+   * The unsatisfied constraint with the fewest rows left that could
+   * satisfy it, ties go to the lowest index
+   */
+  MinimumRemaining: 1,
+  "1": "MinimumRemaining",
+  /**
+   * Like `MinimumRemaining`, but ties are broken at random
+   */
+  MinimumRemainingRandom: 2,
+  "2": "MinimumRemainingRandom",
+});
+/**
+ * The kinds of constraint every Sudoku board must satisfy, followed by the
+ * regions variants add to it
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9 | 10 | 11 | 12}
+ */
+export const ConstraintType = Object.freeze({
+  Row: 0,
+  "0": "Row",
+  Column: 1,
+  "1": "Column",
+  SubGrid: 2,
+  "2": "SubGrid",
+  Existence: 3,
+  "3": "Existence",
+  /**
+   * One of the main diagonals of Sudoku-X
+   */
+  Diagonal: 4,
+  "4": "Diagonal",
+  /**
+   * One of the windows of Windoku
+   */
+  Window: 5,
+  "5": "Window",
+  /**
+   * A killer cage
+   */
+  Cage: 6,
+  "6": "Cage",
+  /**
+   * Two cells a knight's move apart, for anti-knight
+   */
+  Knight: 7,
+  "7": "Knight",
+  /**
+   * Two cells diagonally next to each other, for anti-king
+   */
+  King: 8,
+  "8": "King",
+  /**
+   * A thermometer, it adds no region and only prunes the search
+   */
+  Thermometer: 9,
+  "9": "Thermometer",
+  /**
+   * An arrow, it adds no region and only prunes the search
+   */
+  Arrow: 10,
+  "10": "Arrow",
+  /**
+   * A sandwich clue, it adds no region and only prunes the search
+   */
+  Sandwich: 11,
+  "11": "Sandwich",
+  /**
+   * A kropki dot, or two cells that share a side without one when every
+   * dot is given. It adds no region and only prunes the search
+   */
+  Dot: 12,
+  "12": "Dot",
+});
+/**
+ * @enum {0 | 1}
+ */
+export const DecisionStrategy = Object.freeze({
+  First: 0,
+  "0": "First",
+  Random: 1,
+  "1": "Random",
+});
+/**
+ * What a kropki dot says about the values of its two cells
+ * @enum {0 | 1}
+ */
+export const DotColor = Object.freeze({
+  /**
+   * The values are consecutive, they differ by 1
+   */
+  White: 0,
+  "0": "White",
+  /**
+   * One value is double the other
+   */
+  Black: 1,
+  "1": "Black",
+});
+/**
+ * The symmetry of a puzzle's clues. Clues are removed an orbit at a time,
+ * the cells the symmetry's turns and mirrors map onto each other.
+ * @enum {0 | 1 | 2 | 3 | 4 | 5 | 6}
+ */
+export const Symmetry = Object.freeze({
+  /**
+   * Each cell is its own orbit
+   */
+  None: 0,
+  "0": "None",
+  /**
+   * The clues look the same after half a turn
+   */
+  HalfTurn: 1,
+  "1": "HalfTurn",
+  /**
+   * The clues look the same after a quarter turn
+   */
+  QuarterTurn: 2,
+  "2": "QuarterTurn",
+  /**
+   * The clues mirror across the middle row, top to bottom
+   */
+  HorizontalMirror: 3,
+  "3": "HorizontalMirror",
+  /**
+   * The clues mirror across the middle column, left to right
+   */
+  VerticalMirror: 4,
+  "4": "VerticalMirror",
+  /**
+   * The clues mirror across the main diagonal, from the top left corner
+   */
+  Diagonal: 5,
+  "5": "Diagonal",
+  /**
+   * Every turn and mirror of the board at once
+   */
+  Dihedral: 6,
+  "6": "Dihedral",
+});
+
+const ArrowFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_arrow_free(ptr >>> 0, 1));
+/**
+ * An arrow, the value in its circle must be the sum of the values along it.
+ * Values on the arrow may repeat unless another rule forbids it.
+ */
+export class Arrow {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Arrow.prototype);
+    obj.__wbg_ptr = ptr;
+    ArrowFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    ArrowFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_arrow_free(ptr, 0);
+  }
+  /**
+   * The arrow starts next to the circle and runs through the cells in
+   * order
+   * @param {Cell} circle
+   * @param {Cell[]} cells
+   */
+  constructor(circle, cells) {
+    _assertClass(circle, Cell);
+    var ptr0 = circle.__destroy_into_raw();
+    const ptr1 = passArrayJsValueToWasm0(cells, wasm.__wbindgen_malloc);
+    const len1 = WASM_VECTOR_LEN;
+    const ret = wasm.arrow_new(ptr0, ptr1, len1);
+    this.__wbg_ptr = ret >>> 0;
+    ArrowFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * @returns {Cell[]}
+   */
+  cells() {
+    const ret = wasm.arrow_cells(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * @returns {Cell}
+   */
+  circle() {
+    const ret = wasm.arrow_circle(this.__wbg_ptr);
+    return Cell.__wrap(ret);
+  }
+}
+
+const BoardFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_board_free(ptr >>> 0, 1));
+/**
+ * A square board made of boxes. A board with boxes of 3x3 cells is the
+ * standard 9x9 board, boxes of 2x2, 4x4 and 5x5 cells give 4x4, 16x16 and
+ * 25x25 boards. Boxes may also be rectangles, boxes 2 cells high and 3
+ * wide give a 6x6 board. Cells hold 1 up to the board's size, or 0 when
+ * empty.
+ *
+ * A region map replaces the boxes with irregular regions, for jigsaw
+ * Sudoku.
+ */
+export class Board {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Board.prototype);
+    obj.__wbg_ptr = ptr;
+    BoardFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    BoardFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_board_free(ptr, 0);
+  }
+  /**
+   * The number of cells down each box
+   * @returns {number}
+   */
+  box_height() {
+    const ret = wasm.board_box_height(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {Board} board
+   * @returns {Board}
+   */
+  static from_board(board) {
+    _assertClass(board, Board);
+    const ret = wasm.board_from_board(board.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+  /**
+   * Returns the region of every cell in row order, `undefined` when the
+   * boxes are the regions
+   * @returns {Uint32Array | undefined}
+   */
+  region_map() {
+    const ret = wasm.board_region_map(this.__wbg_ptr);
+    let v1;
+    if (ret[0] !== 0) {
+      v1 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+      wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    }
+    return v1;
+  }
+  /**
+   * Returns every cell whose value differs from the other board's, in
+   * row order. Boards of different sizes differ in every cell.
+   * @param {Board} other
+   * @returns {Cell[]}
+   */
+  differences(other) {
+    _assertClass(other, Board);
+    const ret = wasm.board_differences(this.__wbg_ptr, other.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Like `validate`, followed by every pair of cells that hold the same
+   * value in one of the regions the variants add, then every pair whose
+   * values break a kropki dot or, when every dot is given, sit side by side
+   * without a dot but could take one. Returns an error if the variants do
+   * not fit on the board.
+   * @param {Variants} variants
+   * @returns {Conflict[]}
+   */
+  validate_with(variants) {
+    _assertClass(variants, Variants);
+    const ret = wasm.board_validate_with(this.__wbg_ptr, variants.__wbg_ptr);
+    if (ret[3]) {
+      throw takeFromExternrefTable0(ret[2]);
+    }
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Creates an empty board whose boxes are `box_size` cells on each side,
+   * the board is `box_size * box_size` cells on each side. Returns an error
+   * if `box_size` is 0.
+   * @param {number} box_size
+   * @returns {Board}
+   */
+  static with_box_size(box_size) {
+    const ret = wasm.board_with_box_size(box_size);
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return Board.__wrap(ret[0]);
+  }
+  /**
+   * Replaces the boxes with the regions of a jigsaw board. The map labels
+   * every cell in row order with its region, from 0 up to one less than
+   * the board's size. Each region must be `size` cells joined by their
+   * sides. Returns an error and leaves the board unchanged if the map is
+   * not such a partition of the board.
+   * @param {Uint32Array} region_map
+   */
+  set_region_map(region_map) {
+    const ptr0 = passArray32ToWasm0(region_map, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.board_set_region_map(this.__wbg_ptr, ptr0, len0);
+    if (ret[1]) {
+      throw takeFromExternrefTable0(ret[0]);
+    }
+  }
+  /**
+   * Goes back to using the boxes as regions
+   */
+  clear_region_map() {
+    wasm.board_clear_region_map(this.__wbg_ptr);
+  }
+  /**
+   * Returns every killer cage, thermometer, arrow, sandwich clue and kropki
+   * dot of the variants whose cells are all filled but break its rule:
+   * cages that repeat a value or miss their sum, thermometers that don't
+   * increase from the bulb, arrows that don't add up to their circle, rows
+   * or columns whose sandwich doesn't add up to the clue and dots whose
+   * values don't fit their colour. Pairs without a dot that break the
+   * negative constraint have no index, `validate_with` reports them.
+   * Returns an error if the variants do not fit on the board.
+   * @param {Variants} variants
+   * @returns {BrokenConstraint[]}
+   */
+  broken_constraints(variants) {
+    _assertClass(variants, Variants);
+    const ret = wasm.board_broken_constraints(
+      this.__wbg_ptr,
+      variants.__wbg_ptr,
+    );
+    if (ret[3]) {
+      throw takeFromExternrefTable0(ret[2]);
+    }
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Creates an empty board whose boxes are `box_height` cells high and
+   * `box_width` cells wide. The board is `box_height * box_width` cells on
+   * each side, with `box_width` boxes stacked in each column of boxes and
+   * `box_height` boxes side by side in each row of boxes. Returns an error
+   * if either dimension is 0.
+   * @param {number} box_height
+   * @param {number} box_width
+   * @returns {Board}
+   */
+  static with_box_dimensions(box_height, box_width) {
+    const ret = wasm.board_with_box_dimensions(box_height, box_width);
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return Board.__wrap(ret[0]);
+  }
+  /**
+   * @param {number} x
+   * @param {number} y
+   * @returns {number}
+   */
+  get(x, y) {
+    const ret = wasm.board_get(this.__wbg_ptr, x, y);
+    return ret;
+  }
+  /**
+   * Creates an empty 9x9 board
+   */
+  constructor() {
+    const ret = wasm.board_new();
+    this.__wbg_ptr = ret >>> 0;
+    BoardFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * @param {number} x
+   * @param {number} y
+   * @param {number} value
+   */
+  set(x, y, value) {
+    wasm.board_set(this.__wbg_ptr, x, y, value);
+  }
+  /**
+   * The number of cells on each side of the board, which is also the
+   * largest value a cell can hold
+   * @returns {number}
+   */
+  size() {
+    const ret = wasm.board_size(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * Returns every pair of cells that hold the same value in a row, column
+   * or sub grid. A pair that shares a row and a sub grid is reported once
+   * for each. Values outside of 1 to the board's size are not compared,
+   * the solver reports those as invalid values.
+   * @returns {Conflict[]}
+   */
+  validate() {
+    const ret = wasm.board_validate(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Returns the index of the box holding the cell, boxes are numbered left
+   * to right then top to bottom. On a board with a region map this is the
+   * cell's region instead.
+   * @param {number} x
+   * @param {number} y
+   * @returns {number}
+   */
+  box_index(x, y) {
+    const ret = wasm.board_box_index(this.__wbg_ptr, x, y);
+    return ret >>> 0;
+  }
+  /**
+   * The number of cells across each box
+   * @returns {number}
+   */
+  box_width() {
+    const ret = wasm.board_box_width(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+}
+
+const BrokenConstraintFinalization =
+  (typeof FinalizationRegistry === "undefined")
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry((ptr) =>
+      wasm.__wbg_brokenconstraint_free(ptr >>> 0, 1)
+    );
+/**
+ * A cage, thermometer, arrow, sandwich clue or dot of a variant whose cells
+ * are all filled but break its rule. The index is its position among the
+ * variant's constraints of that type, in the order they were added.
+ */
+export class BrokenConstraint {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(BrokenConstraint.prototype);
+    obj.__wbg_ptr = ptr;
+    BrokenConstraintFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    BrokenConstraintFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_brokenconstraint_free(ptr, 0);
+  }
+  /**
+   * @returns {ConstraintType}
+   */
+  get constraint_type() {
+    const ret = wasm.__wbg_get_brokenconstraint_constraint_type(this.__wbg_ptr);
+    return ret;
+  }
+  /**
+   * @param {ConstraintType} arg0
+   */
+  set constraint_type(arg0) {
+    wasm.__wbg_set_brokenconstraint_constraint_type(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {number}
+   */
+  get index() {
+    const ret = wasm.__wbg_get_brokenconstraint_index(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set index(arg0) {
+    wasm.__wbg_set_brokenconstraint_index(this.__wbg_ptr, arg0);
+  }
+}
+
+const CageFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_cage_free(ptr >>> 0, 1));
+/**
+ * A killer cage, its cells must add up to the sum without repeating a
+ * value
+ */
+export class Cage {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Cage.prototype);
+    obj.__wbg_ptr = ptr;
+    CageFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    CageFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_cage_free(ptr, 0);
+  }
+  /**
+   * @param {Cell[]} cells
+   * @param {number} sum
+   */
+  constructor(cells, sum) {
+    const ptr0 = passArrayJsValueToWasm0(cells, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.cage_new(ptr0, len0, sum);
+    this.__wbg_ptr = ret >>> 0;
+    CageFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * @returns {number}
+   */
+  sum() {
+    const ret = wasm.cage_sum(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @returns {Cell[]}
+   */
+  cells() {
+    const ret = wasm.cage_cells(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+}
+
+const CellFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_cell_free(ptr >>> 0, 1));
+/**
+ * A cell on the board. The top left corner is 0,0
+ */
+export class Cell {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Cell.prototype);
+    obj.__wbg_ptr = ptr;
+    CellFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  static __unwrap(jsValue) {
+    if (!(jsValue instanceof Cell)) {
+      return 0;
+    }
+    return jsValue.__destroy_into_raw();
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    CellFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_cell_free(ptr, 0);
+  }
+  /**
+   * @returns {number}
+   */
+  get x() {
+    const ret = wasm.__wbg_get_brokenconstraint_index(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set x(arg0) {
+    wasm.__wbg_set_brokenconstraint_index(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {number}
+   */
+  get y() {
+    const ret = wasm.__wbg_get_cell_y(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set y(arg0) {
+    wasm.__wbg_set_cell_y(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @param {number} x
+   * @param {number} y
+   */
+  constructor(x, y) {
+    const ret = wasm.cell_new(x, y);
+    this.__wbg_ptr = ret >>> 0;
+    CellFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+}
+
+const ConflictFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_conflict_free(ptr >>> 0, 1));
+/**
+ * Two cells that hold the same value in the same row, column, sub grid or
+ * region of a variant
+ */
+export class Conflict {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Conflict.prototype);
+    obj.__wbg_ptr = ptr;
+    ConflictFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    ConflictFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_conflict_free(ptr, 0);
+  }
+  /**
+   * @returns {number}
+   */
+  get first_x() {
+    const ret = wasm.__wbg_get_brokenconstraint_index(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set first_x(arg0) {
+    wasm.__wbg_set_brokenconstraint_index(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {number}
+   */
+  get first_y() {
+    const ret = wasm.__wbg_get_cell_y(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set first_y(arg0) {
+    wasm.__wbg_set_cell_y(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {number}
+   */
+  get second_x() {
+    const ret = wasm.__wbg_get_conflict_second_x(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set second_x(arg0) {
+    wasm.__wbg_set_conflict_second_x(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {number}
+   */
+  get second_y() {
+    const ret = wasm.__wbg_get_conflict_second_y(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {number} arg0
+   */
+  set second_y(arg0) {
+    wasm.__wbg_set_conflict_second_y(this.__wbg_ptr, arg0);
+  }
+  /**
+   * @returns {ConstraintType}
+   */
+  get constraint_type() {
+    const ret = wasm.__wbg_get_conflict_constraint_type(this.__wbg_ptr);
+    return ret;
+  }
+  /**
+   * @param {ConstraintType} arg0
+   */
+  set constraint_type(arg0) {
+    wasm.__wbg_set_conflict_constraint_type(this.__wbg_ptr, arg0);
+  }
+}
+
+const DotFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_dot_free(ptr >>> 0, 1));
+/**
+ * A kropki dot on the edge between two cells that share a side
+ */
+export class Dot {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Dot.prototype);
+    obj.__wbg_ptr = ptr;
+    DotFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    DotFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_dot_free(ptr, 0);
+  }
+  /**
+   * A black dot, one cell holds double the value of the other
+   * @param {Cell} first
+   * @param {Cell} second
+   * @returns {Dot}
+   */
+  static black(first, second) {
+    _assertClass(first, Cell);
+    var ptr0 = first.__destroy_into_raw();
+    _assertClass(second, Cell);
+    var ptr1 = second.__destroy_into_raw();
+    const ret = wasm.dot_black(ptr0, ptr1);
+    return Dot.__wrap(ret);
+  }
+  /**
+   * @returns {DotColor}
+   */
+  color() {
+    const ret = wasm.dot_color(this.__wbg_ptr);
+    return ret;
+  }
+  /**
+   * @returns {Cell}
+   */
+  first() {
+    const ret = wasm.dot_first(this.__wbg_ptr);
+    return Cell.__wrap(ret);
+  }
+  /**
+   * A white dot, the cells hold consecutive values
+   * @param {Cell} first
+   * @param {Cell} second
+   * @returns {Dot}
+   */
+  static white(first, second) {
+    _assertClass(first, Cell);
+    var ptr0 = first.__destroy_into_raw();
+    _assertClass(second, Cell);
+    var ptr1 = second.__destroy_into_raw();
+    const ret = wasm.dot_white(ptr0, ptr1);
+    return Dot.__wrap(ret);
+  }
+  /**
+   * @returns {Cell}
+   */
+  second() {
+    const ret = wasm.dot_second(this.__wbg_ptr);
+    return Cell.__wrap(ret);
+  }
+}
+
+const ExactCoverFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_exactcover_free(ptr >>> 0, 1));
+/**
+ * A general exact cover problem: a set of rows over a number of columns,
+ * where a solution is a set of rows that covers every column exactly once.
+ * Sudoku is one such problem, but nothing here knows about boards.
+ *
+ * Secondary columns come after the primary ones, and only have to be
+ * covered at most once. A solution may leave them uncovered.
+ *
+ * Solutions are searched for with Dancing Links, using the same decision
+ * strategies and column heuristics as `launch_algorithm_x`. They are
+ * returned as the indexes of their rows, smallest first.
+ */
+export class ExactCover {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(ExactCover.prototype);
+    obj.__wbg_ptr = ptr;
+    ExactCoverFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    ExactCoverFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_exactcover_free(ptr, 0);
+  }
+  /**
+   * The number of primary and secondary columns together
+   * @returns {number}
+   */
+  column_count() {
+    const ret = wasm.exactcover_column_count(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * Returns the first solution that includes every given row, or `None`
+   * if there is no such solution
+   * @param {Uint32Array} given_rows
+   * @returns {Uint32Array | undefined}
+   */
+  first_solution(given_rows) {
+    const ptr0 = passArray32ToWasm0(given_rows, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.exactcover_first_solution(this.__wbg_ptr, ptr0, len0);
+    if (ret[3]) {
+      throw takeFromExternrefTable0(ret[2]);
+    }
+    let v2;
+    if (ret[0] !== 0) {
+      v2 = getArrayU32FromWasm0(ret[0], ret[1]).slice();
+      wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    }
+    return v2;
+  }
+  /**
+   * Counts the solutions that include every given row, stopping once
+   * `limit` have been found. No solution is collected while counting.
+   * @param {Uint32Array} given_rows
+   * @param {number} limit
+   * @returns {number}
+   */
+  count_solutions(given_rows, limit) {
+    const ptr0 = passArray32ToWasm0(given_rows, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.exactcover_count_solutions(
+      this.__wbg_ptr,
+      ptr0,
+      len0,
+      limit,
+    );
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return ret[0] >>> 0;
+  }
+  /**
+   * Like `all_solutions`, with each solution as a `Uint32Array`
+   * @param {Uint32Array} given_rows
+   * @param {number} limit
+   * @returns {Array<any>}
+   */
+  all_solutions(given_rows, limit) {
+    const ptr0 = passArray32ToWasm0(given_rows, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.exactcover_all_solutions(
+      this.__wbg_ptr,
+      ptr0,
+      len0,
+      limit,
+    );
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return takeFromExternrefTable0(ret[0]);
+  }
+  /**
+   * @returns {number}
+   */
+  primary_column_count() {
+    const ret = wasm.exactcover_primary_column_count(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @param {ColumnHeuristic} column_heuristic
+   */
+  set_column_heuristic(column_heuristic) {
+    wasm.exactcover_set_column_heuristic(this.__wbg_ptr, column_heuristic);
+  }
+  /**
+   * @param {DecisionStrategy} decision_strategy
+   */
+  set_decision_strategy(decision_strategy) {
+    wasm.exactcover_set_decision_strategy(this.__wbg_ptr, decision_strategy);
+  }
+  /**
+   * Creates a problem with primary columns `0..primary_column_count`,
+   * followed by secondary columns that may be covered at most once
+   * @param {number} primary_column_count
+   * @param {number} secondary_column_count
+   * @returns {ExactCover}
+   */
+  static with_secondary_columns(primary_column_count, secondary_column_count) {
+    const ret = wasm.exactcover_with_secondary_columns(
+      primary_column_count,
+      secondary_column_count,
+    );
+    return ExactCover.__wrap(ret);
+  }
+  /**
+   * Creates a problem with the given number of columns and no rows. The
+   * search picks the first row and the column with the fewest remaining
+   * rows until told otherwise.
+   * @param {number} column_count
+   */
+  constructor(column_count) {
+    const ret = wasm.exactcover_new(column_count);
+    this.__wbg_ptr = ret >>> 0;
+    ExactCoverFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * Adds a row covering the given columns and returns its index. A column
+   * listed more than once is only covered once.
+   * @param {Uint32Array} columns
+   * @returns {number}
+   */
+  add_row(columns) {
+    const ptr0 = passArray32ToWasm0(columns, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.exactcover_add_row(this.__wbg_ptr, ptr0, len0);
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return ret[0] >>> 0;
+  }
+  /**
+   * Seeds the random choices, so the same seed always finds the same
+   * solutions. Without a seed every search is seeded at random.
+   * @param {bigint | null} [seed]
+   */
+  set_seed(seed) {
+    wasm.exactcover_set_seed(
+      this.__wbg_ptr,
+      !isLikeNone(seed),
+      isLikeNone(seed) ? BigInt(0) : seed,
+    );
+  }
+  /**
+   * @returns {number}
+   */
+  row_count() {
+    const ret = wasm.exactcover_row_count(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+}
+
+const GeneratorOptionsFinalization =
+  (typeof FinalizationRegistry === "undefined")
+    ? { register: () => {}, unregister: () => {} }
+    : new FinalizationRegistry((ptr) =>
+      wasm.__wbg_generatoroptions_free(ptr >>> 0, 1)
+    );
+/**
+ * What `generate_puzzle` generates. By default a classic 9x9 puzzle from a
+ * fresh random seed, with no symmetry and as few clues as it can get to.
+ */
+export class GeneratorOptions {
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    GeneratorOptionsFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_generatoroptions_free(ptr, 0);
+  }
+  /**
+   * @param {Symmetry} symmetry
+   */
+  set_symmetry(symmetry) {
+    wasm.generatoroptions_set_symmetry(this.__wbg_ptr, symmetry);
+  }
+  /**
+   * @param {Variants} variants
+   */
+  set_variants(variants) {
+    _assertClass(variants, Variants);
+    var ptr0 = variants.__destroy_into_raw();
+    wasm.generatoroptions_set_variants(this.__wbg_ptr, ptr0);
+  }
+  /**
+   * The number of clues to stop removing clues at. Orbits of several cells
+   * can take the puzzle a few clues below it.
+   * @returns {number | undefined}
+   */
+  target_clues() {
+    const ret = wasm.generatoroptions_target_clues(this.__wbg_ptr);
+    return ret === 0x100000001 ? undefined : ret;
+  }
+  /**
+   * The board the puzzle is generated on. Its size, boxes and region map
+   * shape the puzzle, and its givens are kept as givens of the puzzle.
+   * @returns {Board}
+   */
+  starting_board() {
+    const ret = wasm.generatoroptions_starting_board(this.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+  /**
+   * @param {number | null} [target_clues]
+   */
+  set_target_clues(target_clues) {
+    wasm.generatoroptions_set_target_clues(
+      this.__wbg_ptr,
+      isLikeNone(target_clues) ? 0x100000001 : target_clues >>> 0,
+    );
+  }
+  /**
+   * @param {Board} starting_board
+   */
+  set_starting_board(starting_board) {
+    _assertClass(starting_board, Board);
+    var ptr0 = starting_board.__destroy_into_raw();
+    wasm.generatoroptions_set_starting_board(this.__wbg_ptr, ptr0);
+  }
+  constructor() {
+    const ret = wasm.generatoroptions_new();
+    this.__wbg_ptr = ret >>> 0;
+    GeneratorOptionsFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * The same seed always gives the same puzzle
+   * @returns {bigint | undefined}
+   */
+  seed() {
+    const ret = wasm.generatoroptions_seed(this.__wbg_ptr);
+    return ret[0] === 0 ? undefined : BigInt.asUintN(64, ret[1]);
+  }
+  /**
+   * @param {bigint | null} [seed]
+   */
+  set_seed(seed) {
+    wasm.generatoroptions_set_seed(
+      this.__wbg_ptr,
+      !isLikeNone(seed),
+      isLikeNone(seed) ? BigInt(0) : seed,
+    );
+  }
+  /**
+   * The symmetry of the puzzle's clues. An orbit with a cell given on the
+   * starting board keeps all of its clues.
+   * @returns {Symmetry}
+   */
+  symmetry() {
+    const ret = wasm.generatoroptions_symmetry(this.__wbg_ptr);
+    return ret;
+  }
+  /**
+   * The rules the puzzle's solution follows on top of classic Sudoku
+   * @returns {Variants}
+   */
+  variants() {
+    const ret = wasm.generatoroptions_variants(this.__wbg_ptr);
+    return Variants.__wrap(ret);
+  }
+}
+
+const PuzzleFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_puzzle_free(ptr >>> 0, 1));
+/**
+ * A generated puzzle with the only solution its givens lead to
+ */
+export class Puzzle {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Puzzle.prototype);
+    obj.__wbg_ptr = ptr;
+    PuzzleFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    PuzzleFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_puzzle_free(ptr, 0);
+  }
+  /**
+   * @returns {Board}
+   */
+  puzzle() {
+    const ret = wasm.puzzle_puzzle(this.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+  /**
+   * @returns {Board}
+   */
+  solution() {
+    const ret = wasm.puzzle_solution(this.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+}
+
+const SamuraiBoardFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) =>
+    wasm.__wbg_samuraiboard_free(ptr >>> 0, 1)
+  );
+/**
+ * A Samurai board: five 9x9 grids laid out on 21x21 cells, one in each
+ * corner and one in the centre overlapping a corner box of each of the
+ * others. The cells of an overlapping box belong to both grids. The cells
+ * of the layout outside every grid always hold 0.
+ */
+export class SamuraiBoard {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(SamuraiBoard.prototype);
+    obj.__wbg_ptr = ptr;
+    SamuraiBoardFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    SamuraiBoardFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_samuraiboard_free(ptr, 0);
+  }
+  /**
+   * Returns true if the cell of the layout is on one of the grids. The top
+   * left corner of the layout is 0,0
+   * @param {number} x
+   * @param {number} y
+   * @returns {boolean}
+   */
+  is_on_grid(x, y) {
+    const ret = wasm.samuraiboard_is_on_grid(this.__wbg_ptr, x, y);
+    return ret !== 0;
+  }
+  /**
+   * Reads a board from 21 lines of 21 characters, one per cell of the
+   * layout. Cells of the grids hold a digit, or `.` or `0` when empty.
+   * Cells outside every grid are spaces, `.` or `0`, and lines may end
+   * early when the rest of their cells are outside every grid.
+   * @param {string} layout
+   * @returns {SamuraiBoard}
+   */
+  static from_layout(layout) {
+    const ptr0 = passStringToWasm0(
+      layout,
+      wasm.__wbindgen_malloc,
+      wasm.__wbindgen_realloc,
+    );
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.samuraiboard_from_layout(ptr0, len0);
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return SamuraiBoard.__wrap(ret[0]);
+  }
+  /**
+   * @param {number} x
+   * @param {number} y
+   * @returns {number}
+   */
+  get(x, y) {
+    const ret = wasm.samuraiboard_get(this.__wbg_ptr, x, y);
+    return ret;
+  }
+  /**
+   * Creates an empty Samurai board
+   */
+  constructor() {
+    const ret = wasm.samuraiboard_new();
+    this.__wbg_ptr = ret >>> 0;
+    SamuraiBoardFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * @param {number} x
+   * @param {number} y
+   * @param {number} value
+   */
+  set(x, y, value) {
+    wasm.samuraiboard_set(this.__wbg_ptr, x, y, value);
+  }
+  /**
+   * Returns a copy of one of the grids as a 9x9 board. Grids are numbered
+   * left to right then top to bottom, so the centre grid is 2. Returns an
+   * error if there is no such grid.
+   * @param {number} grid_idx
+   * @returns {Board}
+   */
+  grid(grid_idx) {
+    const ret = wasm.samuraiboard_grid(this.__wbg_ptr, grid_idx);
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    return Board.__wrap(ret[0]);
+  }
+  /**
+   * Copies a 9x9 board into one of the grids, overwriting the cells it
+   * shares with the centre grid or the outer grids. Returns an error and
+   * leaves the board unchanged if there is no such grid or the board is
+   * not 9x9.
+   * @param {number} grid_idx
+   * @param {Board} grid
+   */
+  set_grid(grid_idx, grid) {
+    _assertClass(grid, Board);
+    const ret = wasm.samuraiboard_set_grid(
+      this.__wbg_ptr,
+      grid_idx,
+      grid.__wbg_ptr,
+    );
+    if (ret[1]) {
+      throw takeFromExternrefTable0(ret[0]);
+    }
+  }
+  /**
+   * Returns every pair of cells that hold the same value in a row, column
+   * or sub grid of one of the grids, at their positions on the layout. A
+   * pair in a box two grids share is reported once.
+   * @returns {Conflict[]}
+   */
+  validate() {
+    const ret = wasm.samuraiboard_validate(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Writes the board as 21 lines of 21 characters, the inverse of
+   * `from_layout`. Empty cells of the grids are `.`, and cells outside
+   * every grid are spaces.
+   * @returns {string}
+   */
+  to_layout() {
+    let deferred1_0;
+    let deferred1_1;
+    try {
+      const ret = wasm.samuraiboard_to_layout(this.__wbg_ptr);
+      deferred1_0 = ret[0];
+      deferred1_1 = ret[1];
+      return getStringFromWasm0(ret[0], ret[1]);
+    } finally {
+      wasm.__wbindgen_free(deferred1_0, deferred1_1, 1);
+    }
+  }
+}
+
+const SamuraiPuzzleFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) =>
+    wasm.__wbg_samuraipuzzle_free(ptr >>> 0, 1)
+  );
+/**
+ * A generated Samurai puzzle with the only solution its givens lead to
+ */
+export class SamuraiPuzzle {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(SamuraiPuzzle.prototype);
+    obj.__wbg_ptr = ptr;
+    SamuraiPuzzleFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    SamuraiPuzzleFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_samuraipuzzle_free(ptr, 0);
+  }
+  /**
+   * @returns {SamuraiBoard}
+   */
+  puzzle() {
+    const ret = wasm.samuraipuzzle_puzzle(this.__wbg_ptr);
+    return SamuraiBoard.__wrap(ret);
+  }
+  /**
+   * @returns {SamuraiBoard}
+   */
+  solution() {
+    const ret = wasm.samuraipuzzle_solution(this.__wbg_ptr);
+    return SamuraiBoard.__wrap(ret);
+  }
+}
+
+const SandwichFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_sandwich_free(ptr >>> 0, 1));
+/**
+ * A sandwich clue outside a row or column, the sum of the values between
+ * its smallest and largest values. On a 9x9 board those are the 1 and the
+ * 9, wherever they are in the line.
+ */
+export class Sandwich {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Sandwich.prototype);
+    obj.__wbg_ptr = ptr;
+    SandwichFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    SandwichFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_sandwich_free(ptr, 0);
+  }
+  /**
+   * `Row` or `Column`
+   * @returns {ConstraintType}
+   */
+  constraint_type() {
+    const ret = wasm.sandwich_constraint_type(this.__wbg_ptr);
+    return ret;
+  }
+  /**
+   * The clue left of the row `index`, counting rows from the top
+   * @param {number} index
+   * @param {number} sum
+   * @returns {Sandwich}
+   */
+  static row(index, sum) {
+    const ret = wasm.sandwich_row(index, sum);
+    return Sandwich.__wrap(ret);
+  }
+  /**
+   * @returns {number}
+   */
+  sum() {
+    const ret = wasm.sandwich_sum(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * @returns {number}
+   */
+  index() {
+    const ret = wasm.sandwich_index(this.__wbg_ptr);
+    return ret >>> 0;
+  }
+  /**
+   * The clue above the column `index`, counting columns from the left
+   * @param {number} index
+   * @param {number} sum
+   * @returns {Sandwich}
+   */
+  static column(index, sum) {
+    const ret = wasm.sandwich_column(index, sum);
+    return Sandwich.__wrap(ret);
+  }
+}
+
+const SandwichPuzzleFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) =>
+    wasm.__wbg_sandwichpuzzle_free(ptr >>> 0, 1)
+  );
+/**
+ * A generated puzzle: the givens, the variants holding its clues, and the
+ * only solution they lead to
+ */
+export class SandwichPuzzle {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(SandwichPuzzle.prototype);
+    obj.__wbg_ptr = ptr;
+    SandwichPuzzleFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
+  }
+
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    SandwichPuzzleFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_sandwichpuzzle_free(ptr, 0);
+  }
+  /**
+   * @returns {Board}
+   */
+  puzzle() {
+    const ret = wasm.sandwichpuzzle_puzzle(this.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+  /**
+   * @returns {Board}
+   */
+  solution() {
+    const ret = wasm.sandwichpuzzle_solution(this.__wbg_ptr);
+    return Board.__wrap(ret);
+  }
+  /**
+   * The sandwich clue of every row and column
+   * @returns {Variants}
+   */
+  variants() {
+    const ret = wasm.sandwichpuzzle_variants(this.__wbg_ptr);
+    return Variants.__wrap(ret);
+  }
+}
+
+const SolutionIterFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) =>
+    wasm.__wbg_solutioniter_free(ptr >>> 0, 1)
+  );
+/**
+ * Lazily yields the solutions of a board. The search pauses after every
+ * solution and resumes on the next call, so only the solution being looked
+ * at is held in memory and the caller can stop at any point.
+ */
+export class SolutionIter {
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    SolutionIterFinalization.unregister(this);
+    return ptr;
+  }
 
-function getDataViewMemory0() {
-  if (
-    cachedDataViewMemory0 === null ||
-    cachedDataViewMemory0.buffer.detached === true ||
-    (cachedDataViewMemory0.buffer.detached === undefined &&
-      cachedDataViewMemory0.buffer !== wasm.memory.buffer)
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_solutioniter_free(ptr, 0);
+  }
+  /**
+   * Takes the same options as `launch_algorithm_x`, with the same defaults
+   * @param {Board | null} [starting_board]
+   * @param {DecisionStrategy | null} [decision_strategy]
+   * @param {ColumnHeuristic | null} [column_heuristic]
+   * @param {bigint | null} [seed]
+   * @param {Variants | null} [variants]
+   */
+  constructor(
+    starting_board,
+    decision_strategy,
+    column_heuristic,
+    seed,
+    variants,
   ) {
-    cachedDataViewMemory0 = new DataView(wasm.memory.buffer);
+    let ptr0 = 0;
+    if (!isLikeNone(starting_board)) {
+      _assertClass(starting_board, Board);
+      ptr0 = starting_board.__destroy_into_raw();
+    }
+    let ptr1 = 0;
+    if (!isLikeNone(variants)) {
+      _assertClass(variants, Variants);
+      ptr1 = variants.__destroy_into_raw();
+    }
+    const ret = wasm.solutioniter_new(
+      ptr0,
+      isLikeNone(decision_strategy) ? 2 : decision_strategy,
+      isLikeNone(column_heuristic) ? 3 : column_heuristic,
+      !isLikeNone(seed),
+      isLikeNone(seed) ? BigInt(0) : seed,
+      ptr1,
+    );
+    if (ret[2]) {
+      throw takeFromExternrefTable0(ret[1]);
+    }
+    this.__wbg_ptr = ret[0] >>> 0;
+    SolutionIterFinalization.register(this, this.__wbg_ptr, this);
+    return this;
   }
-  return cachedDataViewMemory0;
-}
-
-function getArrayJsValueFromWasm0(ptr, len) {
-  ptr = ptr >>> 0;
-  const mem = getDataViewMemory0();
-  const result = [];
-  for (let i = ptr; i < ptr + 4 * len; i += 4) {
-    result.push(wasm.__wbindgen_export_2.get(mem.getUint32(i, true)));
+  /**
+   * Follows JS's iterator protocol, returns `{ value: Board, done: false }`
+   * for every solution and then `{ value: undefined, done: true }`
+   * @returns {any}
+   */
+  next() {
+    const ret = wasm.solutioniter_next(this.__wbg_ptr);
+    return ret;
   }
-  wasm.__externref_drop_slice(ptr, len);
-  return result;
 }
+
+const ThermometerFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) =>
+    wasm.__wbg_thermometer_free(ptr >>> 0, 1)
+  );
 /**
- * @param {Board | null} [starting_board]
- * @param {DecisionStrategy | null} [decision_strategy]
- * @param {number | null} [desired_solutions]
- * @returns {Board[]}
+ * A thermometer, its values must strictly increase from the bulb, the
+ * first cell, to the end of the tube
  */
-export function launch_algorithm_x(
-  starting_board,
-  decision_strategy,
-  desired_solutions,
-) {
-  let ptr0 = 0;
-  if (!isLikeNone(starting_board)) {
-    _assertClass(starting_board, Board);
-    ptr0 = starting_board.__destroy_into_raw();
+export class Thermometer {
+  static __wrap(ptr) {
+    ptr = ptr >>> 0;
+    const obj = Object.create(Thermometer.prototype);
+    obj.__wbg_ptr = ptr;
+    ThermometerFinalization.register(obj, obj.__wbg_ptr, obj);
+    return obj;
   }
-  const ret = wasm.launch_algorithm_x(
-    ptr0,
-    isLikeNone(decision_strategy) ? 2 : decision_strategy,
-    isLikeNone(desired_solutions) ? 0x100000001 : desired_solutions >>> 0,
-  );
-  var v2 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
-  wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
-  return v2;
-}
 
-/**
- * @param {number} a
- * @param {number} b
- * @returns {number}
- */
-export function add(a, b) {
-  const ret = wasm.add(a, b);
-  return ret;
+  __destroy_into_raw() {
+    const ptr = this.__wbg_ptr;
+    this.__wbg_ptr = 0;
+    ThermometerFinalization.unregister(this);
+    return ptr;
+  }
+
+  free() {
+    const ptr = this.__destroy_into_raw();
+    wasm.__wbg_thermometer_free(ptr, 0);
+  }
+  /**
+   * @param {Cell[]} cells
+   */
+  constructor(cells) {
+    const ptr0 = passArrayJsValueToWasm0(cells, wasm.__wbindgen_malloc);
+    const len0 = WASM_VECTOR_LEN;
+    const ret = wasm.thermometer_new(ptr0, len0);
+    this.__wbg_ptr = ret >>> 0;
+    ThermometerFinalization.register(this, this.__wbg_ptr, this);
+    return this;
+  }
+  /**
+   * @returns {Cell[]}
+   */
+  cells() {
+    const ret = wasm.thermometer_cells(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
 }
 
+const VariantsFinalization = (typeof FinalizationRegistry === "undefined")
+  ? { register: () => {}, unregister: () => {} }
+  : new FinalizationRegistry((ptr) => wasm.__wbg_variants_free(ptr >>> 0, 1));
 /**
- * @enum {0 | 1}
+ * Rules a board follows on top of the rows, columns and boxes of classic
+ * Sudoku. Each rule adds regions that, like a box, must hold every value
+ * once. Killer cages add regions that must not repeat a value, and prune
+ * the search by their sums. Thermometers, arrows, sandwich clues and kropki
+ * dots add no regions, they only prune the search.
  */
-export const DecisionStrategy = Object.freeze({
-  First: 0,
-  "0": "First",
-  Random: 1,
-  "1": "Random",
-});
-
-const BoardFinalization = (typeof FinalizationRegistry === "undefined")
-  ? { register: () => {}, unregister: () => {} }
-  : new FinalizationRegistry((ptr) => wasm.__wbg_board_free(ptr >>> 0, 1));
-
-export class Board {
+export class Variants {
   static __wrap(ptr) {
     ptr = ptr >>> 0;
-    const obj = Object.create(Board.prototype);
+    const obj = Object.create(Variants.prototype);
     obj.__wbg_ptr = ptr;
-    BoardFinalization.register(obj, obj.__wbg_ptr, obj);
+    VariantsFinalization.register(obj, obj.__wbg_ptr, obj);
     return obj;
   }
 
   __destroy_into_raw() {
     const ptr = this.__wbg_ptr;
     this.__wbg_ptr = 0;
-    BoardFinalization.unregister(this);
+    VariantsFinalization.unregister(this);
     return ptr;
   }
 
   free() {
     const ptr = this.__destroy_into_raw();
-    wasm.__wbg_board_free(ptr, 0);
+    wasm.__wbg_variants_free(ptr, 0);
+  }
+  clear_dots() {
+    wasm.variants_clear_dots(this.__wbg_ptr);
+  }
+  /**
+   * @returns {Sandwich[]}
+   */
+  sandwiches() {
+    const ret = wasm.variants_sandwiches(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
   }
+  /**
+   * Anti-knight, cells a knight's move apart must not hold the same value
+   * @returns {boolean}
+   */
+  anti_knight() {
+    const ret = wasm.variants_anti_knight(this.__wbg_ptr);
+    return ret !== 0;
+  }
+  clear_cages() {
+    wasm.variants_clear_cages(this.__wbg_ptr);
+  }
+  /**
+   * @param {boolean} windows
+   */
+  set_windows(windows) {
+    wasm.variants_set_windows(this.__wbg_ptr, windows);
+  }
+  /**
+   * Sandwich Sudoku, the values between the smallest and largest values of
+   * a row or column must add up to its clue
+   * @param {Sandwich} sandwich
+   */
+  add_sandwich(sandwich) {
+    _assertClass(sandwich, Sandwich);
+    var ptr0 = sandwich.__destroy_into_raw();
+    wasm.variants_add_sandwich(this.__wbg_ptr, ptr0);
+  }
+  clear_arrows() {
+    wasm.variants_clear_arrows(this.__wbg_ptr);
+  }
+  /**
+   * @returns {Thermometer[]}
+   */
+  thermometers() {
+    const ret = wasm.variants_thermometers(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * @param {boolean} anti_king
+   */
+  set_anti_king(anti_king) {
+    wasm.variants_set_anti_king(this.__wbg_ptr, anti_king);
+  }
+  /**
+   * @param {boolean} diagonals
+   */
+  set_diagonals(diagonals) {
+    wasm.variants_set_diagonals(this.__wbg_ptr, diagonals);
+  }
+  /**
+   * The negative constraint of kropki Sudoku: every dot is given, so two
+   * cells that share a side without a dot are neither consecutive nor
+   * double one another
+   * @returns {boolean}
+   */
+  all_dots_given() {
+    const ret = wasm.variants_all_dots_given(this.__wbg_ptr);
+    return ret !== 0;
+  }
+  /**
+   * Thermo Sudoku, the values on each thermometer must strictly increase
+   * from its bulb
+   * @param {Thermometer} thermometer
+   */
+  add_thermometer(thermometer) {
+    _assertClass(thermometer, Thermometer);
+    var ptr0 = thermometer.__destroy_into_raw();
+    wasm.variants_add_thermometer(this.__wbg_ptr, ptr0);
+  }
+  /**
+   * @param {boolean} anti_knight
+   */
+  set_anti_knight(anti_knight) {
+    wasm.variants_set_anti_knight(this.__wbg_ptr, anti_knight);
+  }
+  clear_sandwiches() {
+    wasm.variants_clear_sandwiches(this.__wbg_ptr);
+  }
+  clear_thermometers() {
+    wasm.variants_clear_thermometers(this.__wbg_ptr);
+  }
+  /**
+   * @param {boolean} all_dots_given
+   */
+  set_all_dots_given(all_dots_given) {
+    wasm.variants_set_all_dots_given(this.__wbg_ptr, all_dots_given);
+  }
+  /**
+   * Creates a set of variants with every rule turned off, which is classic
+   * Sudoku
+   */
   constructor() {
-    const ret = wasm.board_new();
+    const ret = wasm.variants_new();
     this.__wbg_ptr = ret >>> 0;
-    BoardFinalization.register(this, this.__wbg_ptr, this);
+    VariantsFinalization.register(this, this.__wbg_ptr, this);
     return this;
   }
   /**
-   * @param {Board} board
-   * @returns {Board}
+   * @returns {Dot[]}
    */
-  static from_board(board) {
-    _assertClass(board, Board);
-    const ret = wasm.board_from_board(board.__wbg_ptr);
-    return Board.__wrap(ret);
+  dots() {
+    const ret = wasm.variants_dots(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
   }
   /**
-   * @param {number} x
-   * @param {number} y
-   * @param {number} value
+   * @returns {Cage[]}
    */
-  set(x, y, value) {
-    wasm.board_set(this.__wbg_ptr, x, y, value);
+  cages() {
+    const ret = wasm.variants_cages(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
   }
   /**
-   * @param {number} x
-   * @param {number} y
-   * @returns {number}
+   * @returns {Arrow[]}
    */
-  get(x, y) {
-    const ret = wasm.board_get(this.__wbg_ptr, x, y);
-    return ret;
+  arrows() {
+    const ret = wasm.variants_arrows(this.__wbg_ptr);
+    var v1 = getArrayJsValueFromWasm0(ret[0], ret[1]).slice();
+    wasm.__wbindgen_free(ret[0], ret[1] * 4, 4);
+    return v1;
+  }
+  /**
+   * Kropki Sudoku, the values either side of a white dot are consecutive
+   * and one value beside a black dot is double the other
+   * @param {Dot} dot
+   */
+  add_dot(dot) {
+    _assertClass(dot, Dot);
+    var ptr0 = dot.__destroy_into_raw();
+    wasm.variants_add_dot(this.__wbg_ptr, ptr0);
+  }
+  /**
+   * Windoku, the windows must hold every value once. Windows are the size
+   * of a box, with one fewer across and down than there are boxes. They
+   * are one cell apart from each other and from the edges of the board,
+   * so a 9x9 board has four 3x3 windows.
+   * @returns {boolean}
+   */
+  windows() {
+    const ret = wasm.variants_windows(this.__wbg_ptr);
+    return ret !== 0;
+  }
+  /**
+   * Killer Sudoku, the cells of each cage must add up to its sum without
+   * repeating a value. Cages may not share cells.
+   * @param {Cage} cage
+   */
+  add_cage(cage) {
+    _assertClass(cage, Cage);
+    var ptr0 = cage.__destroy_into_raw();
+    wasm.variants_add_cage(this.__wbg_ptr, ptr0);
   }
+  /**
+   * Arrow Sudoku, the value in the circle of each arrow must be the sum of
+   * the values along it
+   * @param {Arrow} arrow
+   */
+  add_arrow(arrow) {
+    _assertClass(arrow, Arrow);
+    var ptr0 = arrow.__destroy_into_raw();
+    wasm.variants_add_arrow(this.__wbg_ptr, ptr0);
+  }
+  /**
+   * Anti-king, cells a king's move apart must not hold the same value. Only
+   * diagonal neighbours add anything, the others share a row or column.
+   * @returns {boolean}
+   */
+  anti_king() {
+    const ret = wasm.variants_anti_king(this.__wbg_ptr);
+    return ret !== 0;
+  }
+  /**
+   * Sudoku-X, both main diagonals must hold every value once
+   * @returns {boolean}
+   */
+  diagonals() {
+    const ret = wasm.variants_diagonals(this.__wbg_ptr);
+    return ret !== 0;
+  }
+}
+
+export function __wbg_arrow_new(arg0) {
+  const ret = Arrow.__wrap(arg0);
+  return ret;
 }
 
 export function __wbg_board_new(arg0) {
@@ -215,6 +2329,16 @@ export function __wbg_board_new(arg0) {
   return ret;
 }
 
+export function __wbg_brokenconstraint_new(arg0) {
+  const ret = BrokenConstraint.__wrap(arg0);
+  return ret;
+}
+
+export function __wbg_cage_new(arg0) {
+  const ret = Cage.__wrap(arg0);
+  return ret;
+}
+
 export function __wbg_call_52af042a326d9b3a() {
   return handleError(function (arg0, arg1, arg2) {
     const ret = arg0.call(arg1, arg2);
@@ -229,11 +2353,31 @@ export function __wbg_call_90bf4b9978d51034() {
   }, arguments);
 }
 
+export function __wbg_cell_new(arg0) {
+  const ret = Cell.__wrap(arg0);
+  return ret;
+}
+
+export function __wbg_cell_unwrap(arg0) {
+  const ret = Cell.__unwrap(arg0);
+  return ret;
+}
+
+export function __wbg_conflict_new(arg0) {
+  const ret = Conflict.__wrap(arg0);
+  return ret;
+}
+
 export function __wbg_crypto_574e78ad8b13b65f(arg0) {
   const ret = arg0.crypto;
   return ret;
 }
 
+export function __wbg_dot_new(arg0) {
+  const ret = Dot.__wrap(arg0);
+  return ret;
+}
+
 export function __wbg_getRandomValues_b8f5dbd5f3995a9e() {
   return handleError(function (arg0, arg1) {
     arg0.getRandomValues(arg1);
@@ -250,6 +2394,26 @@ export function __wbg_msCrypto_a61aeb35a24c1329(arg0) {
   return ret;
 }
 
+export function __wbg_new_1b925e0c0e1d30ba() {
+  const ret = new Object();
+  return ret;
+}
+
+export function __wbg_new_7c134f9c83abf3a4(arg0, arg1) {
+  const ret = new Error(getStringFromWasm0(arg0, arg1));
+  return ret;
+}
+
+export function __wbg_new_d8a154d0939e6bb4() {
+  const ret = new Array();
+  return ret;
+}
+
+export function __wbg_newfromslice_3057bee348a48103(arg0, arg1) {
+  const ret = new Uint32Array(getArrayU32FromWasm0(arg0, arg1));
+  return ret;
+}
+
 export function __wbg_newnoargs_863941679b1933bb(arg0, arg1) {
   const ret = new Function(getStringFromWasm0(arg0, arg1));
   return ret;
@@ -274,6 +2438,11 @@ export function __wbg_prototypesetcall_a81ac58a5b6e988c(arg0, arg1, arg2) {
   Uint8Array.prototype.set.call(getArrayU8FromWasm0(arg0, arg1), arg2);
 }
 
+export function __wbg_push_a625ffb414ba40f2(arg0, arg1) {
+  const ret = arg0.push(arg1);
+  return ret;
+}
+
 export function __wbg_randomFillSync_ac0988aba3254290() {
   return handleError(function (arg0, arg1) {
     arg0.randomFillSync(arg1);
@@ -287,6 +2456,27 @@ export function __wbg_require_60cc747a6bc5215a() {
   }, arguments);
 }
 
+export function __wbg_samuraiboard_new(arg0) {
+  const ret = SamuraiBoard.__wrap(arg0);
+  return ret;
+}
+
+export function __wbg_sandwich_new(arg0) {
+  const ret = Sandwich.__wrap(arg0);
+  return ret;
+}
+
+export function __wbg_set_d66063c1f1a1157e() {
+  return handleError(function (arg0, arg1, arg2) {
+    const ret = Reflect.set(arg0, arg1, arg2);
+    return ret;
+  }, arguments);
+}
+
+export function __wbg_setname_717a9e3c923350a1(arg0, arg1, arg2) {
+  arg0.name = getStringFromWasm0(arg1, arg2);
+}
+
 export function __wbg_static_accessor_GLOBAL_656a564fb01c5b63() {
   const ret = typeof global === "undefined" ? null : global;
   return isLikeNone(ret) ? 0 : addToExternrefTable0(ret);
@@ -312,11 +2502,28 @@ export function __wbg_subarray_07c7c2b284d2102d(arg0, arg1, arg2) {
   return ret;
 }
 
+export function __wbg_thermometer_new(arg0) {
+  const ret = Thermometer.__wrap(arg0);
+  return ret;
+}
+
 export function __wbg_versions_c01dfd4722a88165(arg0) {
   const ret = arg0.versions;
   return ret;
 }
 
+export function __wbg_wbindgendebugstring_0c28a61befa1f3ce(arg0, arg1) {
+  const ret = debugString(arg1);
+  const ptr1 = passStringToWasm0(
+    ret,
+    wasm.__wbindgen_malloc,
+    wasm.__wbindgen_realloc,
+  );
+  const len1 = WASM_VECTOR_LEN;
+  getDataViewMemory0().setInt32(arg0 + 4 * 1, len1, true);
+  getDataViewMemory0().setInt32(arg0 + 4 * 0, ptr1, true);
+}
+
 export function __wbg_wbindgenisfunction_27a5c72d80bbdf07(arg0) {
   const ret = typeof arg0 === "function";
   return ret;
@@ -4,7 +4,7 @@
 // deno-fmt-ignore-file
 // @ts-self-types="./rs_lib.d.ts"
 
-// source-hash: f91ea2e4f7ed1aab3dae850dde78e9e565fa04d9
+// source-hash: 6394afeb383a1b1db46bbee2a42dcdf6a2c12e5e
 import * as wasm from "./rs_lib.wasm";
 export * from "./rs_lib.internal.js";
 import { __wbg_set_wasm } from "./rs_lib.internal.js";
@@ -1,16 +1,98 @@
-import { Board, launch_algorithm_x } from "./lib/rs_lib.js";
+import {
+  Arrow,
+  Board,
+  Cage,
+  Cell,
+  check_uniqueness,
+  ColumnHeuristic,
+  ConstraintType,
+  count_samurai_solutions,
+  count_solutions,
+  DecisionStrategy,
+  Dot,
+  DotColor,
+  ExactCover,
+  generate_puzzle,
+  generate_samurai_puzzle,
+  generate_sandwich_puzzle,
+  GeneratorOptions,
+  launch_algorithm_x,
+  Puzzle,
+  SamuraiBoard,
+  SamuraiPuzzle,
+  Sandwich,
+  SandwichPuzzle,
+  solve_samurai,
+  SolutionIter,
+  Symmetry,
+  Thermometer,
+  Variants,
+} from "./lib/rs_lib.js";
 
 /**
- * WASM representation of a board. When initilized the board's cells are all 0
+ * WASM representation of a board. When initilized the board's cells are all 0.
+ * The constructor creates a 9x9 board
  *
  * @static from_board static method that initializes a new board object from an
  *                    existing board object
+ * @static with_box_size Initializes an empty board whose boxes are
+ *                       `box_size` cells on each side. 9x9 boards have a box
+ *                       size of 3, 16x16 boards a box size of 4
+ * @static with_box_dimensions Initializes an empty board whose boxes are
+ *                             `box_height` cells high and `box_width` cells
+ *                             wide, eg. 2 and 3 for a 6x6 board. Both
+ *                             throw a `SolveError` of kind
+ *                             `InvalidBoxSize` for boxes 0 cells high or
+ *                             wide
+ * @method size Returns the number of cells on each side of the board
+ * @method box_height Returns the number of cells down each box
+ * @method box_width Returns the number of cells across each box
+ * @method box_index Returns the index of the box holding x,y, or of its
+ *                   region on a jigsaw board
+ * @method set_region_map Replaces the boxes with jigsaw regions. Takes the
+ *                        region of every cell in row order, from 0 up to
+ *                        `size - 1`, each region `size` cells joined by
+ *                        their sides. Throws a `SolveError` of kind
+ *                        `InvalidRegionMap` for any other map
+ * @method clear_region_map Goes back to using the boxes as regions
+ * @method region_map Returns the region of every cell, or undefined when the
+ *                    boxes are the regions
  * @method set Sets the value at x,y. The top left corner is 0,0
  * @method get Returns the value at x,y. The top left corner is 0,0
+ * @method validate Returns every pair of cells with equal values in the same
+ *                  row, column or sub grid
+ * @method validate_with Like `validate`, also checking the regions and
+ *                       kropki dots of the passed `Variants`
+ * @method broken_constraints Returns the cages, thermometers, arrows,
+ *                            sandwich clues and dots of the passed
+ *                            `Variants` whose cells are all filled but break
+ *                            their rule
+ * @method differences Returns every cell whose value differs from another
+ *                     board's
  * @method free frees the board from memory
  */
 export { Board };
 
+/**
+ * The kind of constraint a `Conflict` or `BrokenConstraint` breaks: `Row`,
+ * `Column`, `SubGrid` and `Existence` for classic Sudoku, then `Diagonal`,
+ * `Window`, `Cage`, `Knight`, `King`, `Thermometer`, `Arrow`, `Sandwich` and
+ * `Dot` for the variants. Compare a `constraint_type` against it, eg.
+ * `conflict.constraint_type === ConstraintType.Cage`.
+ */
+export { ConstraintType };
+
+/**
+ * A cell on a board, used to build cages, thermometers, arrows and dots. The
+ * top left corner is 0,0
+ *
+ * @constructor Takes the cell's x and y
+ * @property x The cell's column
+ * @property y The cell's row
+ * @method free frees the cell from memory
+ */
+export { Cell };
+
 /**
  * Uses algorithm_x to find the specificed number of solutions in the given
  * board.
@@ -27,5 +109,382 @@ export { Board };
  *                          random. If not passed will be random
  * @param desired_solutions Optioanl number of solutions to search for. By
  *                          default 1
+ * @param column_heuristic Optional heuristic used to pick the next constraint
+ *                         to satisfy. Can be first unsatisfied, minimum
+ *                         remaining, or minimum remaining with random tie
+ *                         breaks. If not passed will be first unsatisfied
+ * @param seed Optional seed for the random choices. The same seed always
+ *             gives the same boards. If not passed a random seed is used
+ * @param variants Optional `Variants` whose extra regions the solutions must
+ *                 also follow. If not passed boards are classic Sudoku
+ * @throws Error named `SolveError` when the board cannot be solved. Its `kind`
+ *         is `Unsolvable`, `ContradictoryGivens`, `InvalidValue`,
+ *         `UnsupportedVariant`, `InvalidCage`, `InvalidThermometer`,
+ *         `InvalidArrow`, `InvalidSandwich` or `InvalidDot`. Contradictory
+ *         givens also carry the `conflicts` found by `Board.validate_with`
  */
 export { launch_algorithm_x };
+
+/**
+ * How `launch_algorithm_x` picks a value for a cell when several could fit:
+ * `First` tries the values in order, `Random` in a random order.
+ */
+export { DecisionStrategy };
+
+/**
+ * How `launch_algorithm_x` picks the next constraint to satisfy:
+ * `FirstUnsatisfied`, `MinimumRemaining` (the constraint with the fewest
+ * choices left) or `MinimumRemainingRandom` (the same, with ties broken at
+ * random).
+ */
+export { ColumnHeuristic };
+
+/**
+ * Counts the solutions of the given board, stopping once `limit` solutions
+ * have been found. Solutions are only counted, never built, so large limits
+ * are cheap.
+ *
+ * @param board Board to count the solutions of
+ * @param limit Maximum number of solutions to count
+ * @param variants Optional `Variants` the solutions must follow
+ * @returns The number of solutions, at most `limit`. 0 if unsolvable
+ * @throws Error named `SolveError` with kind `ContradictoryGivens`,
+ *         `InvalidValue`, `UnsupportedVariant`, `InvalidCage`,
+ *         `InvalidThermometer`, `InvalidArrow`, `InvalidSandwich` or
+ *         `InvalidDot` when the board or variants are not valid
+ */
+export { count_solutions };
+
+/**
+ * Checks whether the given board has exactly one solution. The search stops
+ * as soon as a second solution is found.
+ *
+ * @param board Board to check
+ * @param variants Optional `Variants` the solutions must follow
+ * @returns An object whose `kind` is `Unique`, `Multiple` or `NoSolution`.
+ *          Unique results carry the `solution`. Multiple results carry the
+ *          `first` and `second` solutions found and the `differences`
+ *          between them, the cells where adding a clue rules one out
+ * @throws Error named `SolveError` with kind `ContradictoryGivens` or
+ *         `InvalidValue` when the board is not valid
+ */
+export { check_uniqueness };
+
+declare module "./lib/rs_lib.js" {
+  interface SolutionIter {
+    [Symbol.iterator](): SolutionIter;
+  }
+}
+
+// wasm_bindgen cannot name a method `[Symbol.iterator]`, so the iterator is
+// made its own iterable here
+SolutionIter.prototype[Symbol.iterator] = function (this: SolutionIter) {
+  return this;
+};
+
+/**
+ * Lazily finds the solutions of a board, one per call to `next`. The search
+ * pauses between calls, so only the current solution is held in memory and
+ * iteration can stop at any point. Works with `for...of`.
+ *
+ * @constructor Takes the starting board, decision strategy, column heuristic,
+ *              seed and variants, all optional and defaulting like
+ *              `launch_algorithm_x`
+ * @method next Returns `{ value: Board, done: false }` for each solution,
+ *              then `{ value: undefined, done: true }`
+ * @method free frees the iterator from memory
+ * @throws Error named `SolveError` from the constructor with kind
+ *         `ContradictoryGivens` or `InvalidValue` when the board is not valid
+ */
+export { SolutionIter };
+
+/**
+ * Rules a board follows on top of classic Sudoku, each adding regions that
+ * must hold every value once or pruning the search. The constructor turns
+ * every rule off.
+ *
+ * @method set_diagonals Turns Sudoku-X on or off, both main diagonals must
+ *                       hold every value once
+ * @method set_windows Turns Windoku on or off, the four 3x3 windows one cell
+ *                     in from the boxes of a 9x9 board must hold every value
+ *                     once. Boards of other sizes get windows the size of a
+ *                     box, one cell apart
+ * @method set_anti_knight Turns anti-knight on or off, cells a knight's move
+ *                         apart may not hold the same value
+ * @method set_anti_king Turns anti-king on or off, cells a king's move apart
+ *                       may not hold the same value
+ * @method add_cage Adds a killer `Cage`. Cages may not share cells
+ * @method cages Returns the cages added so far
+ * @method clear_cages Removes every cage
+ * @method add_thermometer Adds a `Thermometer`
+ * @method thermometers Returns the thermometers added so far
+ * @method clear_thermometers Removes every thermometer
+ * @method add_arrow Adds an `Arrow`
+ * @method arrows Returns the arrows added so far
+ * @method clear_arrows Removes every arrow
+ * @method add_sandwich Adds a `Sandwich` clue
+ * @method sandwiches Returns the sandwich clues added so far
+ * @method clear_sandwiches Removes every sandwich clue
+ * @method add_dot Adds a kropki `Dot`
+ * @method dots Returns the dots added so far
+ * @method clear_dots Removes every dot
+ * @method set_all_dots_given Turns the negative constraint on or off: cells
+ *                            side by side without a dot may not be
+ *                            consecutive or double one another
+ * @method all_dots_given Returns whether the negative constraint is on
+ * @method diagonals Returns whether Sudoku-X is on
+ * @method windows Returns whether Windoku is on
+ * @method anti_knight Returns whether anti-knight is on
+ * @method anti_king Returns whether anti-king is on
+ * @method free frees the variants from memory
+ */
+export { Variants };
+
+/**
+ * What `generate_puzzle` generates. The constructor sets up a classic 9x9
+ * puzzle from a random seed.
+ *
+ * @method set_starting_board Sets the board the puzzle is generated on. Its
+ *                            size, boxes and region map shape the puzzle,
+ *                            and its givens stay givens
+ * @method set_variants Sets the `Variants` the puzzle follows
+ * @method set_seed Sets the seed, the same seed always gives the same puzzle
+ * @method set_symmetry Sets the `Symmetry` of the clues, which are removed
+ *                      an orbit of symmetric cells at a time. Orbits with a
+ *                      given of the starting board keep their clues
+ * @method set_target_clues Sets the number of clues to stop at, orbits of
+ *                          several cells may go a little below it
+ * @method starting_board Returns the starting board
+ * @method variants Returns the variants
+ * @method seed Returns the seed, undefined when a random one is used
+ * @method symmetry Returns the symmetry
+ * @method target_clues Returns the target, undefined when there is none
+ * @method free frees the options from memory
+ */
+export { GeneratorOptions };
+
+/**
+ * Generates a puzzle with exactly one solution. A random solution is filled
+ * in, then its cells are emptied in random order, each staying empty only
+ * if the puzzle still has one solution. Every given left is needed.
+ *
+ * @param options `GeneratorOptions` for the puzzle
+ * @returns A `Puzzle`
+ * @throws Error named `SolveError` with kind `Unsolvable`,
+ *         `ContradictoryGivens` or `InvalidValue` when the starting board has
+ *         no solution, the kind of an invalid variant, or
+ *         `UnreachableClueCount` when no more clues can be removed before the
+ *         target clue count
+ */
+export { generate_puzzle };
+
+/**
+ * The symmetry of a generated puzzle's clues: `None`, `HalfTurn`,
+ * `QuarterTurn`, `HorizontalMirror` (top to bottom), `VerticalMirror` (left
+ * to right), `Diagonal` (across the main diagonal) or `Dihedral` (every turn
+ * and mirror).
+ */
+export { Symmetry };
+
+/**
+ * A generated puzzle.
+ *
+ * @method puzzle Returns the board with the puzzle's givens
+ * @method solution Returns the only solution
+ * @method free frees the puzzle from memory
+ */
+export { Puzzle };
+
+/**
+ * A killer cage. Its cells must add up to its sum without repeating a
+ * value. The solver prunes values that can't reach the sum while searching,
+ * and a board with no givens at all can be solved or checked for
+ * uniqueness from its cages alone.
+ *
+ * @constructor Takes an array of `Cell`s and the sum
+ * @method cells Returns the cage's cells
+ * @method sum Returns the cage's sum
+ * @method free frees the cage from memory
+ */
+export { Cage };
+
+/**
+ * A thermometer. Its values must strictly increase from the bulb, its first
+ * cell, along the tube. The solver prunes values that can't keep it
+ * increasing while searching.
+ *
+ * @constructor Takes an array of `Cell`s from the bulb, each touching the
+ *              one before it by a side or a corner
+ * @method cells Returns the thermometer's cells
+ * @method free frees the thermometer from memory
+ */
+export { Thermometer };
+
+/**
+ * An arrow. The value in its circle must be the sum of the values along it,
+ * which may repeat unless another rule forbids it. The solver prunes values
+ * that can't make it add up while searching.
+ *
+ * @constructor Takes the circle's `Cell` and an array of `Cell`s from the
+ *              circle along the arrow, each touching the one before it by
+ *              a side or a corner
+ * @method circle Returns the circle's cell
+ * @method cells Returns the arrow's cells
+ * @method free frees the arrow from memory
+ */
+export { Arrow };
+
+/**
+ * A sandwich clue outside a row or column. The values between the line's 1
+ * and 9, its smallest and largest values, must add up to the sum. The
+ * solver prunes values that can't make the sandwich add up while searching.
+ *
+ * @static row Creates the clue of the row at the index, from the top
+ * @static column Creates the clue of the column at the index, from the left
+ * @method constraint_type Returns `Row` or `Column`
+ * @method index Returns the index of the row or column
+ * @method sum Returns the clue's sum
+ * @method free frees the clue from memory
+ */
+export { Sandwich };
+
+/**
+ * A kropki dot between two cells that share a side. The solver prunes
+ * values that have no partner across the dot while searching.
+ *
+ * @static white Creates a white dot, the cells hold consecutive values
+ * @static black Creates a black dot, one cell holds double the other
+ * @method color Returns `DotColor.White` or `DotColor.Black`
+ * @method first Returns the dot's first cell
+ * @method second Returns the dot's second cell
+ * @method free frees the dot from memory
+ */
+export { Dot };
+
+/**
+ * The colour of a kropki `Dot`, `White` or `Black`.
+ */
+export { DotColor };
+
+/**
+ * Generates a sandwich puzzle with a clue for every row and column and only
+ * a few givens, which has exactly one solution.
+ *
+ * @param starting_board Optional board whose givens the puzzle keeps, an
+ *                       empty 9x9 board by default
+ * @param seed Optional seed for the random choices. The same seed always
+ *             gives the same puzzle. If not passed a random seed is used
+ * @returns A `SandwichPuzzle`
+ * @throws Error named `SolveError` with kind `Unsolvable`,
+ *         `ContradictoryGivens` or `InvalidValue` when the starting board
+ *         has no solution
+ */
+export { generate_sandwich_puzzle };
+
+/**
+ * A generated sandwich puzzle.
+ *
+ * @method puzzle Returns the board with the puzzle's givens
+ * @method variants Returns the `Variants` holding the sandwich clues
+ * @method solution Returns the only solution
+ * @method free frees the puzzle from memory
+ */
+export { SandwichPuzzle };
+
+/**
+ * A Samurai board: five 9x9 grids on a 21x21 layout, one in each corner and
+ * one in the centre sharing a corner box with each of the others. Cells of a
+ * shared box belong to both grids. The constructor creates an empty board.
+ *
+ * @static from_layout Reads a board from 21 lines, a digit, `.` or `0` for
+ *                     each cell of the grids and spaces outside them.
+ *                     Throws a `SolveError` with kind `InvalidLayout` for
+ *                     anything else
+ * @method to_layout Writes the board in the format `from_layout` reads
+ * @method is_on_grid Returns true if the cell of the layout is on a grid
+ * @method get Returns the value at x, y of the layout
+ * @method set Sets the value at x, y of the layout
+ * @method grid Returns a grid as a 9x9 `Board`. Grids are numbered left to
+ *              right then top to bottom, the centre grid is 2. Throws a
+ *              `SolveError` with kind `InvalidGrid` for an index past 4
+ * @method set_grid Copies a 9x9 `Board` into a grid. Throws a `SolveError`
+ *                  with kind `InvalidGrid` for an index past 4 or a board
+ *                  that isn't 9x9
+ * @method validate Returns the conflicts between the board's values, at
+ *                  their positions on the layout
+ * @method free frees the board from memory
+ */
+export { SamuraiBoard };
+
+/**
+ * Finds solutions of a Samurai board.
+ *
+ * @param starting_board Optional `SamuraiBoard` to search from, an empty
+ *                       board by default
+ * @param decision_strategy Optional `DecisionStrategy`, random by default
+ * @param desired_solutions Optional number of solutions to find, 1 by
+ *                          default
+ * @param seed Optional seed for the random choices
+ * @returns An array of solved `SamuraiBoard`s
+ * @throws Error named `SolveError` with kind `Unsolvable`,
+ *         `ContradictoryGivens` or `InvalidValue`
+ */
+export { solve_samurai };
+
+/**
+ * Counts the solutions of a Samurai board, stopping once `limit` have been
+ * found.
+ *
+ * @param board The `SamuraiBoard` to count the solutions of
+ * @param limit The number of solutions to stop at
+ * @returns The number of solutions found
+ * @throws Error named `SolveError` with kind `ContradictoryGivens` or
+ *         `InvalidValue`
+ */
+export { count_samurai_solutions };
+
+/**
+ * Generates a Samurai puzzle with exactly one solution. Every grid of the
+ * puzzle can be solved on its own.
+ *
+ * @param seed Optional seed for the random choices. The same seed always
+ *             gives the same puzzle. If not passed a random seed is used
+ * @returns A `SamuraiPuzzle`
+ */
+export { generate_samurai_puzzle };
+
+/**
+ * A generated Samurai puzzle.
+ *
+ * @method puzzle Returns the `SamuraiBoard` with the puzzle's givens
+ * @method solution Returns the only solution
+ * @method free frees the puzzle from memory
+ */
+export { SamuraiPuzzle };
+
+/**
+ * A general exact cover problem: rows over a number of columns, where a
+ * solution is a set of rows covering every column exactly once. Solutions
+ * are returned as row indexes, smallest first.
+ *
+ * @constructor Takes the number of columns
+ * @static with_secondary_columns Creates a problem with the given number of
+ *                               primary columns, followed by secondary
+ *                               columns that are covered at most once
+ * @method add_row Adds a row covering the given columns and returns its
+ *                 index
+ * @method set_decision_strategy Sets how rows are picked, first by default
+ * @method set_column_heuristic Sets how columns are picked, minimum
+ *                              remaining by default
+ * @method set_seed Seeds the random choices
+ * @method first_solution Returns the first solution including the given
+ *                        rows, or undefined
+ * @method all_solutions Returns up to `limit` solutions including the given
+ *                       rows
+ * @method count_solutions Counts up to `limit` solutions including the given
+ *                         rows
+ * @method free frees the problem from memory
+ * @throws Error named `SolveError` with kind `InvalidColumn`, `InvalidRow`
+ *         or `ContradictoryGivens` for rows or givens the problem can't have
+ */
+export { ExactCover };
@@ -1,58 +1,107 @@
-use crate::board::Board;
-use core::panic;
-use rand::Rng;
-use std::collections::HashSet;
+use crate::bitset::{ColumnSet, RowSet};
+use crate::board::{Board, Cell};
+use crate::dancing_links::ExactCoverIter;
+use crate::exact_cover::ExactCover;
+use crate::rule::{
+  rule_columns, ColumnRule, ExistenceRule, Placement, RowRule, SubGridRule,
+  VariantRule,
+};
+use crate::solution_iter::SolutionIter;
+use crate::solve_error::SolveError;
+use crate::uniqueness::Uniqueness;
+use crate::variants::{Region, Variants};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 
 #[cfg(test)]
 #[path = "algorithm_x_tests.rs"]
 mod algorithm_x_tests;
 
+/// Each row of the table is the set of columns that choice satisfies. There
+/// is a row for every value in every cell of a board, and a column for
+/// every constraint on it.
 pub struct ConstraintTable {
-  pub table: [[bool; 324]; 729],
+  pub table: Vec<ColumnSet>,
 }
 
-impl Default for ConstraintTable {
-  fn default() -> Self {
-    Self {
-      table: [[false; 324]; 729],
-    }
-  }
+/// Number of columns in the constraint table of a board with `size` cells
+/// on each side, one for each constraint
+pub fn constraint_count(size: usize) -> usize {
+  4 * size * size
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct Decision {
-  selected_row: usize,
-  potential_rows: Vec<usize>,
-  rows_conflicting_with_selected_row: Vec<usize>,
+/// Number of rows in the constraint table of a board with `size` cells on
+/// each side, one for each choice of value in a cell
+pub fn choice_count(size: usize) -> usize {
+  size * size * size
 }
 
-enum ConstraintType {
+/// The kinds of constraint every Sudoku board must satisfy, followed by the
+/// regions variants add to it
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConstraintType {
   Row,
   Column,
   SubGrid,
   Existence,
+  /// One of the main diagonals of Sudoku-X
+  Diagonal,
+  /// One of the windows of Windoku
+  Window,
+  /// A killer cage
+  Cage,
+  /// Two cells a knight's move apart, for anti-knight
+  Knight,
+  /// Two cells diagonally next to each other, for anti-king
+  King,
+  /// A thermometer, it adds no region and only prunes the search
+  Thermometer,
+  /// An arrow, it adds no region and only prunes the search
+  Arrow,
+  /// A sandwich clue, it adds no region and only prunes the search
+  Sandwich,
+  /// A kropki dot, or two cells that share a side without one when every
+  /// dot is given. It adds no region and only prunes the search
+  Dot,
 }
 
 impl ConstraintType {
-  fn get_offset(&self) -> usize {
-    match self {
+  /// Each kind of constraint takes up `size * size` columns of the table.
+  /// The regions of variants share the columns after them, `size` for each
+  /// region.
+  fn get_offset(&self, size: usize) -> usize {
+    let kind_idx = match self {
       ConstraintType::Row => 0,
-      ConstraintType::Column => 81,
-      ConstraintType::SubGrid => 162,
-      ConstraintType::Existence => 243,
-    }
+      ConstraintType::Column => 1,
+      ConstraintType::SubGrid => 2,
+      ConstraintType::Existence => 3,
+      ConstraintType::Diagonal
+      | ConstraintType::Window
+      | ConstraintType::Cage
+      | ConstraintType::Knight
+      | ConstraintType::King
+      | ConstraintType::Thermometer
+      | ConstraintType::Arrow
+      | ConstraintType::Sandwich
+      | ConstraintType::Dot => 4,
+    };
+
+    kind_idx * size * size
   }
 }
 
-/// This function is responsible for mapping all 324 constraints
-/// to a predicable index.
+/// This function is responsible for mapping all the constraints of a board
+/// with `size` cells on each side to a predicable index.
 ///
 /// This writeup:
 /// https://web.archive.org/web/20230426084731/https://garethrees.org/2007/06/10/zendoku-generation/#section-4
 /// is the inspiration for my method.
 ///
-/// constraint_broad_value is the zero indexed row/column/sub_grid
+/// constraint_broad_value is the zero indexed row/column/sub_grid, or the
+/// index of a variant's region among all of them
 /// eg. row 1 would be zero, column 5 would be 4.
 ///
 /// constraint_cell_value is the zero indexed cell value
@@ -61,206 +110,119 @@ fn map_constraint_to_column_idx(
   constraint_type: ConstraintType,
   constraint_broad_value: usize,
   constraint_cell_value: usize,
+  size: usize,
 ) -> usize {
-  constraint_type.get_offset()
-    + (constraint_broad_value * 9)
+  constraint_type.get_offset(size)
+    + (constraint_broad_value * size)
     + constraint_cell_value
 }
 
-/// Generates a row in the constraint table using the choices represented by
-/// the board parameter.
-/// These constraints take up the first 81 indexes in the constraint table
-fn fill_row_constraints(board: &Board, constraint_row: &mut [bool; 324]) {
-  for row_idx in 0..9 {
-    let board_row = board.get_row(row_idx);
-    for cell_idx in 0..9 {
-      let required_number = cell_idx as i32 + 1;
-      if board_row.contains(&required_number) {
-        let index =
-          map_constraint_to_column_idx(ConstraintType::Row, row_idx, cell_idx);
-        constraint_row[index] = true;
+/// Calls `f` with the position and zero indexed value of every cell that
+/// holds a value from 1 up to the board's size
+fn for_each_value(board: &Board, mut f: impl FnMut(usize, usize, usize)) {
+  let size = board.size();
+  for y in 0..size {
+    for x in 0..size {
+      let value = board.get(x, y);
+      if (1..=size as i32).contains(&value) {
+        f(x, y, value as usize - 1);
       }
     }
   }
 }
 
-fn fill_column_constraints(board: &Board, constraint_row: &mut [bool; 324]) {
-  for column_idx in 0..9 {
-    let board_column = board.get_column(column_idx);
-    for cell_idx in 0..9 {
-      let required_number = cell_idx as i32 + 1;
-      if board_column.contains(&required_number) {
-        let index = map_constraint_to_column_idx(
-          ConstraintType::Column,
-          column_idx,
-          cell_idx,
-        );
-        constraint_row[index] = true;
-      }
+/// The rules of classic Sudoku, each with the kind of constraint its
+/// columns are in the constraint table. Row constraints take up the first
+/// `size * size` indexes, then come the column, sub grid and existence
+/// constraints.
+const CLASSIC_RULES: [(ConstraintType, &dyn VariantRule); 4] = [
+  (ConstraintType::Row, &RowRule),
+  (ConstraintType::Column, &ColumnRule),
+  (ConstraintType::SubGrid, &SubGridRule),
+  (ConstraintType::Existence, &ExistenceRule),
+];
+
+/// Generates the constraints of one kind in a row of the constraint table,
+/// from the columns the rule covers for every value on the board
+fn fill_rule_constraints(
+  constraint_type: ConstraintType,
+  rule: &dyn VariantRule,
+  board: &Board,
+  constraint_row: &mut ColumnSet,
+) {
+  let offset = constraint_type.get_offset(board.size());
+  for_each_value(board, |x, y, value_idx| {
+    let placement = Placement {
+      cell: Cell { x, y },
+      value: value_idx as u32 + 1,
+    };
+    for column in rule.columns(board, placement) {
+      constraint_row.insert(offset + column);
     }
-  }
+  });
 }
 
-/// The sub grid constraints take up index 162 to 243
-/// The first nine index'es represent whether the top
-/// left sub grid contains each number. The next
-/// nine indexes represent whether the top middle sub
-/// grid contain each number. Following this pattern
-/// the fourth set of nine indexes would represent whether
-/// the left middle sub grid has each number.
-fn fill_sub_grid_constraints(board: &Board, constraint_row: &mut [bool; 324]) {
-  for sub_grid_y_start in (0..9).step_by(3) {
-    for sub_grid_x_start in (0..9).step_by(3) {
-      let mut sub_grid_numbers: Vec<i32> = vec![];
-      // Add row 1
-      sub_grid_numbers.push(board.get(sub_grid_y_start, sub_grid_x_start));
-      sub_grid_numbers.push(board.get(sub_grid_y_start, sub_grid_x_start + 1));
-      sub_grid_numbers.push(board.get(sub_grid_y_start, sub_grid_x_start + 2));
-      // Add row 2
-      sub_grid_numbers.push(board.get(sub_grid_y_start + 1, sub_grid_x_start));
-      sub_grid_numbers
-        .push(board.get(sub_grid_y_start + 1, sub_grid_x_start + 1));
-      sub_grid_numbers
-        .push(board.get(sub_grid_y_start + 1, sub_grid_x_start + 2));
-      // Add row 3
-      sub_grid_numbers.push(board.get(sub_grid_y_start + 2, sub_grid_x_start));
-      sub_grid_numbers
-        .push(board.get(sub_grid_y_start + 2, sub_grid_x_start + 1));
-      sub_grid_numbers
-        .push(board.get(sub_grid_y_start + 2, sub_grid_x_start + 2));
-
-      for cell_idx in 0..9 {
-        let required_number = cell_idx as i32 + 1;
-        if sub_grid_numbers.contains(&required_number) {
-          let index = map_constraint_to_column_idx(
-            ConstraintType::SubGrid,
-            sub_grid_x_start + (sub_grid_y_start / 3),
-            cell_idx,
-          );
-          constraint_row[index] = true;
-        }
-      }
-    }
+pub fn fill_constraint_table_row(board: &Board, row: &mut ColumnSet) {
+  for (constraint_type, rule) in CLASSIC_RULES {
+    fill_rule_constraints(constraint_type, rule, board, row);
   }
 }
 
-fn fill_existence_constraints(board: &Board, constraint_row: &mut [bool; 324]) {
-  for row_idx in 0..9 {
-    for col_idx in 0..9 {
-      let cell_has_value = board.get(col_idx, row_idx) != 0;
-      let index = map_constraint_to_column_idx(
-        ConstraintType::Existence,
-        row_idx,
-        col_idx,
-      );
-      constraint_row[index] = cell_has_value;
+/// The region constraints follow the existence constraints, `size` indexes
+/// for each region in the order they are given. Regions with fewer cells
+/// than values only hold each value at most once, so they have to come
+/// last to be given secondary columns.
+fn fill_region_constraints(
+  board: &Board,
+  regions: &[Region],
+  constraint_row: &mut ColumnSet,
+) {
+  for_each_value(board, |x, y, value_idx| {
+    let cell = Cell { x, y };
+    for (region_idx, region) in regions.iter().enumerate() {
+      if region.cells.contains(&cell) {
+        let index = map_constraint_to_column_idx(
+          region.constraint_type,
+          region_idx,
+          value_idx,
+          board.size(),
+        );
+        constraint_row.insert(index);
+      }
     }
-  }
-}
-
-pub fn fill_constraint_table_row(board: &Board, row: &mut [bool; 324]) {
-  fill_row_constraints(board, row);
-  fill_column_constraints(board, row);
-  fill_sub_grid_constraints(board, row);
-  fill_existence_constraints(board, row);
+  });
 }
 
-pub fn generate_constraint_table() -> ConstraintTable {
-  let mut ct = ConstraintTable::default();
-
-  let mut current_constraint_row = 0;
-
-  for row_idx in 0..9 {
-    for col_idx in 0..9 {
-      for value in 1..10 {
-        // Here we need to get the correct row from the constraint table as a slice
-        // Then we need to create a board with the specified choice,
+/// Generates the constraint table of boards shaped like `shape`, whose
+/// values are ignored, with a column for every value in each of `regions`
+pub fn generate_constraint_table(
+  shape: &Board,
+  regions: &[Region],
+) -> ConstraintTable {
+  let size = shape.size();
+  let mut table = Vec::with_capacity(choice_count(size));
+
+  // The board holds one choice at a time, the choice is cleared again once
+  // its row has been filled
+  let mut board = shape.cleared();
+
+  for row_idx in 0..size {
+    for col_idx in 0..size {
+      for value in 1..=size as i32 {
+        // Here we need to create a board with the specified choice,
         //  - eg for the first index that would be a board with a 1 at 0, 0
-        // Then we need to call `fill_constraint_table_row` with the constraint table slice and the board representing the choice
-        let constraint_row = &mut ct.table[current_constraint_row];
-        let mut board = Board::new();
+        // Then we need to call `fill_constraint_table_row` with the constraint table row and the board representing the choice
+        let mut constraint_row = ColumnSet::new();
         board.set(col_idx, row_idx, value);
-        fill_constraint_table_row(&board, constraint_row);
-        current_constraint_row += 1;
-      }
-    }
-  }
-
-  return ct;
-}
-
-fn find_unsatisfied_constraint(
-  constraint_table: &[[bool; 324]; 729],
-  solution_set: &HashSet<usize>,
-) -> Option<usize> {
-  if solution_set.len() == 0 {
-    return Some(0);
-  }
-
-  for col_idx in 0..324 {
-    let mut column_satisfied = false;
-    for &solution_idx in solution_set {
-      let solution_cell = constraint_table[solution_idx][col_idx];
-      if solution_cell {
-        column_satisfied = true;
-        break;
-      }
-    }
-
-    if !column_satisfied {
-      return Some(col_idx);
-    }
-  }
-
-  None
-}
-
-fn find_satisfying_rows(
-  ct: &[[bool; 324]; 729],
-  hidden_rows: &HashSet<usize>,
-  column_to_satisfy: usize,
-) -> Vec<usize> {
-  let mut rows = vec![];
-  for (row_index, row) in ct.iter().enumerate() {
-    if hidden_rows.contains(&row_index) {
-      continue;
-    }
-
-    if row[column_to_satisfy] {
-      rows.push(row_index);
-    }
-  }
-
-  rows
-}
-
-fn get_conflicting_rows(
-  ct: &[[bool; 324]; 729],
-  hidden_row_indexes: &HashSet<usize>,
-  selected_row_index: usize,
-) -> Vec<usize> {
-  let target_row = ct[selected_row_index];
-
-  let mut conflicting_rows = vec![];
-
-  for (constraint_table_row_index, row) in ct.iter().enumerate() {
-    if constraint_table_row_index == selected_row_index {
-      continue;
-    }
-
-    if hidden_row_indexes.contains(&constraint_table_row_index) {
-      continue;
-    }
-
-    for (column_index, val) in row.iter().enumerate() {
-      if *val && target_row[column_index] {
-        conflicting_rows.push(constraint_table_row_index);
-        break;
+        fill_constraint_table_row(&board, &mut constraint_row);
+        fill_region_constraints(&board, regions, &mut constraint_row);
+        board.set(col_idx, row_idx, 0);
+        table.push(constraint_row);
       }
     }
   }
 
-  conflicting_rows
+  ConstraintTable { table }
 }
 
 #[wasm_bindgen]
@@ -269,156 +231,209 @@ pub enum DecisionStrategy {
   First,
   Random,
 }
-/// REQUIRES: possible_rows is not empty
-/// Given an array of possible row_indexes, selects a
-/// row defined by strategy, then returns a tuple where
-/// the first element is the selected row and the second
-/// element is the remaining rows.
-fn pick_row(
-  mut possible_rows: Vec<usize>,
-  strategy: DecisionStrategy,
-) -> (usize, Vec<usize>) {
-  if possible_rows.is_empty() {
-    panic!("Cannot pick row from empty array")
-  }
 
-  let selected_row_index = match strategy {
-    DecisionStrategy::First => 0,
-    DecisionStrategy::Random => {
-      rand::thread_rng().gen_range(0..possible_rows.len())
-    }
-  };
-
-  let selected_row = possible_rows.swap_remove(selected_row_index);
-
-  (selected_row, possible_rows)
+/// How the next unsatisfied constraint is chosen at every step of the
+/// search.
+#[wasm_bindgen]
+#[derive(Copy, Clone, Debug)]
+pub enum ColumnHeuristic {
+  /// The unsatisfied constraint with the lowest index
+  FirstUnsatisfied,
+  /// The unsatisfied constraint with the fewest rows left that could
+  /// satisfy it, ties go to the lowest index
+  MinimumRemaining,
+  /// Like `MinimumRemaining`, but ties are broken at random
+  MinimumRemainingRandom,
 }
 
-/// Returns the last decision in the list that has potential rows
-/// Modifies decisions in place, removing all decisions with no
-/// potential rows and removing the last decision that has potential
-/// rows
-/// If it returns none, then that means there are no more decisions to pop
-fn get_last_decision(decisions: &mut Vec<Decision>) -> Option<Vec<Decision>> {
-  let mut popped_decisions: Vec<Decision> = vec![];
-  loop {
-    let decision = match decisions.pop() {
-      Some(d) => d,
-      None => return None,
-    };
-
-    let has_potential_rows = !decision.potential_rows.is_empty();
-
-    popped_decisions.push(decision);
-
-    if has_potential_rows {
-      break;
-    }
+/// The random number generator behind every random choice the solver makes.
+/// ChaCha produces the same numbers on every platform, so a seed gives the
+/// same boards natively and in wasm.
+pub(crate) type SolverRng = ChaCha8Rng;
+
+/// Seeds the solver's generator, from the operating system's entropy when
+/// no seed is passed
+pub(crate) fn solver_rng(seed: Option<u64>) -> SolverRng {
+  match seed {
+    Some(seed) => SolverRng::seed_from_u64(seed),
+    None => SolverRng::from_entropy(),
   }
+}
 
-  Some(popped_decisions)
+/// Returns a random index below `len`. Samples a `u64` rather than a
+/// `usize`, which is 32 bits in wasm and would draw different numbers there.
+pub(crate) fn random_index(rng: &mut SolverRng, len: usize) -> usize {
+  rng.gen_range(0..len as u64) as usize
 }
 
-fn backtrack(
-  decisions: &mut Vec<Decision>,
-  hidden_rows: &mut HashSet<usize>,
-  solution_set: &mut HashSet<usize>,
-  decision_strategy: DecisionStrategy,
-) -> (usize, Vec<usize>) {
-  let mut popped_decisions = match get_last_decision(decisions) {
-    Some(popped_ds) => popped_ds,
-    None => {
-      println!("hidden rows: {:?}", hidden_rows);
-      panic!("There are no decisions left to undo, but there is also no possible choice of row. Cannot proceed, exiting")
-    }
-  };
-  for decision in &popped_decisions {
-    // Remove selected row from solution set
-    solution_set.remove(&decision.selected_row);
-    // Remove selected row from hidden rows
-    hidden_rows.remove(&decision.selected_row);
-
-    // Unhide the rows that were hidden from this decision
-    let conflicting_rows: HashSet<usize> = decision
-      .rows_conflicting_with_selected_row
-      .iter()
-      .copied()
-      .collect();
-    let _extracted_elements: Vec<_> = hidden_rows
-      .extract_if(|v| conflicting_rows.contains(v))
-      .collect();
+/// Given how many rows of a column are left to try, and how many have been
+/// tried already, selects the position of the next row to try as defined
+/// by strategy. Returns `None` if there are no rows left.
+///
+/// First tries the top row, then the rest from the bottom up.
+pub(crate) fn pick_row(
+  remaining: usize,
+  tried: usize,
+  strategy: DecisionStrategy,
+  rng: &mut SolverRng,
+) -> Option<usize> {
+  if remaining == 0 {
+    return None;
   }
 
-  let popped_decision = match popped_decisions.pop() {
-    Some(d) => d,
-    None => panic!(
-      "If we are here there should have been at least one popped decision"
-    ),
+  let position = match strategy {
+    DecisionStrategy::First if tried == 0 => 0,
+    DecisionStrategy::First => remaining - 1,
+    DecisionStrategy::Random => random_index(rng, remaining),
   };
 
-  pick_row(popped_decision.potential_rows, decision_strategy)
+  Some(position)
 }
 
-fn map_solution_set_to_board(solution_set: &HashSet<usize>) -> Board {
-  // every cell occupies 9 entries inthe table
-  // to get the value from an index do: (index % 9) + 1
-  // to get the coordinates from an index do (index / 9) = x, where the row = x/9 and the column = x%9
-  let mut placements: Vec<(usize, usize, i32)> = vec![];
-
-  for index in solution_set {
-    let value: i32 = (index % 9 + 1) as i32;
-    let sequential_cell_index = index / 9;
-    let cell_x = sequential_cell_index % 9;
-    let cell_y = sequential_cell_index / 9;
-    placements.push((cell_x, cell_y, value));
+/// Maps the constraint table rows of a solution back to a board shaped like
+/// `shape`
+pub(crate) fn map_solution_set_to_board(
+  solution_set: &[usize],
+  shape: &Board,
+) -> Board {
+  // every cell occupies `size` entries in the table
+  // to get the value from an index do: (index % size) + 1
+  // to get the coordinates from an index do (index / size) = x, where the row = x/size and the column = x%size
+  let mut board = shape.cleared();
+  let size = board.size();
+
+  for &index in solution_set {
+    let value: i32 = (index % size + 1) as i32;
+    let sequential_cell_index = index / size;
+    let cell_x = sequential_cell_index % size;
+    let cell_y = sequential_cell_index / size;
+    board.set(cell_x, cell_y, value);
   }
 
-  let mut board = Board::new();
+  board
+}
 
-  for p in placements {
-    board.set(p.0, p.1, p.2);
+/// The value each cell of a board with `size` cells on each side holds once
+/// the rows are chosen, in row order. Empty cells hold 0.
+pub(crate) fn placed_values(selected_rows: &[usize], size: usize) -> Vec<u32> {
+  // Every row is a value in a cell, `cell * size + value - 1`
+  let mut values = vec![0; size * size];
+  for &row_idx in selected_rows {
+    values[row_idx / size] = (row_idx % size + 1) as u32;
   }
 
-  board
+  values
 }
 
-fn map_board_to_solution_set(board: &Board) -> HashSet<usize> {
+/// The values an empty cell of a board with `size` cells on each side could
+/// still hold, from smallest to largest
+pub(crate) fn candidates(
+  cell: usize,
+  size: usize,
+  is_row_visible: &dyn Fn(usize) -> bool,
+) -> Vec<u32> {
+  (1..=size as u32)
+    .filter(|&value| is_row_visible(cell * size + value as usize - 1))
+    .collect()
+}
+
+fn map_board_to_solution_set(board: &Board) -> Result<RowSet, SolveError> {
   // iterate through each cell in the board and get it's corresponding constraint table row
-  let mut solution_set: HashSet<usize> = HashSet::new();
+  let mut solution_set = RowSet::new();
+  let size = board.size();
 
-  for row_index in 0..9 {
-    for column_index in 0..9 {
+  for row_index in 0..size {
+    for column_index in 0..size {
       let cell = board.get(column_index, row_index);
       if cell == 0 {
         continue;
       }
+      if !(1..=size as i32).contains(&cell) {
+        return Err(SolveError::InvalidValue {
+          x: column_index,
+          y: row_index,
+          value: cell,
+        });
+      }
       let constraint_table_row =
-        (row_index * 9 * 9) + (column_index * 9) + cell as usize - 1;
+        (row_index * size * size) + (column_index * size) + cell as usize - 1;
       solution_set.insert(constraint_table_row);
     }
   }
 
-  solution_set
+  Ok(solution_set)
 }
 
-fn generate_initial_state(
-  board: Option<Board>,
-  constraint_table: &[[bool; 324]; 729],
-) -> (HashSet<usize>, HashSet<usize>) {
-  let initial_solution_set = match board {
-    Some(board) => map_board_to_solution_set(&board),
-    None => HashSet::new(),
-  };
-  let initial_hidden_rows = initial_solution_set.iter().fold(
-    HashSet::new(),
-    |mut accumulator, row_index| {
-      let conflicting_rows =
-        get_conflicting_rows(&constraint_table, &accumulator, *row_index);
-      accumulator.extend(conflicting_rows);
-      accumulator
-    },
+/// Maps the starting board to its constraint table rows, after checking
+/// every value is valid and no two values conflict, in the board's rows,
+/// columns and boxes, in any of `regions` or across a dot of the variants
+pub(crate) fn map_starting_board(
+  board: &Board,
+  regions: &[Region],
+  variants: &Variants,
+) -> Result<Vec<usize>, SolveError> {
+  let given_rows = map_board_to_solution_set(board)?;
+
+  let mut conflicts = board.validate();
+  conflicts.extend(board.validate_regions(regions));
+  conflicts.extend(variants.conflicts(board));
+  if !conflicts.is_empty() {
+    return Err(SolveError::ContradictoryGivens(conflicts));
+  }
+
+  Ok(given_rows.iter().collect())
+}
+
+/// The constraint table of boards shaped like `shape` as an exact cover
+/// problem, with one row for every choice of value in a cell. The primary
+/// columns of the rules follow the regions with as many cells as values,
+/// then come the columns of the other regions and the secondary columns of
+/// the rules. Returns an error if a rule's columns don't fit the board.
+pub(crate) fn generate_exact_cover(
+  shape: &Board,
+  regions: &[Region],
+  rules: &[Rc<dyn VariantRule>],
+) -> Result<ExactCover, SolveError> {
+  let constraint_table = generate_constraint_table(shape, regions);
+  let rule_columns = rule_columns(shape, rules)?;
+
+  let size = shape.size();
+  let partial_region_count = regions
+    .iter()
+    .filter(|region| region.cells.len() < size)
+    .count();
+  let full_region_count = regions.len() - partial_region_count;
+  let full_regions_end = constraint_count(size) + full_region_count * size;
+  let rule_secondary_start =
+    full_regions_end + rule_columns.primary_count + partial_region_count * size;
+  let mut exact_cover = ExactCover::with_secondary_columns(
+    full_regions_end + rule_columns.primary_count,
+    partial_region_count * size + rule_columns.secondary_count,
   );
-  (initial_solution_set, initial_hidden_rows)
+  for (constraint_row, (rule_primary, rule_secondary)) in
+    constraint_table.table.iter().zip(rule_columns.rows)
+  {
+    // The columns of regions with fewer cells than values move up to make
+    // room for the primary columns of the rules
+    let columns: Vec<usize> = constraint_row
+      .iter()
+      .map(|column| match column < full_regions_end {
+        true => column,
+        false => column + rule_columns.primary_count,
+      })
+      .chain(rule_primary.iter().map(|column| full_regions_end + column))
+      .chain(
+        rule_secondary
+          .iter()
+          .map(|column| rule_secondary_start + column),
+      )
+      .collect();
+    exact_cover
+      .add_row(&columns)
+      .expect("Constraint table columns are below the constraint count");
+  }
+
+  Ok(exact_cover)
 }
 
 #[wasm_bindgen]
@@ -426,102 +441,117 @@ pub fn launch_algorithm_x(
   starting_board: Option<Board>,
   decision_strategy: Option<DecisionStrategy>,
   desired_solutions: Option<usize>,
-) -> Vec<Board> {
+  column_heuristic: Option<ColumnHeuristic>,
+  seed: Option<u64>,
+  variants: Option<Variants>,
+) -> Result<Vec<Board>, SolveError> {
   // Convert to exact cover problem
 
-  // Constraints:
+  // Constraints, for a 9x9 board:
   //  - all rows must contain 1-9 (81)
   //  - all columns must contain 1-9 (81)
   //  - each subgrid must contain 1-9 (81)
   //  - each cell must contain a value (81)
   // (81 + 81 + 81 + 81) = 324 constraints
 
-  // Choices:
+  // Choices, for a 9x9 board:
   //  - each cell can be 1-9 (9)
   //  - there are 81 cells (81)
   // 9 * 81 = 729 choices
 
-  let decision_strategy = decision_strategy.unwrap_or(DecisionStrategy::Random);
+  // Other board sizes follow the same pattern with `size` in place of 9,
+  // and every region a variant adds is another `size` constraints
+
   let desired_solutions = desired_solutions.unwrap_or(1);
-  let mut solutions: Vec<Board> = vec![];
-
-  let constraint_table = generate_constraint_table().table;
-
-  let (initial_solution_set, initial_hidden_rows) =
-    generate_initial_state(starting_board, &constraint_table);
-
-  let mut solution_set: HashSet<usize> = initial_solution_set;
-  let mut hidden_rows: HashSet<usize> = initial_hidden_rows;
-
-  let mut decisions: Vec<Decision> = vec![];
-
-  loop {
-    // Step 1: Pick an unsatisifed constraint
-    let unsatisfied_column_idx =
-      match find_unsatisfied_constraint(&constraint_table, &solution_set) {
-        Some(index) => index,
-        None => {
-          solutions.push(map_solution_set_to_board(&solution_set));
-          if solutions.len() >= desired_solutions {
-            return solutions;
-          }
-          backtrack(
-            &mut decisions,
-            &mut hidden_rows,
-            &mut solution_set,
-            decision_strategy,
-          );
-          find_unsatisfied_constraint(&constraint_table, &solution_set).unwrap()
-        }
-      };
-
-    // Step 2: Get all the rows we can pick to satisfy the constraint
-    // If satisfying rows is empty:
-    //   1. Pop the last decision. If the popped decision has no
-    //      potential_rows we can select, pop another. If we pop
-    //      all decisions without finding another potential row
-    //      we could have selected. Hard fail, the board is
-    //      unsolvable.
-    //   2. Iterate through all popped decisions, remove the
-    //      selected row from the solution set, and hidden set.
-    //      Also remove each decision's conflicting rows from the
-    //      hidden rows set.
-    //   3. Select the next potential row from the popped decision
-    //   4. Calculate all the conflicting rows from the newly picked row
-    //      and return those two values as the tuple
-    //      (selected_row, possible_rows) to be turned into the next
-    //      decision. (possible rows is the calculated conflicting rows)
-    let satisfying_rows = find_satisfying_rows(
-      &constraint_table,
-      &hidden_rows,
-      unsatisfied_column_idx,
-    );
-
-    let (selected_row, possible_rows) = match satisfying_rows.is_empty() {
-      false => pick_row(satisfying_rows, decision_strategy),
-      true => backtrack(
-        &mut decisions,
-        &mut hidden_rows,
-        &mut solution_set,
-        decision_strategy,
-      ),
-    };
 
-    // Step 3: Add the row to the solution set
-    solution_set.insert(selected_row);
+  let mut solutions = SolutionIter::new(
+    starting_board,
+    decision_strategy,
+    column_heuristic,
+    seed,
+    variants,
+  )?;
+  let first_solution = solutions.next().ok_or(SolveError::Unsolvable)?;
+
+  Ok(
+    std::iter::once(first_solution)
+      .chain(solutions)
+      .take(desired_solutions)
+      .collect(),
+  )
+}
 
-    // Step 4: Remove any rows that satisfy any of the constraitns satisfied by the chosen row
-    let conflicting_rows =
-      get_conflicting_rows(&constraint_table, &hidden_rows, selected_row);
+/// Starts the search for the solutions of the board under the variants,
+/// after checking the board's values and that the variants fit on it
+pub(crate) fn search_board(
+  board: &Board,
+  variants: Option<Variants>,
+  decision_strategy: DecisionStrategy,
+  column_heuristic: ColumnHeuristic,
+  seed: Option<u64>,
+) -> Result<ExactCoverIter, SolveError> {
+  let variants = variants.unwrap_or_default();
+  let regions = variants.regions(board)?;
+  let given_rows = map_starting_board(board, &regions, &variants)?;
+
+  let mut exact_cover =
+    generate_exact_cover(board, &regions, &variants.rules())?;
+  exact_cover.set_decision_strategy(decision_strategy);
+  exact_cover.set_column_heuristic(column_heuristic);
+  exact_cover.set_seed(seed);
+
+  let mut solutions = exact_cover.solutions(&given_rows)?;
+  for pruner in variants.pruners(board) {
+    solutions.add_pruner(pruner);
+  }
 
-    hidden_rows.extend(conflicting_rows.iter().copied());
+  Ok(solutions)
+}
 
-    let decision = Decision {
-      selected_row: selected_row,
-      potential_rows: possible_rows,
-      rows_conflicting_with_selected_row: conflicting_rows,
-    };
+/// Counts the solutions of the board under the variants, up to `limit`.
+/// Runs the same search as `launch_algorithm_x`, but never builds a board
+/// for a solution.
+#[wasm_bindgen]
+pub fn count_solutions(
+  board: &Board,
+  limit: usize,
+  variants: Option<Variants>,
+) -> Result<usize, SolveError> {
+  let mut solutions = search_board(
+    board,
+    variants,
+    DecisionStrategy::First,
+    ColumnHeuristic::MinimumRemaining,
+    None,
+  )?;
+
+  Ok(solutions.count_up_to(limit))
+}
 
-    decisions.push(decision);
-  }
+/// Checks whether the board has exactly one solution under the variants,
+/// stopping as soon as a second solution is found
+pub fn check_uniqueness(
+  board: &Board,
+  variants: Option<Variants>,
+) -> Result<Uniqueness, SolveError> {
+  let solutions = search_board(
+    board,
+    variants,
+    DecisionStrategy::First,
+    ColumnHeuristic::MinimumRemaining,
+    None,
+  )?;
+  let mut boards =
+    solutions.map(|solution| map_solution_set_to_board(&solution, board));
+  let uniqueness = match (boards.next(), boards.next()) {
+    (None, _) => Uniqueness::NoSolution,
+    (Some(solution), None) => Uniqueness::Unique(Box::new(solution)),
+    (Some(first), Some(second)) => Uniqueness::Multiple {
+      differences: first.differences(&second),
+      first: Box::new(first),
+      second: Box::new(second),
+    },
+  };
+
+  Ok(uniqueness)
 }
@@ -1,12 +1,73 @@
 use super::*;
+use crate::board::BrokenConstraint;
+use crate::board::{Cell, Conflict};
+use crate::killer::Cage;
+use crate::kropki::Dot;
+use crate::lines::{Arrow, Thermometer};
+use crate::sandwich::{generate_sandwich_puzzle, sandwiches_of, Sandwich};
 use std::collections::HashSet;
 
+/// Builds a board from 81 digits read left to right, top to bottom. Zeros
+/// are empty cells.
+fn board_from_str(cells: &str) -> Board {
+  let mut board = Board::new();
+  for (index, digit) in cells.chars().enumerate() {
+    board.set(index % 9, index / 9, digit.to_digit(10).unwrap() as i32);
+  }
+
+  board
+}
+
+/// Asserts the solution is a complete, valid board that keeps every value
+/// of the puzzle
+fn assert_solves(puzzle: &Board, solution: &Board) {
+  for y in 0..9 {
+    for x in 0..9 {
+      let given = puzzle.get(x, y);
+      assert!(given == 0 || given == solution.get(x, y));
+    }
+  }
+
+  for idx in 0..9 {
+    let mut row: Vec<i32> = solution.get_row(idx).to_vec();
+    let mut column: Vec<i32> = solution.get_column(idx).to_vec();
+    let mut sub_grid: Vec<i32> = (0..9)
+      .map(|cell| {
+        solution.get((idx % 3) * 3 + cell % 3, (idx / 3) * 3 + cell / 3)
+      })
+      .collect();
+
+    row.sort_unstable();
+    column.sort_unstable();
+    sub_grid.sort_unstable();
+    assert_eq!(row, (1..10).collect::<Vec<i32>>());
+    assert_eq!(column, (1..10).collect::<Vec<i32>>());
+    assert_eq!(sub_grid, (1..10).collect::<Vec<i32>>());
+  }
+}
+
+/// Unpacks a constraint table row so sections of it can be compared
+fn to_bools(constraint_row: &ColumnSet) -> Vec<bool> {
+  let mut bools = vec![false; constraint_count(9)];
+  for index in constraint_row.iter() {
+    bools[index] = true;
+  }
+
+  bools
+}
+
 #[test]
 fn on_an_empty_board_it_returns_the_correct_constraints() {
   let board = Board::new();
 
-  let mut constraint_row = [false; 324];
-  fill_row_constraints(&board, &mut constraint_row);
+  let mut constraint_row = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Row,
+    &RowRule,
+    &board,
+    &mut constraint_row,
+  );
+  let constraint_row = to_bools(&constraint_row);
 
   let all_false = constraint_row
     .iter()
@@ -23,8 +84,14 @@ fn correctly_resolves_first_row() {
   board.set(2, 0, 3);
   board.set(3, 0, 4);
 
-  let mut constraint_row = [false; 324];
-  fill_row_constraints(&board, &mut constraint_row);
+  let mut constraint_row = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Row,
+    &RowRule,
+    &board,
+    &mut constraint_row,
+  );
+  let constraint_row = to_bools(&constraint_row);
 
   let first_four_true = &constraint_row[0..4]
     .iter()
@@ -50,8 +117,14 @@ fn correctly_resolves_two_rows() {
   board.set(2, 5, 5);
   board.set(3, 5, 8);
 
-  let mut constraint_row = [false; 324];
-  fill_row_constraints(&board, &mut constraint_row);
+  let mut constraint_row = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Row,
+    &RowRule,
+    &board,
+    &mut constraint_row,
+  );
+  let constraint_row = to_bools(&constraint_row);
 
   // fourth row will be from index [3*9, 4*9)
   let fourth_row_section_of_constraints = &constraint_row[27..36];
@@ -71,8 +144,14 @@ fn correctly_resolves_two_rows() {
 #[test]
 fn test_no_false_positives() {
   let board = Board::new();
-  let mut constraint_column = [false; 324];
-  fill_column_constraints(&board, &mut constraint_column);
+  let mut constraint_column = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Column,
+    &ColumnRule,
+    &board,
+    &mut constraint_column,
+  );
+  let constraint_column = to_bools(&constraint_column);
   let all_false = constraint_column
     .iter()
     .all(|element| -> bool { element == &false });
@@ -86,10 +165,16 @@ fn test_detects_first_column_constraints() {
   board.set(0, 0, 8);
   board.set(0, 8, 1);
 
-  let mut constraint_row = [false; 324];
-  fill_column_constraints(&board, &mut constraint_row);
+  let mut constraint_row = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Column,
+    &ColumnRule,
+    &board,
+    &mut constraint_row,
+  );
+  let constraint_row = to_bools(&constraint_row);
 
-  let offset = ConstraintType::Column.get_offset();
+  let offset = ConstraintType::Column.get_offset(9);
   let first_col_slice = &constraint_row[offset..(offset + 9)];
 
   let expected_first_column_constraints =
@@ -108,10 +193,16 @@ fn test_detects_two_random_column_constraints() {
   board.set(8, 7, 6);
   board.set(8, 8, 7);
 
-  let mut column_constraints = [false; 324];
-  fill_column_constraints(&board, &mut column_constraints);
+  let mut column_constraints = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Column,
+    &ColumnRule,
+    &board,
+    &mut column_constraints,
+  );
+  let column_constraints = to_bools(&column_constraints);
 
-  let column_two_offset = ConstraintType::Column.get_offset() + (9 * 2);
+  let column_two_offset = ConstraintType::Column.get_offset(9) + (9 * 2);
   let column_two_constraints =
     &column_constraints[column_two_offset..(column_two_offset + 9)];
 
@@ -119,7 +210,7 @@ fn test_detects_two_random_column_constraints() {
     [true, true, false, false, true, false, false, false, false];
   assert_eq!(column_two_constraints, expected_column_two_constraints);
 
-  let column_eight_offset = ConstraintType::Column.get_offset() + (9 * 8);
+  let column_eight_offset = ConstraintType::Column.get_offset(9) + (9 * 8);
   let column_eight_constraints =
     &column_constraints[column_eight_offset..(column_eight_offset + 9)];
 
@@ -167,9 +258,15 @@ fn test_detects_sub_grid_constraints() {
   // | 8 | 7 | 4 |
   // | 3 | 9 | 9 |
 
-  let top_left_offset = ConstraintType::SubGrid.get_offset();
-  let mut sub_grid_constraints = [false; 324];
-  fill_sub_grid_constraints(&board, &mut sub_grid_constraints);
+  let top_left_offset = ConstraintType::SubGrid.get_offset(9);
+  let mut sub_grid_constraints = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::SubGrid,
+    &SubGridRule,
+    &board,
+    &mut sub_grid_constraints,
+  );
+  let sub_grid_constraints = to_bools(&sub_grid_constraints);
 
   let top_left_constraints =
     &sub_grid_constraints[top_left_offset..(top_left_offset + 9)];
@@ -177,14 +274,14 @@ fn test_detects_sub_grid_constraints() {
     [true, true, false, true, true, false, false, false, true];
   assert_eq!(top_left_constraints, expected_top_left_constraints);
 
-  let middle_sub_grid_offset = ConstraintType::SubGrid.get_offset() + (9 * 4);
+  let middle_sub_grid_offset = ConstraintType::SubGrid.get_offset(9) + (9 * 4);
   let middle_constraints =
     &sub_grid_constraints[middle_sub_grid_offset..(middle_sub_grid_offset + 9)];
   let expected_middle_constraints =
     [true, false, true, true, true, false, false, false, true];
   assert_eq!(middle_constraints, expected_middle_constraints);
 
-  let bottom_left_offset = ConstraintType::SubGrid.get_offset() + (9 * 8);
+  let bottom_left_offset = ConstraintType::SubGrid.get_offset(9) + (9 * 8);
   let bottom_left_constraints =
     &sub_grid_constraints[bottom_left_offset..(bottom_left_offset + 9)];
   let expected_bottom_left_constraints =
@@ -294,11 +391,32 @@ fn test_detects_all_constraints_satisfied() {
   board.set(7, 8, 1);
   board.set(8, 8, 9);
 
-  let mut constraints_row = [false; 324];
-  fill_row_constraints(&board, &mut constraints_row);
-  fill_column_constraints(&board, &mut constraints_row);
-  fill_sub_grid_constraints(&board, &mut constraints_row);
-  fill_existence_constraints(&board, &mut constraints_row);
+  let mut constraints_row = ColumnSet::new();
+  fill_rule_constraints(
+    ConstraintType::Row,
+    &RowRule,
+    &board,
+    &mut constraints_row,
+  );
+  fill_rule_constraints(
+    ConstraintType::Column,
+    &ColumnRule,
+    &board,
+    &mut constraints_row,
+  );
+  fill_rule_constraints(
+    ConstraintType::SubGrid,
+    &SubGridRule,
+    &board,
+    &mut constraints_row,
+  );
+  fill_rule_constraints(
+    ConstraintType::Existence,
+    &ExistenceRule,
+    &board,
+    &mut constraints_row,
+  );
+  let constraints_row = to_bools(&constraints_row);
 
   let all_true = constraints_row
     .iter()
@@ -318,40 +436,40 @@ fn test_generate_constraint_table() {
   // Place 9 at 0,0 |     false     |    false      |      true     | ... |    false      | ... |
   // Place 1 at 1,0 |     true      |    false      |      false    | ... |    false      | ... |
   // ...
-  let ct = generate_constraint_table();
+  let ct = generate_constraint_table(&Board::new(), &[]);
 
   // =====================================
   // For choice of placing 4 at 0,1
   // =====================================
-  let first_choice_row = &ct.table[9 * 9 * 1 + 4 - 1];
+  let first_choice_row = &to_bools(&ct.table[9 * 9 + 4 - 1]);
   // Asserting that the "Row 1 has a 4" constraint is true for choice "Place 4 at 0,1"
   assert!(first_choice_row[9 + 4 - 1]);
   // Asserting that the "Column 0 has a 4" constraint is true for choice "Place 4 at 0,1"
-  assert!(first_choice_row[ConstraintType::Column.get_offset() + 4 - 1]);
+  assert!(first_choice_row[ConstraintType::Column.get_offset(9) + 4 - 1]);
   // Asserting that the "Subgrid 0 has a 4" constraint is true for choice "Place 4 at 0,1"
-  assert!(first_choice_row[ConstraintType::SubGrid.get_offset() + 4 - 1]);
+  assert!(first_choice_row[ConstraintType::SubGrid.get_offset(9) + 4 - 1]);
   // Asserting that the "Cell 0,1 contains a value" constraint is true for choice "Place 4 at 0,1"
-  assert!(first_choice_row[ConstraintType::Existence.get_offset() + 9]);
+  assert!(first_choice_row[ConstraintType::Existence.get_offset(9) + 9]);
   // Asserting that all other constraints are false
   assert!(first_choice_row.iter().filter(|var| **var).count() == 4);
 
   // =====================================
   // For choice of placing 7 at 8, 4
   // =====================================
-  let second_choice_row = &ct.table[(9 * 8) + (9 * 9 * 4) + 7 - 1];
+  let second_choice_row = &to_bools(&ct.table[(9 * 8) + (9 * 9 * 4) + 7 - 1]);
   // Asserting that the "Row 4 has a 7" constraint is true for choice "Place 7 at 8, 4"
   assert!(second_choice_row[(9 * 4) + 7 - 1]);
   // Asserting that the "Column 8 has a 7" constraint is true for choice "Place 7 at 8,4"
   assert!(
-    second_choice_row[ConstraintType::Column.get_offset() + (9 * 8) + 7 - 1]
+    second_choice_row[ConstraintType::Column.get_offset(9) + (9 * 8) + 7 - 1]
   );
   // Asserting that the "Subgrid 5 has a 7" constraint is true for choice "Place 7 at 8,4"
   assert!(
-    second_choice_row[ConstraintType::SubGrid.get_offset() + (9 * 5) + 7 - 1]
+    second_choice_row[ConstraintType::SubGrid.get_offset(9) + (9 * 5) + 7 - 1]
   );
   // Asserting that the "Cell 8,4 has a value" constraint is true for choice "Place 7 at 8,4"
   assert!(
-    second_choice_row[ConstraintType::Existence.get_offset() + (4 * 9) + 8]
+    second_choice_row[ConstraintType::Existence.get_offset(9) + (4 * 9) + 8]
   );
   // Asserting that all other constraints are false
   assert!(second_choice_row.iter().filter(|var| **var).count() == 4);
@@ -359,227 +477,43 @@ fn test_generate_constraint_table() {
   // =====================================
   // For choice of placting 1 at 3,0
   // =====================================
-  let third_choice_row = &ct.table[(9 * 3) + 1 - 1];
+  let third_choice_row = &to_bools(&ct.table[(9 * 3) + 1 - 1]);
   // Assert that the "Row 0 has a 1" constraint is true for "Place 1 at 3,0"
   assert!(third_choice_row[1 - 1]);
   // Assert that the "Column 3 has a 1" constraint is true for "Place 1 at 3,0"
   assert!(
-    third_choice_row[ConstraintType::Column.get_offset() + (9 * 3) + 1 - 1]
+    third_choice_row[ConstraintType::Column.get_offset(9) + (9 * 3) + 1 - 1]
   );
   // Assert that the "Subgrid 1 has a 1" constraint is true for "Place 1 at 3,0"
-  assert!(third_choice_row[ConstraintType::SubGrid.get_offset() + 9 + 1 - 1]);
+  assert!(third_choice_row[ConstraintType::SubGrid.get_offset(9) + 9 + 1 - 1]);
   // Assert that all other constraints are false
   assert!(third_choice_row.iter().filter(|var| **var).count() == 4);
 }
 
-#[test]
-fn test_find_satisfying_row_scenarios() {
-  // 1) No row satisfies the column → None
-  let mut ct = [[false; 324]; 729];
-  let col = 137;
-  let hidden = HashSet::<usize>::new();
-  assert_eq!(find_satisfying_rows(&ct, &hidden, col), vec![]);
-
-  // 2) A single row satisfies → that row index
-  ct[10][col] = true;
-  assert_eq!(find_satisfying_rows(&ct, &hidden, col), vec![10]);
-
-  // 3) An earlier row also satisfies, but it’s hidden → return next visible (10)
-  ct[3][col] = true;
-  let mut hidden = HashSet::new();
-  hidden.insert(3);
-  assert_eq!(find_satisfying_rows(&ct, &hidden, col), vec![10]);
-
-  // 4) If earlier row isn’t hidden → earliest match (3) wins
-  let hidden = HashSet::<usize>::new();
-  assert_eq!(find_satisfying_rows(&ct, &hidden, col).first(), Some(&3));
-
-  // 5) Hide both earlier matches; ensure it finds a later satisfying row
-  let mut hidden = HashSet::new();
-  hidden.insert(3);
-  hidden.insert(10);
-  ct[400][col] = true;
-  assert_eq!(find_satisfying_rows(&ct, &hidden, col).first(), Some(&400));
-
-  // 6) Boundary columns: 0 and 241
-  let mut ct2 = [[false; 324]; 729];
-
-  ct2[5][0] = true;
-  assert_eq!(
-    find_satisfying_rows(&ct2, &HashSet::new(), 0).first(),
-    Some(&5)
-  );
-
-  ct2[5][0] = false;
-  ct2[25][241] = true;
-  assert_eq!(
-    find_satisfying_rows(&ct2, &HashSet::new(), 241).first(),
-    Some(&25)
-  );
-}
-
-#[test]
-fn test_get_conflicting_rows() {
-  const N_COLS: usize = 324;
-  const N_ROWS: usize = 729;
-
-  fn row_with(cols_true: &[usize]) -> [bool; N_COLS] {
-    let mut row = [false; N_COLS];
-    for &c in cols_true {
-      row[c] = true;
-    }
-    row
-  }
-
-  // Build a full constraint table with a few meaningful rows, rest all-false.
-  let mut ct = [[false; N_COLS]; N_ROWS];
-
-  // Selected row: true at columns 0, 5, 241
-  ct[10] = row_with(&[0, 5, 241]);
-
-  // Conflicting rows (share at least one true col with row 10)
-  ct[3] = row_with(&[5]); // shares 5
-  ct[7] = row_with(&[241]); // shares 241
-  ct[15] = row_with(&[0, 100]); // shares 0 (and some other)
-  ct[20] = row_with(&[0, 5, 241]); // shares many
-
-  // Non-conflicting rows
-  ct[8] = row_with(&[1, 2, 3]); // no overlap
-  ct[9] = row_with(&[]); // all false
-  ct[11] = row_with(&[6, 7]); // no overlap with 0,5,241
-
-  // Hidden rows: one conflicting row is hidden, one non-conflicting row hidden too
-  let mut hidden = HashSet::new();
-  hidden.insert(7); // would conflict, but hidden -> should be excluded
-  hidden.insert(8); // doesn't conflict anyway
-
-  // Act
-  let mut got = get_conflicting_rows(&ct, &hidden, 10);
-  got.sort_unstable();
-
-  // Expect: conflicting rows except selected (10) and hidden (7)
-  let mut expected = vec![3, 15, 20];
-  expected.sort_unstable();
-  assert_eq!(got, expected);
-
-  // Additional check: if we hide nothing, we should get 7 back too
-  let empty_hidden = HashSet::new();
-  let mut got2 = get_conflicting_rows(&ct, &empty_hidden, 10);
-  got2.sort_unstable();
-
-  let mut expected2 = vec![3, 7, 15, 20];
-  expected2.sort_unstable();
-  assert_eq!(got2, expected2);
-
-  // Additional check: selecting a row with no trues should yield no conflicts
-  // (since condition requires *val && target_row[column_index])
-  let got3 = get_conflicting_rows(&ct, &empty_hidden, 9);
-  assert!(got3.is_empty());
-}
-
 #[test]
 fn test_pick_row() {
-  // TODO: Assert that this panicks for an empty input
-  // Empty input -> Panic (both strategies)
-
-  // Single element -> always that element, remainder empty (both strategies)
-  let r = pick_row(vec![42], DecisionStrategy::First);
-  assert_eq!(r.0, 42);
-  assert!(r.1.is_empty());
-
-  let r = pick_row(vec![42], DecisionStrategy::Random);
-  assert_eq!(r.0, 42);
-  assert!(r.1.is_empty());
-
-  // Multiple elements, First strategy: selects index 0, remainder is original without that element
-  let input = vec![10, 20, 30, 40];
-  let (selected, remaining) = pick_row(input.clone(), DecisionStrategy::First);
-  assert_eq!(selected, 10);
-  assert_eq!(remaining.len(), input.len() - 1);
-
-  // Remaining should contain all originals except selected
-  let mut remaining_set: HashSet<usize> = remaining.into_iter().collect();
-  assert_eq!(remaining_set.len(), 3); // no duplicates introduced
-  assert!(remaining_set.remove(&20));
-  assert!(remaining_set.remove(&30));
-  assert!(remaining_set.remove(&40));
-  assert!(remaining_set.is_empty());
-
-  // Multiple elements, Random strategy:
-  // We can't assert which element was picked, but we can assert invariants.
-  let input = vec![1, 2, 3, 4, 5];
-  let (selected, remaining) = pick_row(input.clone(), DecisionStrategy::Random);
-  assert_eq!(remaining.len(), input.len() - 1);
-  assert!(input.contains(&selected));
-
-  // Selected + remaining should equal the original multiset (no loss/duplication)
-  let mut all: HashSet<usize> = remaining.into_iter().collect();
-  assert!(all.insert(selected)); // selected shouldn't already be in remaining
-  let input_set: HashSet<usize> = input.into_iter().collect();
-  assert_eq!(all, input_set);
-}
-
-#[test]
-fn test_backtracking() {
-  let mut decisions: Vec<Decision> = vec![
-    Decision {
-      selected_row: 0,
-      potential_rows: vec![1, 2, 3],
-      rows_conflicting_with_selected_row: vec![4, 5, 6],
-    },
-    Decision {
-      selected_row: 7,
-      potential_rows: vec![],
-      rows_conflicting_with_selected_row: vec![8, 9, 10],
-    },
-    Decision {
-      selected_row: 11,
-      potential_rows: vec![12],
-      rows_conflicting_with_selected_row: vec![13, 14, 15],
-    },
-  ];
-  let mut hidden_rows: HashSet<usize> =
-    HashSet::from([4, 5, 6, 8, 9, 10, 13, 14, 15]);
-  let mut solution_set: HashSet<usize> = HashSet::from([0, 7, 11]);
-
-  let (selected_row, potential_rows) = backtrack(
-    &mut decisions,
-    &mut hidden_rows,
-    &mut solution_set,
-    DecisionStrategy::First,
-  );
-
-  assert_eq!(selected_row, 12);
-  assert_eq!(potential_rows, vec![]);
-  assert_eq!(
-    decisions,
-    vec![
-      Decision {
-        selected_row: 0,
-        potential_rows: vec![1, 2, 3],
-        rows_conflicting_with_selected_row: vec![4, 5, 6]
-      },
-      Decision {
-        selected_row: 7,
-        potential_rows: vec![],
-        rows_conflicting_with_selected_row: vec![8, 9, 10]
-      },
-    ]
-  );
-  assert_eq!(hidden_rows, HashSet::from([4, 5, 6, 8, 9, 10]));
-  assert_eq!(solution_set, HashSet::from([0, 7]));
-
-  let (selected_row, potential_rows) = backtrack(
-    &mut decisions,
-    &mut hidden_rows,
-    &mut solution_set,
-    DecisionStrategy::First,
-  );
-  assert_eq!(selected_row, 1);
-  assert_eq!(potential_rows, vec![3, 2]);
-  assert_eq!(decisions, vec![]);
-  assert_eq!(hidden_rows, HashSet::new());
-  assert_eq!(solution_set, HashSet::new());
+  let mut rng = solver_rng(Some(0));
+
+  // No rows left -> None (both strategies)
+  assert_eq!(pick_row(0, 0, DecisionStrategy::First, &mut rng), None);
+  assert_eq!(pick_row(0, 3, DecisionStrategy::Random, &mut rng), None);
+
+  // Single row -> always that row (both strategies)
+  assert_eq!(pick_row(1, 0, DecisionStrategy::First, &mut rng), Some(0));
+  assert_eq!(pick_row(1, 2, DecisionStrategy::First, &mut rng), Some(0));
+  assert_eq!(pick_row(1, 0, DecisionStrategy::Random, &mut rng), Some(0));
+
+  // First strategy: the top row, then the rest from the bottom up
+  assert_eq!(pick_row(4, 0, DecisionStrategy::First, &mut rng), Some(0));
+  assert_eq!(pick_row(3, 1, DecisionStrategy::First, &mut rng), Some(2));
+  assert_eq!(pick_row(2, 2, DecisionStrategy::First, &mut rng), Some(1));
+
+  // Random strategy: we can't assert which row was picked, but it must be
+  // one of the remaining ones
+  for _ in 0..20 {
+    let position = pick_row(5, 0, DecisionStrategy::Random, &mut rng).unwrap();
+    assert!(position < 5);
+  }
 }
 
 #[test]
@@ -598,8 +532,7 @@ fn test_map_solution_set_to_board() {
   // - (9 * 9 * 1) + (9 * 3) + 4 - 1 = 111
   // - (9 * 9 * 2) + 5 - 1 = 166
   // - (9 * 9 * 3) + (9 * 3) + 9 - 1 = 278
-  let solution_set = HashSet::from([0, 111, 166, 278]);
-  let board = map_solution_set_to_board(&solution_set);
+  let board = map_solution_set_to_board(&[0, 111, 166, 278], &Board::new());
   board.print_board();
   assert!(board.get(0, 0) == 1);
   assert!(board.get(3, 1) == 4);
@@ -620,7 +553,7 @@ fn test_map_solution_set_to_board() {
 }
 
 #[test]
-fn test_generate_initial_state() {
+fn test_map_board_to_solution_set() {
   // This was tested by first running the algorithm
   // organically through 150 iterations, then
   // recording the board state and the algorithm state.
@@ -720,70 +653,15 @@ fn test_generate_initial_state() {
   board.set(8, 7, 0);
   board.set(8, 8, 0);
 
-  let generated_solution_set = map_board_to_solution_set(&board);
+  let generated_solution_set = map_board_to_solution_set(&board).unwrap();
   assert_eq!(
     generated_solution_set,
-    HashSet::from([
+    RowSet::from_iter([
       107, 305, 128, 360, 118, 205, 401, 236, 87, 80, 264, 226, 10, 70, 148,
       50, 156, 140, 216, 252, 346, 97, 20, 215, 175, 30, 108, 185, 60, 272,
       165, 322, 352, 326, 390, 292, 40, 244, 195, 284, 312, 0
     ])
   );
-
-  let constraint_table = generate_constraint_table().table;
-
-  let generated_hidden_rows = generated_solution_set.iter().fold(
-    HashSet::new(),
-    |mut accumulator, row_index| {
-      let conflicting_rows =
-        get_conflicting_rows(&constraint_table, &accumulator, *row_index);
-      accumulator.extend(conflicting_rows);
-      accumulator
-    },
-  );
-
-  assert_eq!(
-    generated_hidden_rows,
-    HashSet::from([
-      432, 29, 596, 278, 633, 398, 247, 492, 34, 306, 211, 338, 75, 476, 26,
-      184, 92, 196, 684, 499, 676, 577, 327, 359, 363, 697, 489, 425, 4, 361,
-      658, 228, 396, 255, 418, 378, 111, 114, 210, 671, 485, 290, 275, 529,
-      366, 11, 441, 288, 162, 267, 595, 329, 8, 274, 723, 459, 136, 251, 427,
-      235, 539, 455, 385, 367, 552, 121, 402, 315, 458, 42, 266, 39, 132, 707,
-      616, 129, 349, 380, 190, 523, 311, 382, 56, 442, 509, 405, 257, 393, 89,
-      403, 119, 634, 52, 207, 57, 695, 641, 253, 32, 546, 364, 356, 636, 443,
-      590, 53, 117, 137, 282, 212, 610, 76, 436, 220, 152, 664, 84, 454, 677,
-      516, 71, 113, 421, 462, 59, 74, 238, 553, 287, 362, 17, 482, 12, 246,
-      109, 139, 388, 19, 567, 303, 151, 90, 506, 145, 573, 570, 434, 295, 88,
-      300, 452, 342, 373, 689, 27, 314, 678, 654, 55, 383, 717, 276, 263, 431,
-      626, 45, 78, 104, 642, 54, 100, 112, 219, 103, 221, 213, 188, 245, 150,
-      583, 670, 249, 68, 391, 576, 519, 535, 258, 158, 394, 293, 384, 313, 168,
-      317, 72, 438, 386, 101, 445, 13, 110, 323, 187, 149, 631, 146, 28, 301,
-      6, 270, 357, 16, 234, 231, 600, 372, 85, 513, 155, 484, 649, 472, 153,
-      202, 254, 354, 728, 340, 637, 138, 483, 157, 669, 608, 161, 561, 681,
-      644, 243, 91, 347, 351, 343, 299, 120, 548, 397, 614, 374, 487, 550, 469,
-      515, 522, 514, 344, 83, 597, 224, 332, 166, 125, 540, 381, 467, 399, 309,
-      63, 18, 122, 471, 387, 594, 714, 480, 179, 433, 48, 556, 406, 73, 7, 289,
-      201, 651, 414, 486, 256, 404, 49, 98, 331, 533, 66, 62, 159, 222, 334,
-      426, 163, 508, 144, 675, 135, 423, 58, 698, 512, 192, 61, 281, 265, 727,
-      336, 674, 328, 1, 198, 99, 229, 408, 93, 350, 174, 134, 116, 147, 569,
-      194, 371, 102, 330, 368, 67, 232, 428, 37, 589, 2, 131, 22, 31, 79, 286,
-      241, 563, 106, 46, 164, 181, 603, 280, 44, 320, 277, 127, 718, 657, 248,
-      648, 588, 474, 337, 379, 172, 333, 646, 218, 262, 64, 607, 502, 171, 23,
-      464, 446, 369, 173, 725, 593, 587, 230, 691, 41, 496, 617, 3, 715, 279,
-      450, 395, 479, 297, 465, 204, 142, 35, 9, 495, 269, 209, 566, 712, 21,
-      400, 177, 273, 176, 160, 123, 186, 415, 82, 86, 661, 217, 214, 560, 197,
-      298, 291, 365, 650, 555, 437, 77, 407, 95, 193, 124, 51, 417, 259, 182,
-      302, 304, 668, 94, 318, 527, 261, 271, 14, 701, 199, 488, 36, 702, 283,
-      296, 451, 627, 688, 377, 621, 69, 180, 96, 237, 409, 141, 189, 169, 200,
-      310, 389, 448, 370, 466, 208, 308, 629, 545, 24, 115, 321, 565, 353, 227,
-      507, 203, 416, 647, 206, 65, 604, 335, 536, 710, 435, 15, 294, 355, 475,
-      183, 580, 392, 47, 722, 154, 191, 126, 223, 268, 239, 170, 319, 81, 260,
-      348, 242, 130, 233, 324, 250, 411, 133, 307, 325, 473, 316, 240, 358, 38,
-      43, 143, 167, 33, 708, 105, 620, 25, 285, 526, 568, 5, 178, 424, 345,
-      225, 685
-    ])
-  );
 }
 
 #[test]
@@ -880,7 +758,8 @@ fn test_no_unnecessary_backtracks() {
   board.set(8, 7, 1);
   board.set(8, 8, 0);
 
-  let solutions = launch_algorithm_x(Some(board), None, Some(1));
+  let solutions =
+    launch_algorithm_x(Some(board), None, Some(1), None, None, None).unwrap();
   let solution = solutions.first().unwrap();
 
   let mut zero_exists = false;
@@ -888,12 +767,13 @@ fn test_no_unnecessary_backtracks() {
     zero_exists =
       zero_exists || solution.get_row(row_idx).iter().any(|e| e == &0);
   }
-  assert_eq!(zero_exists, false);
+  assert!(!zero_exists);
 }
 
 #[test]
 fn test_algorithm_x() {
-  let solutions = launch_algorithm_x(None, None, Some(1));
+  let solutions =
+    launch_algorithm_x(None, None, Some(1), None, None, None).unwrap();
   let solution = solutions.first().unwrap();
 
   let mut zero_exists = false;
@@ -901,7 +781,7 @@ fn test_algorithm_x() {
     zero_exists =
       zero_exists || solution.get_row(row_idx).iter().any(|e| e == &0);
   }
-  assert_eq!(zero_exists, false);
+  assert!(!zero_exists);
 }
 
 // // Below will run 10 benchmarks
@@ -925,3 +805,1460 @@ fn test_algorithm_x() {
 
 //   assert!(false);
 // }
+
+/// The board the first strategy finds on an empty board, recorded from the
+/// original implementation, which scanned a table of bools
+const FIRST_STRATEGY_ROWS: [[i32; 9]; 9] = [
+  [1, 2, 3, 4, 5, 6, 7, 8, 9],
+  [7, 8, 9, 1, 2, 3, 6, 5, 4],
+  [4, 5, 6, 7, 8, 9, 1, 2, 3],
+  [2, 1, 4, 3, 6, 5, 9, 7, 8],
+  [3, 9, 8, 2, 1, 7, 5, 4, 6],
+  [5, 6, 7, 9, 4, 8, 3, 1, 2],
+  [8, 3, 1, 5, 9, 2, 4, 6, 7],
+  [9, 4, 2, 6, 7, 1, 8, 3, 5],
+  [6, 7, 5, 8, 3, 4, 2, 9, 1],
+];
+
+#[test]
+fn test_first_strategy_solution_is_unchanged() {
+  // The first strategy must keep producing the same board
+  let solutions = launch_algorithm_x(
+    None,
+    Some(DecisionStrategy::First),
+    Some(1),
+    Some(ColumnHeuristic::FirstUnsatisfied),
+    None,
+    None,
+  )
+  .unwrap();
+  let solution = solutions.first().unwrap();
+
+  for (row_idx, expected_row) in FIRST_STRATEGY_ROWS.iter().enumerate() {
+    assert_eq!(solution.get_row(row_idx), expected_row);
+  }
+}
+
+#[test]
+fn test_default_column_heuristic_keeps_the_first_strategy_solution() {
+  // Callers from before the column heuristic was added leave it unset
+  let solutions = launch_algorithm_x(
+    None,
+    Some(DecisionStrategy::First),
+    None,
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+
+  for (row_idx, expected_row) in FIRST_STRATEGY_ROWS.iter().enumerate() {
+    assert_eq!(solutions[0].get_row(row_idx), expected_row);
+  }
+}
+
+#[test]
+fn test_minimum_remaining_solves_a_17_clue_puzzle() {
+  let puzzle = board_from_str(
+    "000000010400000000020000000000050407008000300001090000300400200050100000000806000",
+  );
+
+  for column_heuristic in [
+    ColumnHeuristic::MinimumRemaining,
+    ColumnHeuristic::MinimumRemainingRandom,
+  ] {
+    let solutions = launch_algorithm_x(
+      Some(Board::from_board(&puzzle)),
+      Some(DecisionStrategy::First),
+      Some(1),
+      Some(column_heuristic),
+      None,
+      None,
+    )
+    .unwrap();
+    assert_solves(&puzzle, &solutions[0]);
+  }
+}
+
+#[test]
+fn test_unsolvable_board_returns_an_error() {
+  // Every value but 1 is ruled out of the top left cell, and 1 is already
+  // in its column
+  let puzzle = board_from_str(
+    "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+  );
+
+  let result =
+    launch_algorithm_x(Some(puzzle), None, Some(1), None, None, None);
+  assert_eq!(result.err(), Some(SolveError::Unsolvable));
+}
+
+#[test]
+fn test_contradictory_givens_return_an_error() {
+  let mut board = Board::new();
+  board.set(0, 0, 5);
+  board.set(1, 1, 5);
+
+  let result = launch_algorithm_x(Some(board), None, Some(1), None, None, None);
+  assert_eq!(
+    result.err(),
+    Some(SolveError::ContradictoryGivens(vec![Conflict {
+      first_x: 0,
+      first_y: 0,
+      second_x: 1,
+      second_y: 1,
+      constraint_type: ConstraintType::SubGrid,
+    }]))
+  );
+}
+
+#[test]
+fn test_invalid_values_return_an_error() {
+  for value in [-3, 10, 12] {
+    let mut board = Board::new();
+    board.set(4, 7, value);
+
+    let result =
+      launch_algorithm_x(Some(board), None, Some(1), None, None, None);
+    assert_eq!(
+      result.err(),
+      Some(SolveError::InvalidValue { x: 4, y: 7, value })
+    );
+  }
+}
+
+#[test]
+fn test_count_solutions() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  assert_eq!(count_solutions(&solution, 10, None), Ok(1));
+
+  // The 1 and 2 in these four cells can be swapped
+  let mut two_solutions = Board::from_board(&solution);
+  two_solutions.set(0, 0, 0);
+  two_solutions.set(1, 0, 0);
+  two_solutions.set(0, 3, 0);
+  two_solutions.set(1, 3, 0);
+  assert_eq!(count_solutions(&two_solutions, 10, None), Ok(2));
+  assert_eq!(count_solutions(&two_solutions, 1, None), Ok(1));
+  assert_eq!(count_solutions(&two_solutions, 0, None), Ok(0));
+
+  let seventeen_clues = board_from_str(
+    "000000010400000000020000000000050407008000300001090000300400200050100000000806000",
+  );
+  assert_eq!(count_solutions(&seventeen_clues, 2, None), Ok(1));
+
+  let unsolvable = board_from_str(
+    "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+  );
+  assert_eq!(count_solutions(&unsolvable, 2, None), Ok(0));
+
+  assert_eq!(count_solutions(&Board::new(), 20_000, None), Ok(20_000));
+}
+
+#[test]
+fn test_check_uniqueness() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  assert_eq!(
+    check_uniqueness(&solution, None),
+    Ok(Uniqueness::Unique(Box::new(Board::from_board(&solution))))
+  );
+
+  let mut two_solutions = Board::from_board(&solution);
+  two_solutions.set(0, 0, 0);
+  two_solutions.set(1, 0, 0);
+  two_solutions.set(0, 3, 0);
+  two_solutions.set(1, 3, 0);
+  let Ok(Uniqueness::Multiple {
+    first,
+    second,
+    differences,
+  }) = check_uniqueness(&two_solutions, None)
+  else {
+    panic!("expected multiple solutions");
+  };
+  assert_solves(&two_solutions, &first);
+  assert_solves(&two_solutions, &second);
+  assert_eq!(
+    differences,
+    vec![
+      Cell { x: 0, y: 0 },
+      Cell { x: 1, y: 0 },
+      Cell { x: 0, y: 3 },
+      Cell { x: 1, y: 3 },
+    ]
+  );
+
+  let unsolvable = board_from_str(
+    "023456789000000000000000000100000000000000000000000000000000000000000000000000000",
+  );
+  assert_eq!(
+    check_uniqueness(&unsolvable, None),
+    Ok(Uniqueness::NoSolution)
+  );
+
+  let mut contradictory = Board::new();
+  contradictory.set(0, 0, 5);
+  contradictory.set(0, 1, 5);
+  assert!(matches!(
+    check_uniqueness(&contradictory, None),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+}
+
+#[test]
+fn test_seed_reproduces_boards() {
+  let solve = |seed| {
+    launch_algorithm_x(
+      None,
+      Some(DecisionStrategy::Random),
+      Some(3),
+      Some(ColumnHeuristic::MinimumRemainingRandom),
+      Some(seed),
+      None,
+    )
+    .unwrap()
+  };
+
+  assert_eq!(solve(7), solve(7));
+  assert_ne!(solve(7), solve(8));
+
+  // Recorded natively, wasm must produce the same board for this seed
+  assert_eq!(
+    solve(2024)[0],
+    board_from_str(
+      "794183256312456987658972143871624395965731824423598761246817539189345672537269418"
+    )
+  );
+}
+
+#[test]
+fn test_solution_iter() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  let mut two_solutions = Board::from_board(&solution);
+  two_solutions.set(0, 0, 0);
+  two_solutions.set(1, 0, 0);
+  two_solutions.set(0, 3, 0);
+  two_solutions.set(1, 3, 0);
+
+  let mut solutions =
+    SolutionIter::new(Some(two_solutions), None, None, None, None).unwrap();
+  let first = solutions.next().unwrap();
+  let second = solutions.next().unwrap();
+  assert_ne!(first, second);
+  assert!([&first, &second].contains(&&solution));
+  assert_eq!(solutions.next(), None);
+  assert_eq!(solutions.next(), None);
+
+  // Resuming the search finds the same solutions as collecting them
+  let strategy = Some(DecisionStrategy::Random);
+  let heuristic = Some(ColumnHeuristic::MinimumRemainingRandom);
+  let collected =
+    launch_algorithm_x(None, strategy, Some(5), heuristic, Some(11), None)
+      .unwrap();
+  let iterated: Vec<Board> =
+    SolutionIter::new(None, strategy, heuristic, Some(11), None)
+      .unwrap()
+      .take(5)
+      .collect();
+  assert_eq!(iterated, collected);
+}
+
+#[test]
+fn test_solution_iter_validates_the_board() {
+  let mut board = Board::new();
+  board.set(0, 0, 5);
+  board.set(0, 1, 5);
+
+  assert!(matches!(
+    SolutionIter::new(Some(board), None, None, None, None),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+}
+
+/// Asserts the board is completely filled and breaks no constraint
+fn assert_valid_solution(board: &Board) {
+  let size = board.size() as i32;
+  for y in 0..board.size() {
+    assert!(board
+      .get_row(y)
+      .iter()
+      .all(|value| (1..=size).contains(value)));
+  }
+  assert_eq!(board.validate(), vec![]);
+}
+
+#[test]
+fn test_solves_other_box_sizes() {
+  for box_size in [2, 4, 5] {
+    let board = Board::with_box_size(box_size).unwrap();
+    let solutions = launch_algorithm_x(
+      Some(board),
+      None,
+      Some(2),
+      Some(ColumnHeuristic::MinimumRemaining),
+      Some(3),
+      None,
+    )
+    .unwrap();
+
+    assert_eq!(solutions.len(), 2);
+    for solution in &solutions {
+      assert_eq!(solution.size(), box_size * box_size);
+      assert_valid_solution(solution);
+    }
+  }
+
+  // There are 288 ways to fill a 4x4 board, and one to fill a 1x1 board
+  assert_eq!(
+    count_solutions(&Board::with_box_size(2).unwrap(), 1000, None),
+    Ok(288)
+  );
+  assert_eq!(
+    count_solutions(&Board::with_box_size(1).unwrap(), 1000, None),
+    Ok(1)
+  );
+}
+
+#[test]
+fn test_solves_a_16x16_puzzle() {
+  let solution = launch_algorithm_x(
+    Some(Board::with_box_size(4).unwrap()),
+    None,
+    Some(1),
+    Some(ColumnHeuristic::MinimumRemaining),
+    Some(16),
+    None,
+  )
+  .unwrap()
+  .remove(0);
+
+  // Keep every third cell as a given
+  let mut puzzle = Board::with_box_size(4).unwrap();
+  for y in 0..16 {
+    for x in 0..16 {
+      if (y * 16 + x) % 3 == 0 {
+        puzzle.set(x, y, solution.get(x, y));
+      }
+    }
+  }
+
+  let solutions = launch_algorithm_x(
+    Some(Board::from_board(&puzzle)),
+    None,
+    Some(1),
+    Some(ColumnHeuristic::MinimumRemaining),
+    None,
+    None,
+  )
+  .unwrap();
+  assert_valid_solution(&solutions[0]);
+  for y in 0..16 {
+    for x in 0..16 {
+      if puzzle.get(x, y) != 0 {
+        assert_eq!(solutions[0].get(x, y), puzzle.get(x, y));
+      }
+    }
+  }
+}
+
+#[test]
+fn test_values_are_checked_against_the_board_size() {
+  let mut board = Board::with_box_size(2).unwrap();
+  board.set(1, 2, 5);
+  assert_eq!(
+    count_solutions(&board, 1, None),
+    Err(SolveError::InvalidValue {
+      x: 1,
+      y: 2,
+      value: 5
+    })
+  );
+
+  let mut board = Board::with_box_size(4).unwrap();
+  board.set(1, 2, 16);
+  assert_eq!(count_solutions(&board, 1, None), Ok(1));
+}
+
+#[test]
+fn test_constraint_table_sizes() {
+  assert_eq!(constraint_count(9), 324);
+  assert_eq!(choice_count(9), 729);
+
+  let ct = generate_constraint_table(&Board::with_box_size(2).unwrap(), &[]);
+  assert_eq!(ct.table.len(), choice_count(4));
+  // Placing a 3 at 1,2 satisfies "Row 2 has a 3", "Column 1 has a 3",
+  // "Sub grid 2 has a 3" and "Cell 1,2 has a value"
+  let row = &ct.table[(2 * 4 + 1) * 4 + 3 - 1];
+  assert_eq!(
+    row.iter().collect::<Vec<_>>(),
+    vec![2 * 4 + 2, 16 + 4 + 2, 32 + 2 * 4 + 2, 48 + 2 * 4 + 1]
+  );
+}
+
+#[test]
+fn test_solves_rectangular_boxes() {
+  for (box_height, box_width) in [(2, 3), (3, 2), (2, 4), (2, 5), (3, 4)] {
+    let board = Board::with_box_dimensions(box_height, box_width).unwrap();
+    let solutions =
+      launch_algorithm_x(Some(board), None, Some(2), None, Some(6), None)
+        .unwrap();
+
+    for solution in &solutions {
+      assert_eq!(solution.size(), box_height * box_width);
+      assert_eq!(solution.box_height(), box_height);
+      assert_eq!(solution.box_width(), box_width);
+      assert_valid_solution(solution);
+    }
+  }
+
+  // Two 2x3 boxes can hold 1 to 6 in the same rows without a conflict, a
+  // 3x2 box can't
+  let mut board = Board::with_box_dimensions(2, 3).unwrap();
+  board.set(0, 0, 1);
+  board.set(1, 2, 1);
+  assert_eq!(count_solutions(&board, 1, None), Ok(1));
+
+  let mut board = Board::with_box_dimensions(3, 2).unwrap();
+  board.set(0, 0, 1);
+  board.set(1, 2, 1);
+  assert!(matches!(
+    count_solutions(&board, 1, None),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+}
+
+fn diagonal_variants() -> Variants {
+  let mut variants = Variants::new();
+  variants.set_diagonals(true);
+  variants
+}
+
+fn window_variants() -> Variants {
+  let mut variants = Variants::new();
+  variants.set_windows(true);
+  variants
+}
+
+fn both_variants() -> Variants {
+  let mut variants = diagonal_variants();
+  variants.set_windows(true);
+  variants
+}
+
+#[test]
+fn test_generates_variant_boards() {
+  for variants in [diagonal_variants(), window_variants(), both_variants()] {
+    let solutions = launch_algorithm_x(
+      None,
+      None,
+      Some(2),
+      None,
+      Some(8),
+      Some(variants.clone()),
+    )
+    .unwrap();
+
+    for solution in &solutions {
+      assert_valid_solution(solution);
+      assert_eq!(solution.validate_with(&variants), Ok(vec![]));
+    }
+  }
+
+  let solution =
+    &launch_algorithm_x(None, None, None, None, Some(8), Some(both_variants()))
+      .unwrap()[0];
+  let mut diagonal: Vec<i32> = (0..9).map(|i| solution.get(i, i)).collect();
+  let mut window: Vec<i32> =
+    (0..9).map(|i| solution.get(5 + i % 3, 1 + i / 3)).collect();
+  diagonal.sort_unstable();
+  window.sort_unstable();
+  assert_eq!(diagonal, (1..10).collect::<Vec<i32>>());
+  assert_eq!(window, (1..10).collect::<Vec<i32>>());
+}
+
+#[test]
+fn test_counts_variant_solutions() {
+  // Every solution under a variant is a classic solution that also
+  // satisfies the variant's regions
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+  assert_eq!(classic_solutions.len(), 288);
+
+  for variants in [diagonal_variants(), window_variants(), both_variants()] {
+    let expected = classic_solutions
+      .iter()
+      .filter(|solution| solution.validate_with(&variants) == Ok(vec![]))
+      .count();
+    assert!(expected > 0 && expected < 288);
+
+    let board = Board::with_box_size(2).unwrap();
+    assert_eq!(
+      count_solutions(&board, 1000, Some(variants.clone())),
+      Ok(expected)
+    );
+    let solutions =
+      SolutionIter::new(Some(board), None, None, None, Some(variants)).unwrap();
+    assert_eq!(solutions.count(), expected);
+  }
+}
+
+#[test]
+fn test_variant_givens_are_validated() {
+  let mut board = Board::new();
+  board.set(0, 0, 5);
+  board.set(8, 8, 5);
+  assert_eq!(count_solutions(&board, 1, None), Ok(1));
+  assert_eq!(
+    count_solutions(&board, 1, Some(diagonal_variants())),
+    Err(SolveError::ContradictoryGivens(vec![Conflict {
+      first_x: 0,
+      first_y: 0,
+      second_x: 8,
+      second_y: 8,
+      constraint_type: ConstraintType::Diagonal,
+    }]))
+  );
+
+  let mut board = Board::new();
+  board.set(1, 1, 5);
+  board.set(3, 3, 5);
+  assert!(matches!(
+    check_uniqueness(&board, Some(window_variants())),
+    Err(SolveError::ContradictoryGivens(conflicts))
+      if conflicts[0].constraint_type == ConstraintType::Window
+  ));
+
+  // Windows are one cell apart, which leaves no room below a 2x3 box
+  assert!(matches!(
+    count_solutions(
+      &Board::with_box_dimensions(2, 3).unwrap(),
+      1,
+      Some(window_variants())
+    ),
+    Err(SolveError::UnsupportedVariant(_))
+  ));
+}
+
+/// A 9x9 jigsaw made by trading one cell between the boxes of each pair in
+/// the top, middle and bottom rows of boxes
+fn jigsaw_board() -> Board {
+  #[rustfmt::skip]
+  let region_map = vec![
+    0, 0, 1, 1, 1, 1, 2, 2, 2,
+    0, 0, 0, 1, 1, 1, 2, 2, 2,
+    0, 0, 0, 0, 1, 1, 2, 2, 2,
+    3, 3, 3, 4, 4, 5, 5, 5, 5,
+    3, 3, 3, 4, 4, 4, 5, 5, 5,
+    3, 3, 3, 4, 4, 4, 4, 5, 5,
+    6, 6, 6, 6, 7, 7, 8, 8, 8,
+    6, 6, 6, 7, 7, 7, 8, 8, 8,
+    6, 6, 7, 7, 7, 7, 8, 8, 8,
+  ];
+
+  let mut board = Board::new();
+  board.set_region_map(region_map).unwrap();
+  board
+}
+
+/// Counts the solutions by trying every value in every empty cell, keeping
+/// the ones that leave the board valid
+fn count_by_brute_force(board: &mut Board, index: usize) -> usize {
+  let size = board.size();
+  if index == size * size {
+    return 1;
+  }
+
+  let (x, y) = (index % size, index / size);
+  if board.get(x, y) != 0 {
+    return count_by_brute_force(board, index + 1);
+  }
+
+  let mut count = 0;
+  for value in 1..=size as i32 {
+    board.set(x, y, value);
+    if board.validate().is_empty() {
+      count += count_by_brute_force(board, index + 1);
+    }
+  }
+  board.set(x, y, 0);
+
+  count
+}
+
+#[test]
+fn test_solves_jigsaw_boards() {
+  let solutions = launch_algorithm_x(
+    Some(jigsaw_board()),
+    None,
+    Some(3),
+    None,
+    Some(2),
+    None,
+  )
+  .unwrap();
+  for solution in &solutions {
+    assert_eq!(solution.region_map(), jigsaw_board().region_map());
+    assert_valid_solution(solution);
+
+    for region in 0..9 {
+      let mut values: Vec<i32> = (0..81)
+        .filter(|&index| solution.box_index(index % 9, index / 9) == region)
+        .map(|index| solution.get(index % 9, index / 9))
+        .collect();
+      values.sort_unstable();
+      assert_eq!(values, (1..10).collect::<Vec<i32>>());
+    }
+  }
+
+  let mut board = Board::with_box_size(2).unwrap();
+  board
+    .set_region_map(vec![0, 0, 0, 1, 2, 0, 1, 1, 2, 2, 3, 1, 2, 3, 3, 3])
+    .unwrap();
+  let expected = count_by_brute_force(&mut board.clone(), 0);
+  assert_eq!(expected, 96);
+  assert_eq!(count_solutions(&board, 1000, None), Ok(expected));
+
+  // Removing one value from a solution leaves it unique
+  let mut puzzle = solutions[0].clone();
+  puzzle.set(4, 4, 0);
+  assert_eq!(
+    check_uniqueness(&puzzle, None),
+    Ok(Uniqueness::Unique(Box::new(solutions[0].clone())))
+  );
+}
+
+#[test]
+fn test_jigsaw_givens_are_validated() {
+  // 2,0 and 0,1 share a box, but not a region
+  let mut board = jigsaw_board();
+  board.set(2, 0, 1);
+  board.set(0, 1, 1);
+  assert_eq!(count_solutions(&board, 1, None), Ok(1));
+
+  // 2,0 and 3,1 share a region, but not a box
+  let mut board = jigsaw_board();
+  board.set(2, 0, 1);
+  board.set(3, 1, 1);
+  assert_eq!(
+    count_solutions(&board, 1, None),
+    Err(SolveError::ContradictoryGivens(vec![Conflict {
+      first_x: 2,
+      first_y: 0,
+      second_x: 3,
+      second_y: 1,
+      constraint_type: ConstraintType::SubGrid,
+    }]))
+  );
+}
+
+/// Cages over the solution, each adding up to the values the solution has
+/// in its cells
+fn killer_variants(
+  solution: &Board,
+  cages: &[Vec<(usize, usize)>],
+) -> Variants {
+  let mut variants = Variants::new();
+  for cage in cages {
+    let cells = cage.iter().map(|&(x, y)| Cell { x, y }).collect();
+    let sum = cage.iter().map(|&(x, y)| solution.get(x, y) as u32).sum();
+    variants.add_cage(Cage::new(cells, sum));
+  }
+
+  variants
+}
+
+/// Splits every 3x3 box into an L of three cells in its top left corner,
+/// two upright pairs and its two bottom corners on their own. Then takes
+/// the split cells out of their cages.
+fn killer_cages(split: &[(usize, usize)]) -> Vec<Vec<(usize, usize)>> {
+  let mut cages = vec![];
+  for box_y in (0..9).step_by(3) {
+    for box_x in (0..9).step_by(3) {
+      let (x, y) = (box_x, box_y);
+      cages.push(vec![(x, y), (x + 1, y), (x, y + 1)]);
+      cages.push(vec![(x + 2, y), (x + 2, y + 1)]);
+      cages.push(vec![(x + 1, y + 1), (x + 1, y + 2)]);
+      cages.push(vec![(x, y + 2)]);
+      cages.push(vec![(x + 2, y + 2)]);
+    }
+  }
+
+  for cell in split {
+    for cage in cages.iter_mut() {
+      cage.retain(|other| other != cell);
+    }
+    cages.push(vec![*cell]);
+  }
+
+  cages
+}
+
+#[test]
+fn test_solves_killer_cages() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+
+  // Killer puzzles often have no givens at all
+  let variants = killer_variants(&solution, &killer_cages(&[]));
+  assert_eq!(
+    count_solutions(&Board::new(), 1000, Some(variants.clone())),
+    Ok(80)
+  );
+  assert!(matches!(
+    check_uniqueness(&Board::new(), Some(variants.clone())),
+    Ok(Uniqueness::Multiple { .. })
+  ));
+  for solution in
+    launch_algorithm_x(None, None, Some(80), None, Some(4), Some(variants))
+      .unwrap()
+  {
+    assert_valid_solution(&solution);
+  }
+
+  let split = [(2, 6), (4, 4), (0, 3), (6, 0), (1, 1)];
+  let variants = killer_variants(&solution, &killer_cages(&split));
+  assert_eq!(
+    check_uniqueness(&Board::new(), Some(variants.clone())),
+    Ok(Uniqueness::Unique(Box::new(solution.clone())))
+  );
+
+  // A given that can't add up to its cage's sum leaves no solution
+  let mut board = Board::new();
+  board.set(0, 0, 9);
+  assert_eq!(count_solutions(&board, 1, Some(variants.clone())), Ok(0));
+  assert_eq!(
+    launch_algorithm_x(Some(board), None, None, None, None, Some(variants)),
+    Err(SolveError::Unsolvable)
+  );
+}
+
+#[test]
+fn test_counts_killer_solutions() {
+  // Every solution of a killer puzzle is a classic solution whose cages
+  // add up without repeating a value
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+  let cages: Vec<Vec<(usize, usize)>> = vec![
+    vec![(0, 0), (1, 0), (0, 1)],
+    vec![(2, 0), (3, 0)],
+    vec![(1, 1), (2, 1), (2, 2)],
+    vec![(3, 1), (3, 2), (3, 3)],
+    vec![(0, 2), (0, 3), (1, 3)],
+  ];
+
+  let fits_cages = |board: &Board, sums: &[i32]| {
+    cages.iter().zip(sums).all(|(cage, &sum)| {
+      let values: HashSet<i32> =
+        cage.iter().map(|&(x, y)| board.get(x, y)).collect();
+      values.len() == cage.len() && values.iter().sum::<i32>() == sum
+    })
+  };
+
+  let mut solvable_puzzles = 0;
+  for solution in &classic_solutions[..20] {
+    let variants = killer_variants(solution, &cages);
+    let sums: Vec<i32> =
+      variants.cages().iter().map(|c| c.sum() as i32).collect();
+    let expected = classic_solutions
+      .iter()
+      .filter(|other| fits_cages(other, &sums))
+      .count();
+    if expected > 0 {
+      solvable_puzzles += 1;
+    }
+
+    let board = Board::with_box_size(2).unwrap();
+    // A cage that repeats a value in the solution can sum to less or more
+    // than different values add up to, which is rejected
+    let reachable = cages.iter().zip(&sums).all(|(cage, &sum)| {
+      let cell_count = cage.len() as i32;
+      let largest_sum = cell_count * (2 * 4 - cell_count + 1) / 2;
+      (cell_count * (cell_count + 1) / 2..=largest_sum).contains(&sum)
+    });
+    if !reachable {
+      assert_eq!(expected, 0);
+      assert!(matches!(
+        count_solutions(&board, 1000, Some(variants)),
+        Err(SolveError::InvalidCage(_))
+      ));
+      continue;
+    }
+
+    assert_eq!(
+      count_solutions(&board, 1000, Some(variants.clone())),
+      Ok(expected)
+    );
+    let solutions =
+      SolutionIter::new(Some(board), None, None, None, Some(variants));
+    assert_eq!(solutions.unwrap().count(), expected);
+  }
+  assert!(solvable_puzzles > 0);
+}
+
+#[test]
+fn test_killer_cages_are_validated() {
+  let mut variants = Variants::new();
+  variants
+    .add_cage(Cage::new(vec![Cell { x: 0, y: 0 }, Cell { x: 4, y: 4 }], 9));
+
+  let mut board = Board::new();
+  board.set(0, 0, 3);
+  board.set(4, 4, 3);
+  assert_eq!(
+    count_solutions(&board, 1, Some(variants.clone())),
+    Err(SolveError::ContradictoryGivens(vec![Conflict {
+      first_x: 0,
+      first_y: 0,
+      second_x: 4,
+      second_y: 4,
+      constraint_type: ConstraintType::Cage,
+    }]))
+  );
+
+  for cells in [
+    vec![Cell { x: 9, y: 0 }],
+    vec![Cell { x: 4, y: 4 }],
+    (0..10).map(|x| Cell { x: x % 9, y: x / 9 }).collect(),
+  ] {
+    let mut variants = variants.clone();
+    variants.add_cage(Cage::new(cells, 1));
+    assert!(matches!(
+      count_solutions(&Board::new(), 1, Some(variants)),
+      Err(SolveError::InvalidCage(_))
+    ));
+  }
+}
+
+#[test]
+fn test_empty_cages_are_rejected() {
+  let mut variants = Variants::new();
+  variants.add_cage(Cage::new(vec![], 0));
+  assert!(matches!(
+    count_solutions(&Board::new(), 1, Some(variants)),
+    Err(SolveError::InvalidCage(_))
+  ));
+}
+
+#[test]
+fn test_unreachable_cage_sums_are_rejected() {
+  let cells = vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }];
+  // Two different values add up to at least 1 + 2 and at most 8 + 9
+  for (sum, valid) in [(2, false), (3, true), (17, true), (18, false)] {
+    let mut variants = Variants::new();
+    variants.add_cage(Cage::new(cells.clone(), sum));
+    let result = count_solutions(&Board::new(), 1, Some(variants));
+    assert_eq!(matches!(result, Err(SolveError::InvalidCage(_))), !valid);
+  }
+}
+
+fn anti_knight_variants() -> Variants {
+  let mut variants = Variants::new();
+  variants.set_anti_knight(true);
+  variants
+}
+
+fn anti_king_variants() -> Variants {
+  let mut variants = Variants::new();
+  variants.set_anti_king(true);
+  variants
+}
+
+#[test]
+fn test_solves_anti_chess_variants() {
+  // Follows both rules, every row is the one above moved three cells right
+  let solution = board_from_str(
+    "758231649649758231231649758975823164164975823823164975497582316316497582582316497",
+  );
+  let mut both = anti_knight_variants();
+  both.set_anti_king(true);
+  assert_eq!(solution.validate_with(&both), Ok(vec![]));
+
+  // Only the first row and column are given
+  let mut puzzle = solution.cleared();
+  for i in 0..9 {
+    puzzle.set(i, 0, solution.get(i, 0));
+    puzzle.set(0, i, solution.get(0, i));
+  }
+  assert_eq!(count_solutions(&puzzle, 1000, None), Ok(1000));
+  for variants in [anti_knight_variants(), both] {
+    let solutions = launch_algorithm_x(
+      Some(puzzle.clone()),
+      None,
+      Some(2),
+      None,
+      None,
+      Some(variants.clone()),
+    )
+    .unwrap();
+    for found in &solutions {
+      assert_solves(&puzzle, found);
+      assert_eq!(found.validate_with(&variants), Ok(vec![]));
+    }
+  }
+
+  let solutions = launch_algorithm_x(
+    None,
+    None,
+    Some(2),
+    None,
+    Some(5),
+    Some(anti_king_variants()),
+  )
+  .unwrap();
+  for solution in &solutions {
+    assert_valid_solution(solution);
+    assert_eq!(solution.validate_with(&anti_king_variants()), Ok(vec![]));
+  }
+
+  // Counting agrees with filtering the classic 4x4 solutions
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+  for variants in [anti_knight_variants(), anti_king_variants()] {
+    let expected = classic_solutions
+      .iter()
+      .filter(|solution| solution.validate_with(&variants) == Ok(vec![]))
+      .count();
+    assert!(expected < 288);
+    assert_eq!(
+      count_solutions(&Board::with_box_size(2).unwrap(), 1000, Some(variants)),
+      Ok(expected)
+    );
+  }
+}
+
+#[test]
+fn test_anti_chess_givens_are_validated() {
+  let mut board = Board::new();
+  board.set(2, 0, 4);
+  board.set(3, 2, 4);
+  board.set(5, 5, 7);
+  board.set(6, 4, 7);
+  assert_eq!(count_solutions(&board, 1, None), Ok(1));
+
+  let knight_conflict = Conflict {
+    first_x: 2,
+    first_y: 0,
+    second_x: 3,
+    second_y: 2,
+    constraint_type: ConstraintType::Knight,
+  };
+  let king_conflict = Conflict {
+    first_x: 5,
+    first_y: 5,
+    second_x: 6,
+    second_y: 4,
+    constraint_type: ConstraintType::King,
+  };
+  assert_eq!(
+    count_solutions(&board, 1, Some(anti_knight_variants())),
+    Err(SolveError::ContradictoryGivens(vec![knight_conflict]))
+  );
+  assert_eq!(
+    check_uniqueness(&board, Some(anti_king_variants())),
+    Err(SolveError::ContradictoryGivens(vec![king_conflict]))
+  );
+}
+
+fn cells(cells: &[(usize, usize)]) -> Vec<Cell> {
+  cells.iter().map(|&(x, y)| Cell { x, y }).collect()
+}
+
+#[test]
+fn test_solves_thermometers_and_arrows() {
+  let mut variants = Variants::new();
+  variants.add_thermometer(Thermometer::new(cells(&[(0, 0), (1, 0), (2, 0)])));
+  variants.add_thermometer(Thermometer::new(cells(&[(0, 0), (0, 1), (0, 2)])));
+  variants.add_thermometer(Thermometer::new(cells(&[
+    (8, 8),
+    (7, 7),
+    (6, 6),
+    (5, 5),
+  ])));
+  variants.add_arrow(Arrow::new(Cell { x: 4, y: 0 }, cells(&[(4, 1), (4, 2)])));
+  variants.add_arrow(Arrow::new(
+    Cell { x: 8, y: 0 },
+    cells(&[(7, 1), (6, 2), (5, 3)]),
+  ));
+
+  let solutions = launch_algorithm_x(
+    None,
+    None,
+    Some(20),
+    None,
+    Some(3),
+    Some(variants.clone()),
+  )
+  .unwrap();
+  assert_eq!(solutions.len(), 20);
+  for solution in &solutions {
+    assert_valid_solution(solution);
+    assert!(solution.get(0, 0) < solution.get(2, 0).min(solution.get(0, 2)));
+    assert_eq!(solution.broken_constraints(&variants), Ok(vec![]));
+  }
+
+  // The bulb of a thermometer as long as the board has values must be 1
+  let mut long_variants = variants.clone();
+  long_variants.add_thermometer(Thermometer::new(
+    (0..9).map(|y| Cell { x: 8, y }).collect(),
+  ));
+  let mut board = Board::new();
+  board.set(8, 0, 2);
+  assert_eq!(
+    count_solutions(&board, 1, Some(long_variants.clone())),
+    Ok(0)
+  );
+  assert_eq!(
+    launch_algorithm_x(
+      Some(board),
+      None,
+      None,
+      None,
+      None,
+      Some(long_variants)
+    ),
+    Err(SolveError::Unsolvable)
+  );
+
+  // Two cells can't add up to 1
+  let mut board = Board::new();
+  board.set(4, 0, 1);
+  assert_eq!(count_solutions(&board, 1, Some(variants)), Ok(0));
+}
+
+#[test]
+fn test_counts_thermometer_and_arrow_solutions() {
+  // Every solution is a classic solution that breaks none of the
+  // thermometers and arrows
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+
+  let mut layouts = vec![];
+  let mut variants = Variants::new();
+  variants.add_thermometer(Thermometer::new(cells(&[(0, 0), (1, 0), (1, 1)])));
+  layouts.push(variants.clone());
+  variants.add_arrow(Arrow::new(Cell { x: 3, y: 3 }, cells(&[(2, 2), (1, 2)])));
+  layouts.push(variants.clone());
+  variants.add_thermometer(Thermometer::new(cells(&[(3, 0), (3, 1)])));
+  variants.add_arrow(Arrow::new(Cell { x: 0, y: 3 }, cells(&[(1, 3), (2, 2)])));
+  layouts.push(variants.clone());
+  let mut variants = Variants::new();
+  variants.add_arrow(Arrow::new(Cell { x: 0, y: 0 }, cells(&[(1, 1), (2, 2)])));
+  layouts.push(variants);
+
+  for variants in layouts {
+    let expected = classic_solutions
+      .iter()
+      .filter(|solution| solution.broken_constraints(&variants) == Ok(vec![]))
+      .count();
+    assert!(expected > 0);
+
+    let board = Board::with_box_size(2).unwrap();
+    assert_eq!(
+      count_solutions(&board, 1000, Some(variants.clone())),
+      Ok(expected)
+    );
+    let solutions =
+      SolutionIter::new(Some(board), None, None, None, Some(variants));
+    assert_eq!(solutions.unwrap().count(), expected);
+  }
+}
+
+#[test]
+fn test_thermometers_and_arrows_are_validated() {
+  for thermometer in [
+    vec![],
+    cells(&[(0, 0), (0, 9)]),
+    cells(&[(0, 0), (1, 1), (0, 0)]),
+    cells(&[(0, 0), (2, 0)]),
+    (0..10).map(|x| Cell { x: x % 9, y: x / 9 }).collect(),
+  ] {
+    let mut variants = Variants::new();
+    variants.add_thermometer(Thermometer::new(thermometer));
+    assert!(matches!(
+      count_solutions(&Board::new(), 1, Some(variants)),
+      Err(SolveError::InvalidThermometer(_))
+    ));
+  }
+
+  for (circle, arrow) in [
+    ((0, 0), vec![]),
+    ((9, 0), cells(&[(8, 0)])),
+    ((0, 0), cells(&[(1, 0), (0, 0)])),
+    ((0, 0), cells(&[(0, 2)])),
+  ] {
+    let mut variants = Variants::new();
+    let (x, y) = circle;
+    variants.add_arrow(Arrow::new(Cell { x, y }, arrow));
+    assert!(matches!(
+      count_solutions(&Board::new(), 1, Some(variants)),
+      Err(SolveError::InvalidArrow(_))
+    ));
+  }
+}
+
+#[test]
+fn test_counts_sandwich_solutions() {
+  // Every solution is a classic solution whose sandwiches add up
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+
+  for solution in &classic_solutions[..20] {
+    // Half the clues of the solution, alternating rows and columns
+    let mut variants = Variants::new();
+    for (sandwich_idx, sandwich) in
+      sandwiches_of(solution).into_iter().enumerate()
+    {
+      if sandwich_idx % 2 == 0 {
+        variants.add_sandwich(sandwich);
+      }
+    }
+    let expected = classic_solutions
+      .iter()
+      .filter(|other| other.broken_constraints(&variants) == Ok(vec![]))
+      .count();
+    assert!(expected > 0);
+
+    let board = Board::with_box_size(2).unwrap();
+    assert_eq!(
+      count_solutions(&board, 1000, Some(variants.clone())),
+      Ok(expected)
+    );
+    let solutions =
+      SolutionIter::new(Some(board), None, None, None, Some(variants));
+    assert_eq!(solutions.unwrap().count(), expected);
+  }
+}
+
+#[test]
+fn test_generates_sandwich_puzzles() {
+  for seed in [0, 2] {
+    let generated = generate_sandwich_puzzle(None, Some(seed)).unwrap();
+    let (puzzle, variants, solution) = (
+      generated.puzzle(),
+      generated.variants(),
+      generated.solution(),
+    );
+
+    assert_valid_solution(&solution);
+    assert_solves(&puzzle, &solution);
+    assert_eq!(variants.sandwiches().len(), 18);
+    assert_eq!(solution.broken_constraints(&variants), Ok(vec![]));
+    assert_eq!(
+      check_uniqueness(&puzzle, Some(variants.clone())),
+      Ok(Uniqueness::Unique(Box::new(solution.clone())))
+    );
+
+    // Only a few givens, and none of them can be taken away
+    let givens: Vec<Cell> = puzzle.differences(&puzzle.cleared());
+    assert!(givens.len() < 17, "{} givens", givens.len());
+    for Cell { x, y } in givens {
+      let mut fewer_givens = puzzle.clone();
+      fewer_givens.set(x, y, 0);
+      assert!(matches!(
+        check_uniqueness(&fewer_givens, Some(variants.clone())),
+        Ok(Uniqueness::Multiple { .. })
+      ));
+    }
+  }
+
+  // Givens on the starting board are kept
+  let mut board = Board::new();
+  board.set(4, 4, 5);
+  let generated = generate_sandwich_puzzle(Some(board), Some(0)).unwrap();
+  assert_eq!(generated.puzzle().get(4, 4), 5);
+  assert_eq!(generated.solution().get(4, 4), 5);
+}
+
+#[test]
+fn test_sandwich_clues_are_validated() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  let sandwiches = sandwiches_of(&solution);
+  // 1 and 9 at either end of the first row, and 7 cells apart in the first
+  // column
+  assert_eq!(sandwiches[0], Sandwich::row(0, 35));
+  assert_eq!(sandwiches[9], Sandwich::column(0, 29));
+
+  let mut variants = Variants::new();
+  variants.add_sandwich(Sandwich::row(0, 35));
+  variants.add_sandwich(Sandwich::column(0, 1));
+  variants.add_sandwich(Sandwich::column(8, sandwiches[17].sum()));
+  assert_eq!(
+    solution.broken_constraints(&variants),
+    Ok(vec![BrokenConstraint {
+      constraint_type: ConstraintType::Sandwich,
+      index: 1,
+    }])
+  );
+
+  // No two cells between the 1 and the 9 add up to 1
+  let mut board = Board::new();
+  board.set(0, 0, 1);
+  variants.clear_sandwiches();
+  variants.add_sandwich(Sandwich::row(0, 1));
+  assert_eq!(count_solutions(&board, 1, Some(variants.clone())), Ok(0));
+
+  variants.add_sandwich(Sandwich::column(9, 0));
+  assert!(matches!(
+    count_solutions(&Board::new(), 1, Some(variants)),
+    Err(SolveError::InvalidSandwich(_))
+  ));
+}
+
+#[test]
+fn test_sandwiches_on_a_1x1_board() {
+  let mut board = Board::with_box_size(1).unwrap();
+  board.set(0, 0, 1);
+  // The only value is both the smallest and the largest, nothing is between
+  assert_eq!(
+    sandwiches_of(&board),
+    vec![Sandwich::row(0, 0), Sandwich::column(0, 0)]
+  );
+
+  let mut variants = Variants::new();
+  variants.add_sandwich(Sandwich::row(0, 0));
+  assert!(matches!(
+    count_solutions(&board, 1, Some(variants)),
+    Err(SolveError::InvalidSandwich(_))
+  ));
+  assert!(matches!(
+    generate_sandwich_puzzle(Some(Board::with_box_size(1).unwrap()), Some(0)),
+    Err(SolveError::InvalidSandwich(_))
+  ));
+}
+
+/// The dot between every pair of cells that share a side and hold values a
+/// dot could join, white for 1 and 2
+fn dots_of(solution: &Board) -> Vec<Dot> {
+  let size = solution.size();
+  let mut dots = vec![];
+  for y in 0..size {
+    for x in 0..size {
+      for (other_x, other_y) in [(x + 1, y), (x, y + 1)] {
+        if other_x == size || other_y == size {
+          continue;
+        }
+        let (first, second) = (
+          Cell { x, y },
+          Cell {
+            x: other_x,
+            y: other_y,
+          },
+        );
+        let values = (solution.get(x, y), solution.get(other_x, other_y));
+        if (values.0 - values.1).abs() == 1 {
+          dots.push(Dot::white(first, second));
+        } else if values.0 == 2 * values.1 || values.1 == 2 * values.0 {
+          dots.push(Dot::black(first, second));
+        }
+      }
+    }
+  }
+
+  dots
+}
+
+#[test]
+fn test_counts_kropki_solutions() {
+  // Every solution is a classic solution whose values fit the dots, and
+  // when every dot is given, whose pairs without a dot fit none
+  let classic_solutions = launch_algorithm_x(
+    Some(Board::with_box_size(2).unwrap()),
+    None,
+    Some(1000),
+    None,
+    None,
+    None,
+  )
+  .unwrap();
+
+  let mut layouts = vec![];
+  let mut variants = Variants::new();
+  variants.add_dot(Dot::white(Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }));
+  layouts.push(variants.clone());
+  variants.add_dot(Dot::black(Cell { x: 2, y: 2 }, Cell { x: 2, y: 1 }));
+  variants.add_dot(Dot::black(Cell { x: 3, y: 3 }, Cell { x: 3, y: 2 }));
+  layouts.push(variants.clone());
+  let mut variants = Variants::new();
+  for dot in dots_of(&classic_solutions[0]) {
+    variants.add_dot(dot);
+  }
+  layouts.push(variants.clone());
+  variants.set_all_dots_given(true);
+  layouts.push(variants);
+
+  for variants in layouts {
+    let expected = classic_solutions
+      .iter()
+      .filter(|solution| solution.validate_with(&variants) == Ok(vec![]))
+      .count();
+    assert!(expected > 0);
+
+    let board = Board::with_box_size(2).unwrap();
+    assert_eq!(
+      count_solutions(&board, 1000, Some(variants.clone())),
+      Ok(expected)
+    );
+    let solutions =
+      SolutionIter::new(Some(board), None, None, None, Some(variants));
+    assert_eq!(solutions.unwrap().count(), expected);
+  }
+}
+
+#[test]
+fn test_solves_kropki_puzzles() {
+  let solution = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  let mut variants = Variants::new();
+  for dot in dots_of(&solution) {
+    variants.add_dot(dot);
+  }
+  assert_eq!(solution.validate_with(&variants), Ok(vec![]));
+  assert_eq!(solution.broken_constraints(&variants), Ok(vec![]));
+
+  // This solution's dots are enough to pin it down without any givens,
+  // with or without the negative constraint
+  for all_dots_given in [false, true] {
+    variants.set_all_dots_given(all_dots_given);
+    assert_eq!(solution.validate_with(&variants), Ok(vec![]));
+    assert!(matches!(
+      check_uniqueness(&Board::new(), Some(variants.clone())),
+      Ok(Uniqueness::Unique(unique)) if *unique == solution
+    ));
+    let found =
+      launch_algorithm_x(None, None, None, None, None, Some(variants.clone()));
+    assert_eq!(found, Ok(vec![solution.clone()]));
+  }
+}
+
+#[test]
+fn test_kropki_dots_are_validated() {
+  for (first, second) in [((0, 0), (0, 9)), ((0, 0), (1, 1)), ((0, 0), (0, 0))]
+  {
+    let mut variants = Variants::new();
+    variants.add_dot(Dot::white(
+      Cell {
+        x: first.0,
+        y: first.1,
+      },
+      Cell {
+        x: second.0,
+        y: second.1,
+      },
+    ));
+    assert!(matches!(
+      count_solutions(&Board::new(), 1, Some(variants)),
+      Err(SolveError::InvalidDot(_))
+    ));
+  }
+  let mut variants = Variants::new();
+  variants.add_dot(Dot::white(Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }));
+  variants.add_dot(Dot::black(Cell { x: 1, y: 0 }, Cell { x: 0, y: 0 }));
+  assert!(matches!(
+    count_solutions(&Board::new(), 1, Some(variants)),
+    Err(SolveError::InvalidDot(_))
+  ));
+
+  // Givens across a dot that don't fit it, and side by side without one
+  // when every dot is given
+  let mut variants = Variants::new();
+  variants.add_dot(Dot::black(Cell { x: 0, y: 0 }, Cell { x: 0, y: 1 }));
+  let mut board = Board::new();
+  board.set(0, 0, 3);
+  board.set(0, 1, 5);
+  board.set(1, 0, 4);
+  let dot_conflict = Conflict {
+    first_x: 0,
+    first_y: 0,
+    second_x: 0,
+    second_y: 1,
+    constraint_type: ConstraintType::Dot,
+  };
+  assert_eq!(board.validate_with(&variants), Ok(vec![dot_conflict]));
+  assert_eq!(
+    count_solutions(&board, 1, Some(variants.clone())),
+    Err(SolveError::ContradictoryGivens(vec![dot_conflict]))
+  );
+
+  variants.set_all_dots_given(true);
+  let negative_conflict = Conflict {
+    first_x: 0,
+    first_y: 0,
+    second_x: 1,
+    second_y: 0,
+    constraint_type: ConstraintType::Dot,
+  };
+  assert_eq!(
+    check_uniqueness(&board, Some(variants.clone())),
+    Err(SolveError::ContradictoryGivens(vec![
+      dot_conflict,
+      negative_conflict
+    ]))
+  );
+
+  // Only the dot has an index to report
+  let mut filled = board_from_str(
+    "123456789789123654456789123214365978398217546567948312831592467942671835675834291",
+  );
+  filled.set(0, 1, 8);
+  assert_eq!(
+    filled.broken_constraints(&variants),
+    Ok(vec![BrokenConstraint {
+      constraint_type: ConstraintType::Dot,
+      index: 0,
+    }])
+  );
+
+  // Values outside of 1 to 9 fit no dot and break none
+  for (first, second) in [(-3, 5), (20, 10)] {
+    let mut board = Board::new();
+    board.set(0, 0, first);
+    board.set(0, 1, second);
+    assert_eq!(board.broken_constraints(&variants), Ok(vec![]));
+    assert_eq!(board.validate_with(&variants), Ok(vec![]));
+  }
+}
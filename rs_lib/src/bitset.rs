@@ -0,0 +1,86 @@
+/// A set of indexes, packed 64 to a `u64` word. The words grow to fit the
+/// largest index inserted, so the same set serves every board size.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitSet {
+  words: Vec<u64>,
+}
+
+/// One bit for each constraint table column the row satisfies
+pub type ColumnSet = BitSet;
+
+/// One bit for each constraint table row in a solution
+pub type RowSet = BitSet;
+
+impl BitSet {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns true if the index was not already in the set
+  pub fn insert(&mut self, index: usize) -> bool {
+    let (word, mask) = (index / 64, 1 << (index % 64));
+    if word >= self.words.len() {
+      self.words.resize(word + 1, 0);
+    }
+    let inserted = self.words[word] & mask == 0;
+    self.words[word] |= mask;
+    inserted
+  }
+
+  /// Iterates over the indexes in the set, smallest first
+  pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+    self.words.iter().enumerate().flat_map(|(word_idx, word)| {
+      let mut remaining = *word;
+      std::iter::from_fn(move || {
+        if remaining == 0 {
+          return None;
+        }
+        let bit = remaining.trailing_zeros() as usize;
+        remaining &= remaining - 1;
+        Some(word_idx * 64 + bit)
+      })
+    })
+  }
+}
+
+impl FromIterator<usize> for BitSet {
+  fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+    let mut set = Self::new();
+    set.extend(iter);
+    set
+  }
+}
+
+impl Extend<usize> for BitSet {
+  fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+    for index in iter {
+      self.insert(index);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn insert_and_iterate() {
+    let mut set = RowSet::new();
+    assert_eq!(set.iter().next(), None);
+
+    assert!(set.insert(0));
+    assert!(set.insert(64));
+    assert!(set.insert(15_624));
+    assert!(!set.insert(64));
+
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 64, 15_624]);
+    assert_eq!(set, RowSet::from_iter([15_624, 0, 64]));
+  }
+
+  #[test]
+  fn collect_and_extend() {
+    let mut set = ColumnSet::from_iter([100, 1, 323]);
+    set.extend([2, 323]);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![1, 2, 100, 323]);
+  }
+}
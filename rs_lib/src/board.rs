@@ -1,3 +1,6 @@
+use crate::algorithm_x::ConstraintType;
+use crate::solve_error::SolveError;
+use crate::variants::{Region, Variants};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -5,58 +8,405 @@ pub fn add(a: i32, b: i32) -> i32 {
   a + b
 }
 
+/// Two cells that hold the same value in the same row, column, sub grid or
+/// region of a variant
 #[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conflict {
+  pub first_x: usize,
+  pub first_y: usize,
+  pub second_x: usize,
+  pub second_y: usize,
+  pub constraint_type: ConstraintType,
+}
+
+/// A cage, thermometer, arrow, sandwich clue or dot of a variant whose cells
+/// are all filled but break its rule. The index is its position among the
+/// variant's constraints of that type, in the order they were added.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BrokenConstraint {
+  pub constraint_type: ConstraintType,
+  pub index: usize,
+}
+
+/// A cell on the board. The top left corner is 0,0
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+  pub x: usize,
+  pub y: usize,
+}
+
+#[wasm_bindgen]
+impl Cell {
+  #[wasm_bindgen(constructor)]
+  pub fn new(x: usize, y: usize) -> Self {
+    Self { x, y }
+  }
+}
+
+/// A square board made of boxes. A board with boxes of 3x3 cells is the
+/// standard 9x9 board, boxes of 2x2, 4x4 and 5x5 cells give 4x4, 16x16 and
+/// 25x25 boards. Boxes may also be rectangles, boxes 2 cells high and 3
+/// wide give a 6x6 board. Cells hold 1 up to the board's size, or 0 when
+/// empty.
+///
+/// A region map replaces the boxes with irregular regions, for jigsaw
+/// Sudoku.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Board {
-  cells: [i32; 81],
+  box_height: usize,
+  box_width: usize,
+  cells: Vec<i32>,
+  /// The region of every cell in row order, `None` when the boxes are the
+  /// regions
+  region_map: Option<Vec<usize>>,
 }
 
 impl Default for Board {
   fn default() -> Self {
-    Self { cells: [0; 81] }
+    Self::with_box_size(3).expect("3x3 boxes are a valid box size")
   }
 }
 
 #[wasm_bindgen]
 impl Board {
+  /// Creates an empty 9x9 board
   #[wasm_bindgen(constructor)]
   pub fn new() -> Self {
     Self::default()
   }
 
+  /// Creates an empty board whose boxes are `box_size` cells on each side,
+  /// the board is `box_size * box_size` cells on each side. Returns an error
+  /// if `box_size` is 0.
+  pub fn with_box_size(box_size: usize) -> Result<Board, SolveError> {
+    Self::with_box_dimensions(box_size, box_size)
+  }
+
+  /// Creates an empty board whose boxes are `box_height` cells high and
+  /// `box_width` cells wide. The board is `box_height * box_width` cells on
+  /// each side, with `box_width` boxes stacked in each column of boxes and
+  /// `box_height` boxes side by side in each row of boxes. Returns an error
+  /// if either dimension is 0.
+  pub fn with_box_dimensions(
+    box_height: usize,
+    box_width: usize,
+  ) -> Result<Board, SolveError> {
+    if box_height == 0 || box_width == 0 {
+      return Err(SolveError::InvalidBoxSize {
+        box_height,
+        box_width,
+      });
+    }
+    let size = box_height * box_width;
+
+    Ok(Self {
+      box_height,
+      box_width,
+      cells: vec![0; size * size],
+      region_map: None,
+    })
+  }
+
   pub fn from_board(board: &Board) -> Self {
-    Self { cells: board.cells }
+    board.clone()
+  }
+
+  /// The number of cells down each box
+  pub fn box_height(&self) -> usize {
+    self.box_height
+  }
+
+  /// The number of cells across each box
+  pub fn box_width(&self) -> usize {
+    self.box_width
+  }
+
+  /// The number of cells on each side of the board, which is also the
+  /// largest value a cell can hold
+  pub fn size(&self) -> usize {
+    self.box_height * self.box_width
   }
 
   pub fn set(&mut self, x: usize, y: usize, value: i32) {
-    self.cells[y * 9 + x] = value;
+    let size = self.size();
+    self.cells[y * size + x] = value;
   }
 
   pub fn get(&self, x: usize, y: usize) -> i32 {
-    self.cells[y * 9 + x]
+    self.cells[y * self.size() + x]
+  }
+
+  /// Returns the index of the box holding the cell, boxes are numbered left
+  /// to right then top to bottom. On a board with a region map this is the
+  /// cell's region instead.
+  pub fn box_index(&self, x: usize, y: usize) -> usize {
+    if let Some(region_map) = &self.region_map {
+      return region_map[y * self.size() + x];
+    }
+
+    let boxes_per_row = self.box_height;
+    (y / self.box_height) * boxes_per_row + x / self.box_width
+  }
+
+  /// Replaces the boxes with the regions of a jigsaw board. The map labels
+  /// every cell in row order with its region, from 0 up to one less than
+  /// the board's size. Each region must be `size` cells joined by their
+  /// sides. Returns an error and leaves the board unchanged if the map is
+  /// not such a partition of the board.
+  pub fn set_region_map(
+    &mut self,
+    region_map: Vec<usize>,
+  ) -> Result<(), SolveError> {
+    check_region_map(&region_map, self.size())?;
+    self.region_map = Some(region_map);
+
+    Ok(())
+  }
+
+  /// Goes back to using the boxes as regions
+  pub fn clear_region_map(&mut self) {
+    self.region_map = None;
+  }
+
+  /// Returns the region of every cell in row order, `undefined` when the
+  /// boxes are the regions
+  pub fn region_map(&self) -> Option<Vec<usize>> {
+    self.region_map.clone()
+  }
+
+  /// Returns every pair of cells that hold the same value in a row, column
+  /// or sub grid. A pair that shares a row and a sub grid is reported once
+  /// for each. Values outside of 1 to the board's size are not compared,
+  /// the solver reports those as invalid values.
+  pub fn validate(&self) -> Vec<Conflict> {
+    let size = self.size();
+    let cell_count = self.cells.len();
+    let mut conflicts = vec![];
+
+    for first in 0..cell_count {
+      let value = self.cells[first];
+      if !(1..=size as i32).contains(&value) {
+        continue;
+      }
+
+      for second in (first + 1)..cell_count {
+        if self.cells[second] != value {
+          continue;
+        }
+
+        let (first_x, first_y) = (first % size, first / size);
+        let (second_x, second_y) = (second % size, second / size);
+        let shared_constraints = [
+          (ConstraintType::Row, first_y == second_y),
+          (ConstraintType::Column, first_x == second_x),
+          (
+            ConstraintType::SubGrid,
+            self.box_index(first_x, first_y)
+              == self.box_index(second_x, second_y),
+          ),
+        ];
+
+        for (constraint_type, shared) in shared_constraints {
+          if shared {
+            conflicts.push(Conflict {
+              first_x,
+              first_y,
+              second_x,
+              second_y,
+              constraint_type,
+            });
+          }
+        }
+      }
+    }
+
+    conflicts
+  }
+
+  /// Like `validate`, followed by every pair of cells that hold the same
+  /// value in one of the regions the variants add, then every pair whose
+  /// values break a kropki dot or, when every dot is given, sit side by side
+  /// without a dot but could take one. Returns an error if the variants do
+  /// not fit on the board.
+  pub fn validate_with(
+    &self,
+    variants: &Variants,
+  ) -> Result<Vec<Conflict>, SolveError> {
+    let mut conflicts = self.validate();
+    conflicts.extend(self.validate_regions(&variants.regions(self)?));
+    conflicts.extend(variants.conflicts(self));
+
+    Ok(conflicts)
+  }
+
+  /// Returns every killer cage, thermometer, arrow, sandwich clue and kropki
+  /// dot of the variants whose cells are all filled but break its rule:
+  /// cages that repeat a value or miss their sum, thermometers that don't
+  /// increase from the bulb, arrows that don't add up to their circle, rows
+  /// or columns whose sandwich doesn't add up to the clue and dots whose
+  /// values don't fit their colour. Pairs without a dot that break the
+  /// negative constraint have no index, `validate_with` reports them.
+  /// Returns an error if the variants do not fit on the board.
+  pub fn broken_constraints(
+    &self,
+    variants: &Variants,
+  ) -> Result<Vec<BrokenConstraint>, SolveError> {
+    variants.regions(self)?;
+
+    Ok(variants.broken_constraints(self))
+  }
+
+  /// Returns every cell whose value differs from the other board's, in
+  /// row order. Boards of different sizes differ in every cell.
+  pub fn differences(&self, other: &Board) -> Vec<Cell> {
+    let size = self.size();
+
+    (0..self.cells.len())
+      .filter(|&index| {
+        !self.has_shape_of(other) || self.cells[index] != other.cells[index]
+      })
+      .map(|index| Cell {
+        x: index % size,
+        y: index / size,
+      })
+      .collect()
   }
 }
 
-// cannot export these methods to wasm because js cannot respect ownership rules
 impl Board {
-  pub fn get_row(&self, row_idx: usize) -> &[i32] {
-    let start = row_idx * 9;
-    let end = start + 9;
-    &self.cells[start..end]
+  /// Returns every pair of cells that hold the same value in one of the
+  /// regions, region by region
+  pub(crate) fn validate_regions(&self, regions: &[Region]) -> Vec<Conflict> {
+    let size = self.size() as i32;
+    let mut conflicts = vec![];
+
+    for region in regions {
+      for (first_idx, first) in region.cells.iter().enumerate() {
+        let value = self.get(first.x, first.y);
+        if !(1..=size).contains(&value) {
+          continue;
+        }
+
+        for second in &region.cells[first_idx + 1..] {
+          if self.get(second.x, second.y) == value {
+            conflicts.push(Conflict {
+              first_x: first.x,
+              first_y: first.y,
+              second_x: second.x,
+              second_y: second.y,
+              constraint_type: region.constraint_type,
+            });
+          }
+        }
+      }
+    }
+
+    conflicts
   }
 
-  pub fn get_column(&self, col_idx: usize) -> [i32; 9] {
-    let mut column = [0; 9];
+  /// Returns true if both boards have the same size, boxes and regions
+  pub(crate) fn has_shape_of(&self, other: &Board) -> bool {
+    self.box_height == other.box_height
+      && self.box_width == other.box_width
+      && self.region_map == other.region_map
+  }
+
+  /// Returns an empty board with the same size, boxes and regions
+  pub(crate) fn cleared(&self) -> Board {
+    Self {
+      cells: vec![0; self.cells.len()],
+      ..self.clone()
+    }
+  }
+}
+
+/// Checks the region map labels each of the `size * size` cells of a board
+/// with a region below `size`, and that every region is `size` cells joined
+/// by their sides
+fn check_region_map(
+  region_map: &[usize],
+  size: usize,
+) -> Result<(), SolveError> {
+  let invalid = |reason: String| Err(SolveError::InvalidRegionMap(reason));
+
+  if region_map.len() != size * size {
+    return invalid(format!(
+      "The region map has {} cells, the board has {}",
+      region_map.len(),
+      size * size
+    ));
+  }
+  if let Some(index) = region_map.iter().position(|&region| region >= size) {
+    return invalid(format!(
+      "The cell at {}, {} is in region {}, regions go up to {}",
+      index % size,
+      index / size,
+      region_map[index],
+      size - 1
+    ));
+  }
+
+  for region in 0..size {
+    let cells: Vec<usize> = (0..region_map.len())
+      .filter(|&index| region_map[index] == region)
+      .collect();
+    if cells.len() != size {
+      return invalid(format!(
+        "Region {} has {} cells, every region needs {}",
+        region,
+        cells.len(),
+        size
+      ));
+    }
+
+    // Walk the region from its first cell, every cell has to be reached
+    let mut reached = vec![cells[0]];
+    let mut unvisited = vec![cells[0]];
+    while let Some(index) = unvisited.pop() {
+      let (x, y) = (index % size, index / size);
+      let neighbours = [
+        (x > 0).then(|| index - 1),
+        (x + 1 < size).then(|| index + 1),
+        (y > 0).then(|| index - size),
+        (y + 1 < size).then(|| index + size),
+      ];
 
-    for (y_idx, slot) in column.iter_mut().enumerate() {
-      *slot = self.get(col_idx, y_idx);
+      for neighbour in neighbours.into_iter().flatten() {
+        if region_map[neighbour] == region && !reached.contains(&neighbour) {
+          reached.push(neighbour);
+          unvisited.push(neighbour);
+        }
+      }
+    }
+    if reached.len() != size {
+      return invalid(format!("The cells of region {} are not joined", region));
     }
+  }
+
+  Ok(())
+}
 
-    column
+// cannot export these methods to wasm because js cannot respect ownership rules
+impl Board {
+  pub fn get_row(&self, row_idx: usize) -> &[i32] {
+    let start = row_idx * self.size();
+    let end = start + self.size();
+    &self.cells[start..end]
+  }
+
+  pub fn get_column(&self, col_idx: usize) -> Vec<i32> {
+    (0..self.size())
+      .map(|y_idx| self.get(col_idx, y_idx))
+      .collect()
   }
   pub fn print_board(&self) {
-    for row in 0..9 {
+    for row in 0..self.size() {
       let mut row_string = String::from("");
-      for cell in 0..9 {
+      for cell in 0..self.size() {
         row_string.push_str(&format!("| {} |", self.get(cell, row)));
       }
       println!("{}", row_string);
@@ -89,6 +439,310 @@ mod tests {
     board.set(2, 3, 5);
 
     let second_column = board.get_column(2);
-    assert_eq!([0, 1, 2, 5, 0, 0, 0, 0, 0], second_column);
+    assert_eq!(vec![0, 1, 2, 5, 0, 0, 0, 0, 0], second_column);
+  }
+
+  #[test]
+  fn validate_accepts_a_board_without_conflicts() {
+    let mut board = Board::new();
+    board.set(0, 0, 1);
+    board.set(4, 1, 1);
+    board.set(8, 2, 1);
+    board.set(1, 0, 2);
+    board.set(0, 5, -3);
+    board.set(0, 6, -3);
+
+    assert_eq!(board.validate(), vec![]);
+  }
+
+  #[test]
+  fn validate_reports_every_conflict() {
+    let mut board = Board::new();
+    // Shares a row and a sub grid
+    board.set(0, 0, 5);
+    board.set(2, 0, 5);
+    // Shares a column with 0,0
+    board.set(0, 7, 5);
+    // Shares a sub grid only
+    board.set(4, 4, 9);
+    board.set(5, 5, 9);
+
+    let conflict =
+      |first: (usize, usize), second: (usize, usize), kind| Conflict {
+        first_x: first.0,
+        first_y: first.1,
+        second_x: second.0,
+        second_y: second.1,
+        constraint_type: kind,
+      };
+
+    assert_eq!(
+      board.validate(),
+      vec![
+        conflict((0, 0), (2, 0), ConstraintType::Row),
+        conflict((0, 0), (2, 0), ConstraintType::SubGrid),
+        conflict((0, 0), (0, 7), ConstraintType::Column),
+        conflict((4, 4), (5, 5), ConstraintType::SubGrid),
+      ]
+    );
+  }
+
+  #[test]
+  fn boards_are_sized_by_their_boxes() {
+    let mut board = Board::with_box_size(4).unwrap();
+    assert_eq!(board.size(), 16);
+    assert_eq!(board.get_row(15).len(), 16);
+    assert_eq!(board.box_index(0, 0), 0);
+    assert_eq!(board.box_index(15, 0), 3);
+    assert_eq!(board.box_index(4, 5), 5);
+    assert_eq!(board.box_index(15, 15), 15);
+
+    board.set(15, 15, 16);
+    assert_eq!(board.get(15, 15), 16);
+    assert_eq!(Board::new(), Board::with_box_size(3).unwrap());
+  }
+
+  #[test]
+  fn boxes_can_be_rectangles() {
+    // Six 2x3 boxes, two to each row of boxes
+    let board = Board::with_box_dimensions(2, 3).unwrap();
+    assert_eq!(board.size(), 6);
+    assert_eq!(board.box_index(2, 1), 0);
+    assert_eq!(board.box_index(3, 0), 1);
+    assert_eq!(board.box_index(0, 2), 2);
+    assert_eq!(board.box_index(5, 5), 5);
+
+    // Twelve 3x4 boxes, three to each row of boxes
+    let board = Board::with_box_dimensions(3, 4).unwrap();
+    assert_eq!(board.size(), 12);
+    assert_eq!(board.box_index(11, 0), 2);
+    assert_eq!(board.box_index(0, 3), 3);
+    assert_eq!(board.box_index(11, 11), 11);
+
+    assert!(!board.has_shape_of(&Board::with_box_dimensions(4, 3).unwrap()));
+    assert_eq!(
+      Board::with_box_dimensions(2, 3)
+        .unwrap()
+        .differences(&Board::with_box_dimensions(3, 2).unwrap())
+        .len(),
+      36
+    );
+  }
+
+  #[test]
+  fn boxes_must_have_cells() {
+    for (box_height, box_width) in [(0, 0), (0, 3), (3, 0)] {
+      assert_eq!(
+        Board::with_box_dimensions(box_height, box_width),
+        Err(SolveError::InvalidBoxSize {
+          box_height,
+          box_width,
+        })
+      );
+    }
+    assert!(Board::with_box_size(0).is_err());
+  }
+
+  /// A 4x4 jigsaw, read row by row:
+  /// 0 0 0 1
+  /// 2 0 1 1
+  /// 2 3 3 1
+  /// 2 2 3 3
+  fn jigsaw_map() -> Vec<usize> {
+    vec![0, 0, 0, 1, 2, 0, 1, 1, 2, 3, 3, 1, 2, 2, 3, 3]
+  }
+
+  #[test]
+  fn region_maps_replace_the_boxes() {
+    let mut board = Board::with_box_size(2).unwrap();
+    assert_eq!(board.region_map(), None);
+    board.set_region_map(jigsaw_map()).unwrap();
+    assert_eq!(board.region_map(), Some(jigsaw_map()));
+    assert_eq!(board.box_index(2, 0), 0);
+    assert_eq!(board.box_index(0, 1), 2);
+    assert_eq!(board.box_index(3, 2), 1);
+
+    // 1,0 and 0,1 share a box but not a region, 2,0 and 1,1 share a
+    // region but not a box
+    board.set(1, 0, 1);
+    board.set(0, 1, 1);
+    assert_eq!(board.validate(), vec![]);
+    board.set(1, 0, 0);
+    board.set(0, 1, 0);
+    board.set(2, 0, 1);
+    board.set(1, 1, 1);
+    assert_eq!(
+      board.validate(),
+      vec![Conflict {
+        first_x: 2,
+        first_y: 0,
+        second_x: 1,
+        second_y: 1,
+        constraint_type: ConstraintType::SubGrid,
+      }]
+    );
+
+    assert!(!board.has_shape_of(&Board::with_box_size(2).unwrap()));
+    assert_eq!(board.cleared().region_map(), Some(jigsaw_map()));
+    board.clear_region_map();
+    assert_eq!(board.box_index(2, 0), 1);
+  }
+
+  #[test]
+  fn region_maps_must_partition_the_board() {
+    let mut board = Board::with_box_size(2).unwrap();
+    let invalid = |board: &mut Board, region_map: Vec<usize>| {
+      matches!(
+        board.set_region_map(region_map),
+        Err(SolveError::InvalidRegionMap(_))
+      )
+    };
+
+    assert!(invalid(&mut board, jigsaw_map()[..15].to_vec()));
+
+    let mut out_of_range = jigsaw_map();
+    out_of_range[15] = 4;
+    assert!(invalid(&mut board, out_of_range));
+
+    // Region 1 takes a cell from region 3
+    let mut uneven = jigsaw_map();
+    uneven[10] = 1;
+    assert!(invalid(&mut board, uneven));
+
+    // Regions 0 and 2 swap cells, keeping their sizes but splitting both
+    let mut split = jigsaw_map();
+    split.swap(0, 12);
+    assert!(invalid(&mut board, split));
+
+    assert_eq!(board.region_map(), None);
+  }
+
+  #[test]
+  fn validate_uses_the_board_size() {
+    let mut board = Board::with_box_size(2).unwrap();
+    board.set(0, 0, 4);
+    board.set(1, 1, 4);
+    board.set(3, 3, 4);
+    // Out of range values are left to the solver
+    board.set(2, 0, 5);
+    board.set(3, 0, 5);
+
+    assert_eq!(
+      board.validate(),
+      vec![Conflict {
+        first_x: 0,
+        first_y: 0,
+        second_x: 1,
+        second_y: 1,
+        constraint_type: ConstraintType::SubGrid,
+      }]
+    );
+  }
+
+  #[test]
+  fn validate_with_checks_the_variant_regions() {
+    let mut board = Board::new();
+    board.set(0, 0, 3);
+    board.set(4, 4, 3);
+    board.set(6, 6, 7);
+    board.set(7, 7, 7);
+
+    let mut variants = Variants::new();
+    assert_eq!(board.validate_with(&variants), Ok(board.validate()));
+
+    variants.set_diagonals(true);
+    variants.set_windows(true);
+    let conflict = |first, second, constraint_type| Conflict {
+      first_x: first,
+      first_y: first,
+      second_x: second,
+      second_y: second,
+      constraint_type,
+    };
+    assert_eq!(
+      board.validate_with(&variants),
+      Ok(vec![
+        conflict(6, 7, ConstraintType::SubGrid),
+        conflict(0, 4, ConstraintType::Diagonal),
+        conflict(6, 7, ConstraintType::Diagonal),
+        conflict(6, 7, ConstraintType::Window),
+      ])
+    );
+  }
+
+  #[test]
+  fn broken_constraints_only_checks_filled_constraints() {
+    use crate::killer::Cage;
+    use crate::lines::{Arrow, Thermometer};
+
+    let cells = |cells: &[(usize, usize)]| -> Vec<Cell> {
+      cells.iter().map(|&(x, y)| Cell { x, y }).collect()
+    };
+    let mut variants = Variants::new();
+    variants.add_cage(Cage::new(cells(&[(0, 0), (1, 0)]), 5));
+    variants.add_cage(Cage::new(cells(&[(0, 1), (1, 1)]), 4));
+    variants.add_thermometer(Thermometer::new(cells(&[(0, 2), (1, 2)])));
+    variants.add_thermometer(Thermometer::new(cells(&[(0, 3), (1, 3)])));
+    variants.add_arrow(Arrow::new(Cell { x: 8, y: 0 }, cells(&[(7, 1)])));
+    variants.add_arrow(Arrow::new(Cell { x: 8, y: 2 }, cells(&[(7, 3)])));
+
+    let mut board = Board::new();
+    assert_eq!(board.broken_constraints(&variants), Ok(vec![]));
+
+    // Filled in and kept: 2 + 3 = 5, 1 < 2 and 4 = 4
+    for (x, y, value) in [(0, 0, 2), (1, 0, 3), (0, 2, 1), (1, 2, 2)] {
+      board.set(x, y, value);
+    }
+    board.set(8, 0, 4);
+    board.set(7, 1, 4);
+    // Filled in and broken: 2 + 2 repeats, 5 > 3 and 6 != 7
+    for (x, y, value) in [(0, 1, 2), (1, 1, 2), (0, 3, 5), (1, 3, 3)] {
+      board.set(x, y, value);
+    }
+    board.set(8, 2, 6);
+    board.set(7, 3, 7);
+
+    let broken = |constraint_type, index| BrokenConstraint {
+      constraint_type,
+      index,
+    };
+    assert_eq!(
+      board.broken_constraints(&variants),
+      Ok(vec![
+        broken(ConstraintType::Cage, 1),
+        broken(ConstraintType::Thermometer, 1),
+        broken(ConstraintType::Arrow, 1),
+      ])
+    );
+
+    // A constraint with an empty cell isn't broken yet
+    board.set(7, 3, 0);
+    board.set(1, 3, 0);
+    assert_eq!(
+      board.broken_constraints(&variants),
+      Ok(vec![broken(ConstraintType::Cage, 1)])
+    );
+
+    variants.add_arrow(Arrow::new(Cell { x: 0, y: 0 }, vec![]));
+    assert!(matches!(
+      board.broken_constraints(&variants),
+      Err(SolveError::InvalidArrow(_))
+    ));
+  }
+
+  #[test]
+  fn differences_lists_cells_in_row_order() {
+    let mut first = Board::new();
+    first.set(3, 0, 4);
+    first.set(8, 8, 1);
+    let mut second = Board::from_board(&first);
+    assert_eq!(first.differences(&second), vec![]);
+
+    second.set(8, 8, 2);
+    second.set(5, 2, 7);
+    assert_eq!(
+      first.differences(&second),
+      vec![Cell { x: 5, y: 2 }, Cell { x: 8, y: 8 }]
+    );
   }
 }
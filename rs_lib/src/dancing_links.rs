@@ -1,160 +1,751 @@
-use crate::algorithm_x::{generate_constraint_table, ConstraintTable};
-use std::array;
+use crate::algorithm_x::{
+  pick_row, random_index, ColumnHeuristic, DecisionStrategy, SolverRng,
+};
+use crate::exact_cover::ExactCover;
+use crate::solve_error::SolveError;
 
 #[cfg(test)]
 #[path = "dancing_links_test.rs"]
 mod dancing_links_test;
 
+/// A node in the linked table. The root, the column headers and the cells
+/// all share this layout, and link to their neighbours by their index in
+/// the table's arena.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct Cell {
-  column_index: usize,
-  row_index: usize,
-  up: Option<usize>,
-  down: Option<usize>,
-  left: Option<usize>,
-  right: Option<usize>,
+struct Node {
+  row_index: u32,
+  column_index: u32,
+  up: u32,
+  down: u32,
+  left: u32,
+  right: u32,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct ColumnHeader {
-  cell_count: i32,
-  up: Option<usize>,
-  down: Option<usize>,
-  left: Option<usize>,
-  right: Option<usize>,
+const ROOT: u32 = 0;
+
+/// Row index given to the root and the column headers
+const HEADER_ROW: u32 = u32::MAX;
+
+/// A sparse Dancing Links table. Node 0 is the root, node `c + 1` is the
+/// header of column `c`, and the cells of the exact cover rows follow in
+/// row order. A row gets one cell for every column it covers.
+///
+/// Every ring (the header row, each row of cells, and each column
+/// including its header) is circular.
+#[derive(Clone, Debug, PartialEq)]
+struct LinkedTable {
+  nodes: Vec<Node>,
+  cell_counts: Vec<u32>,
+  /// The first node of every row. A row without cells starts where the
+  /// next row does.
+  row_starts: Vec<u32>,
+  /// Whether each column has been covered
+  covered_columns: Vec<bool>,
+  /// Whether each row has been hidden by a pruner
+  hidden_rows: Vec<bool>,
+  /// The nodes of the rows decisions have already tried, unlinked from
+  /// their decision's column, in the order they were set aside
+  set_aside_nodes: Vec<u32>,
+  /// The rows pruners hid after a decision, in the order they were hidden
+  pruned_rows: Vec<usize>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum Link {
-  ColumnHeader(ColumnHeader),
-  Cell(Cell),
-  EmptyLink,
+#[derive(Debug, Clone, PartialEq)]
+struct Decision {
+  column: usize,
+  selected_row: usize,
+  /// How many rows of the column this decision tried before the selected
+  /// one, the last entries of the table's `set_aside_nodes`
+  set_aside_count: usize,
+  /// How many rows pruners hid once the row was selected, the last entries
+  /// of the table's `pruned_rows`
+  pruned_count: usize,
 }
 
-struct LinkedTable {
-  table: [[Link; 324]; 730],
+/// Restricts a search beyond what exact cover can express, such as the
+/// sums of killer cages
+pub(crate) trait Prune {
+  /// Called with every row selected so far, given rows included, after
+  /// each decision. `is_row_visible` tells whether a row could still be
+  /// selected. Returns the rows that can no longer be part of a solution,
+  /// or `None` if no solution includes the selected rows.
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>>;
+}
+
+fn header_node(col_idx: usize) -> u32 {
+  col_idx as u32 + 1
+}
+
+/// Generates the root followed by a header for every column. The primary
+/// headers are linked left to right with the root, the search has to cover
+/// all of them. Secondary headers are linked only to themselves, so they
+/// are never chosen but are still covered by the rows that use them. Each
+/// header's up and down pointers point to itself until cells are added
+/// beneath it.
+fn generate_column_headers(
+  primary_column_count: usize,
+  secondary_column_count: usize,
+) -> Vec<Node> {
+  let ring_size = primary_column_count as u32 + 1;
+  let node_count = ring_size + secondary_column_count as u32;
+
+  (0..node_count)
+    .map(|index| {
+      let (left, right) = match index < ring_size {
+        true => ((index + ring_size - 1) % ring_size, (index + 1) % ring_size),
+        false => (index, index),
+      };
+
+      Node {
+        row_index: HEADER_ROW,
+        column_index: index.wrapping_sub(1),
+        up: index,
+        down: index,
+        left,
+        right,
+      }
+    })
+    .collect()
 }
 
-impl Default for LinkedTable {
-  fn default() -> Self {
-    LinkedTable {
-      table: [[Link::EmptyLink; 324]; 730],
+fn generate_linked_table(exact_cover: &ExactCover) -> LinkedTable {
+  let column_count = exact_cover.column_count();
+  let row_count = exact_cover.row_count();
+  let cell_count: usize = (0..row_count)
+    .map(|row_idx| exact_cover.row(row_idx).len())
+    .sum();
+
+  let mut nodes = generate_column_headers(
+    exact_cover.primary_column_count(),
+    column_count - exact_cover.primary_column_count(),
+  );
+  nodes.reserve_exact(cell_count);
+
+  let mut linked_table = LinkedTable {
+    nodes,
+    cell_counts: vec![0; column_count],
+    row_starts: Vec::with_capacity(row_count),
+    covered_columns: vec![false; column_count],
+    hidden_rows: vec![false; row_count],
+    set_aside_nodes: vec![],
+    pruned_rows: vec![],
+  };
+
+  for row_idx in 0..row_count {
+    let row_start = linked_table.nodes.len() as u32;
+    linked_table.row_starts.push(row_start);
+
+    for &col_idx in exact_cover.row(row_idx) {
+      linked_table.append_cell(row_idx, col_idx, row_start);
     }
   }
+
+  linked_table
 }
 
-/// Generates a row of column headers with the correct
-/// cell counts. DOES NOT INITIALIZE UP, DOWN, LEFT
-/// RIGHT POINTERS, THOSE ARE LEFT AS `None`
-fn generate_column_headers(constraint_table: &ConstraintTable) -> [Link; 324] {
-  let column_cell_counts = array::from_fn::<i32, 324, _>(|row_index| {
-    let mut count = 0;
-    for column_index in 0..729 {
-      if constraint_table.table[column_index][row_index] {
-        count += 1;
+impl LinkedTable {
+  /// Adds a cell to the bottom of its column and the end of its row
+  fn append_cell(&mut self, row_idx: usize, col_idx: usize, row_start: u32) {
+    let index = self.nodes.len() as u32;
+    let header = header_node(col_idx);
+    let up = self.nodes[header as usize].up;
+    let left = match index == row_start {
+      true => index,
+      false => self.nodes[row_start as usize].left,
+    };
+
+    self.nodes.push(Node {
+      row_index: row_idx as u32,
+      column_index: col_idx as u32,
+      up,
+      down: header,
+      left,
+      right: row_start,
+    });
+
+    self.nodes[up as usize].down = index;
+    self.nodes[header as usize].up = index;
+    self.nodes[left as usize].right = index;
+    self.nodes[row_start as usize].left = index;
+    self.cell_counts[col_idx] += 1;
+  }
+
+  fn node(&self, index: u32) -> &Node {
+    &self.nodes[index as usize]
+  }
+
+  fn node_mut(&mut self, index: u32) -> &mut Node {
+    &mut self.nodes[index as usize]
+  }
+
+  /// Returns the uncovered column picked by the heuristic, `None` once
+  /// every column has been covered
+  fn choose_column(
+    &self,
+    heuristic: ColumnHeuristic,
+    rng: &mut SolverRng,
+  ) -> Option<usize> {
+    let first_header = self.node(ROOT).right;
+    if first_header == ROOT {
+      return None;
+    }
+
+    let mut chosen = self.node(first_header).column_index as usize;
+    if let ColumnHeuristic::FirstUnsatisfied = heuristic {
+      return Some(chosen);
+    }
+
+    let mut ties = 1;
+    let mut header = self.node(first_header).right;
+    // A column no row can satisfy can't be beaten, stop looking
+    while header != ROOT && self.cell_counts[chosen] > 0 {
+      let col_idx = self.node(header).column_index as usize;
+      let count = self.cell_counts[col_idx];
+
+      if count < self.cell_counts[chosen] {
+        chosen = col_idx;
+        ties = 1;
+      } else if count == self.cell_counts[chosen]
+        && matches!(heuristic, ColumnHeuristic::MinimumRemainingRandom)
+      {
+        // Reservoir sampling, every tied column is equally likely to win
+        ties += 1;
+        if random_index(rng, ties) == 0 {
+          chosen = col_idx;
+        }
       }
+
+      header = self.node(header).right;
     }
 
-    count
-  });
-
-  array::from_fn::<Link, 324, _>(|row_index| {
-    Link::ColumnHeader(ColumnHeader {
-      cell_count: column_cell_counts[row_index],
-      up: None,
-      down: None,
-      left: None,
-      right: None,
+    Some(chosen)
+  }
+
+  /// Returns the node of the row that sits in the given column
+  fn row_node(&self, row_idx: usize, col_idx: usize) -> u32 {
+    let mut node = self.row_starts[row_idx];
+    while self.node(node).column_index as usize != col_idx {
+      node = self.node(node).right;
+    }
+
+    node
+  }
+
+  /// Returns the rows still visible in the column, top to bottom.
+  fn visible_rows(&self, col_idx: usize) -> impl Iterator<Item = usize> + '_ {
+    let header = header_node(col_idx);
+    let mut node = self.node(header).down;
+    std::iter::from_fn(move || {
+      if node == header {
+        return None;
+      }
+
+      let row_idx = self.node(node).row_index as usize;
+      node = self.node(node).down;
+      Some(row_idx)
     })
-  })
-}
+  }
 
-fn generate_linked_rows(
-  constraint_table: &ConstraintTable,
-) -> Box<[[Link; 324]; 729]> {
-  let mut linked_rows: Vec<[Link; 324]> = vec![];
+  /// Picks the next row to try in a column, as defined by strategy, after
+  /// `tried` rows of it have been set aside. Returns `None` once every row
+  /// has been tried.
+  fn pick_row(
+    &self,
+    col_idx: usize,
+    tried: usize,
+    strategy: DecisionStrategy,
+    rng: &mut SolverRng,
+  ) -> Option<usize> {
+    let remaining = self.cell_counts[col_idx] as usize;
+    let position = pick_row(remaining, tried, strategy, rng)?;
 
-  for (row_idx, row) in constraint_table.table.iter().enumerate() {
-    let mut current_linked = [Link::EmptyLink; 324];
-    for (col_idx, cell) in row.iter().enumerate() {
-      if !cell {
-        continue;
+    self.visible_rows(col_idx).nth(position)
+  }
+
+  /// Unlinks a row a decision has tried from the decision's column, which
+  /// must be covered, so the decision doesn't pick it again
+  fn set_aside_row(&mut self, row_idx: usize, col_idx: usize) {
+    let node = self.row_node(row_idx, col_idx);
+    let Node { up, down, .. } = *self.node(node);
+    self.node_mut(up).down = down;
+    self.node_mut(down).up = up;
+    self.cell_counts[col_idx] -= 1;
+    self.set_aside_nodes.push(node);
+  }
+
+  /// Relinks the last `count` rows set aside, most recent first
+  fn restore_set_aside_rows(&mut self, count: usize) {
+    for _ in 0..count {
+      let node = self
+        .set_aside_nodes
+        .pop()
+        .expect("Restored rows were set aside");
+      let Node {
+        up,
+        down,
+        column_index,
+        ..
+      } = *self.node(node);
+      self.node_mut(up).down = node;
+      self.node_mut(down).up = node;
+      self.cell_counts[column_index as usize] += 1;
+    }
+  }
+
+  /// Removes the column from the header row, and every row that satisfies
+  /// the column from all the other columns it appears in.
+  fn cover(&mut self, col_idx: usize) {
+    self.covered_columns[col_idx] = true;
+    let header = header_node(col_idx);
+    let Node { left, right, .. } = *self.node(header);
+    self.node_mut(left).right = right;
+    self.node_mut(right).left = left;
+
+    let mut row_node = self.node(header).down;
+    while row_node != header {
+      let mut node = self.node(row_node).right;
+      while node != row_node {
+        let Node {
+          up,
+          down,
+          column_index,
+          ..
+        } = *self.node(node);
+        self.node_mut(up).down = down;
+        self.node_mut(down).up = up;
+        self.cell_counts[column_index as usize] -= 1;
+
+        node = self.node(node).right;
       }
+      row_node = self.node(row_node).down;
+    }
+  }
+
+  /// Exactly reverses `cover`, the column must be the last one covered.
+  fn uncover(&mut self, col_idx: usize) {
+    let header = header_node(col_idx);
+    let mut row_node = self.node(header).up;
+    while row_node != header {
+      let mut node = self.node(row_node).left;
+      while node != row_node {
+        let Node {
+          up,
+          down,
+          column_index,
+          ..
+        } = *self.node(node);
+        self.node_mut(up).down = node;
+        self.node_mut(down).up = node;
+        self.cell_counts[column_index as usize] += 1;
+
+        node = self.node(node).left;
+      }
+      row_node = self.node(row_node).up;
+    }
+
+    let Node { left, right, .. } = *self.node(header);
+    self.node_mut(left).right = header;
+    self.node_mut(right).left = header;
+    self.covered_columns[col_idx] = false;
+  }
+
+  /// Covers every column of the selected row except `col_idx`, which the
+  /// caller has already covered.
+  fn cover_row(&mut self, selected_row: usize, col_idx: usize) {
+    let row_node = self.row_node(selected_row, col_idx);
+    let mut node = self.node(row_node).right;
+    while node != row_node {
+      self.cover(self.node(node).column_index as usize);
+      node = self.node(node).right;
+    }
+  }
+
+  fn uncover_row(&mut self, selected_row: usize, col_idx: usize) {
+    let row_node = self.row_node(selected_row, col_idx);
+    let mut node = self.node(row_node).left;
+    while node != row_node {
+      self.uncover(self.node(node).column_index as usize);
+      node = self.node(node).left;
+    }
+  }
+
+  /// Returns the cells of the row, in order. Empty rows have none.
+  fn row_nodes(&self, row_idx: usize) -> std::ops::Range<u32> {
+    let row_end = match self.row_starts.get(row_idx + 1) {
+      Some(&next_row_start) => next_row_start,
+      None => self.nodes.len() as u32,
+    };
 
-      current_linked[col_idx] = Link::Cell(Cell {
-        column_index: col_idx,
-        row_index: row_idx,
-        up: None,
-        down: None,
-        left: None,
-        right: None,
+    self.row_starts[row_idx]..row_end
+  }
+
+  /// Returns true if the row can still be selected: it has cells, none of
+  /// its columns has been covered and it has not been hidden
+  fn is_row_visible(&self, row_idx: usize) -> bool {
+    let mut cells = self.row_nodes(row_idx);
+
+    !self.hidden_rows[row_idx]
+      && !cells.is_empty()
+      && cells.all(|node| {
+        !self.covered_columns[self.node(node).column_index as usize]
       })
+  }
+
+  /// Removes a visible row from every column it appears in
+  fn hide_row(&mut self, row_idx: usize) {
+    self.hidden_rows[row_idx] = true;
+    for node in self.row_nodes(row_idx) {
+      let Node {
+        up,
+        down,
+        column_index,
+        ..
+      } = *self.node(node);
+      self.node_mut(up).down = down;
+      self.node_mut(down).up = up;
+      self.cell_counts[column_index as usize] -= 1;
     }
-    linked_rows.push(current_linked);
   }
 
-  let linked_arm: Box<[[Link; 324]; 729]> = linked_rows.try_into().unwrap();
+  /// Exactly reverses `hide_row`, the row must be the last one hidden
+  fn unhide_row(&mut self, row_idx: usize) {
+    for node in self.row_nodes(row_idx).rev() {
+      let Node {
+        up,
+        down,
+        column_index,
+        ..
+      } = *self.node(node);
+      self.node_mut(up).down = node;
+      self.node_mut(down).up = node;
+      self.cell_counts[column_index as usize] += 1;
+    }
+    self.hidden_rows[row_idx] = false;
+  }
+
+  /// Hides a row for a pruner, recording it so it can be shown again when
+  /// the decision it followed is undone
+  fn prune_row(&mut self, row_idx: usize) {
+    self.hide_row(row_idx);
+    self.pruned_rows.push(row_idx);
+  }
+
+  /// Exactly reverses the last `count` calls to `prune_row`
+  fn unprune_rows(&mut self, count: usize) {
+    for _ in 0..count {
+      let row_idx = self.pruned_rows.pop().expect("Unpruned rows were pruned");
+      self.unhide_row(row_idx);
+    }
+  }
 
-  linked_arm
+  /// Adds a row to the solution before searching. The row must have at
+  /// least one cell, and none of its columns may have been covered already.
+  fn select_given_row(&mut self, given_row: usize) {
+    let first_col = self.node(self.row_starts[given_row]).column_index as usize;
+    self.cover(first_col);
+    self.cover_row(given_row, first_col);
+  }
 }
 
-/// HAHAHAH
-fn link_linked_table(mut linked_table: LinkedTable) -> LinkedTable {
-  for row_idx in 0..730 {
-    // We know col[0] is the ColumHeader so it is the first link
-    let mut first_link_index: Option<usize> = None;
-    let mut last_link_index: Option<usize> = None;
+/// Undoes decisions until one of them has a row left to try, then selects
+/// that row in place of the decision's previous selection, which is set
+/// aside. Returns false when every decision has been exhausted.
+fn backtrack(
+  linked_table: &mut LinkedTable,
+  decisions: &mut Vec<Decision>,
+  decision_strategy: DecisionStrategy,
+  rng: &mut SolverRng,
+) -> bool {
+  while let Some(decision) = decisions.pop() {
+    linked_table.unprune_rows(decision.pruned_count);
+    linked_table.uncover_row(decision.selected_row, decision.column);
+    linked_table.set_aside_row(decision.selected_row, decision.column);
+    let set_aside_count = decision.set_aside_count + 1;
+
+    let Some(selected_row) = linked_table.pick_row(
+      decision.column,
+      set_aside_count,
+      decision_strategy,
+      rng,
+    ) else {
+      linked_table.restore_set_aside_rows(set_aside_count);
+      linked_table.uncover(decision.column);
+      continue;
+    };
 
-    for col_idx in 0..324 {
-      let curr_cell = &mut linked_table.table[row_idx][col_idx];
+    linked_table.cover_row(selected_row, decision.column);
+    decisions.push(Decision {
+      column: decision.column,
+      selected_row,
+      set_aside_count,
+      pruned_count: 0,
+    });
+
+    return true;
+  }
+
+  false
+}
 
-      match curr_cell {
-        Link::EmptyLink => (),
-        Link::ColumnHeader(col_head) => col_head.right = last_link_index,
-        Link::Cell(cell) => cell.right = last_link_index,
+/// A search for exact covers with Knuth's Dancing Links. The given rows
+/// are part of every solution.
+///
+/// Pruners are consulted after every decision. The rows they rule out are
+/// hidden until the decision is undone, and a selection they reject is
+/// undone straight away.
+///
+/// Iterating yields each solution as the indexes of its rows, smallest
+/// first. The decision stack is kept between solutions, so every call to
+/// `next` resumes the search where the previous one stopped.
+pub struct ExactCoverIter {
+  linked_table: LinkedTable,
+  decisions: Vec<Decision>,
+  given_rows: Vec<usize>,
+  decision_strategy: DecisionStrategy,
+  column_heuristic: ColumnHeuristic,
+  rng: SolverRng,
+  pruners: Vec<Box<dyn Prune>>,
+  /// Scratch buffers for pruning, kept to avoid allocating on every
+  /// decision
+  selected_buffer: Vec<usize>,
+  pruned_buffer: Vec<usize>,
+  /// The decisions make up the last solution found, and have to be
+  /// undone before searching for the next one
+  at_solution: bool,
+  exhausted: bool,
+}
+
+impl ExactCoverIter {
+  /// Selects the given rows and prepares the search. Returns an error if a
+  /// given row does not exist, or if two given rows cover the same column.
+  pub(crate) fn new(
+    exact_cover: &ExactCover,
+    given_rows: &[usize],
+    decision_strategy: DecisionStrategy,
+    column_heuristic: ColumnHeuristic,
+    rng: SolverRng,
+  ) -> Result<Self, SolveError> {
+    let mut linked_table = generate_linked_table(exact_cover);
+
+    let mut covered_columns = vec![false; exact_cover.column_count()];
+    for &given_row in given_rows {
+      if given_row >= exact_cover.row_count() {
+        return Err(SolveError::InvalidRow { row: given_row });
       }
 
-      if curr_cell == &Link::EmptyLink {
+      let given_columns = exact_cover.row(given_row);
+      if given_columns
+        .iter()
+        .any(|&col_idx| covered_columns[col_idx])
+      {
+        return Err(SolveError::ContradictoryGivens(vec![]));
+      }
+      if given_columns.is_empty() {
         continue;
       }
 
-      last_link_index = Some(col_idx);
-      if first_link_index.is_none() {
-        first_link_index = Some(col_idx);
+      for &col_idx in given_columns {
+        covered_columns[col_idx] = true;
       }
+      linked_table.select_given_row(given_row);
     }
-    // Both of these should be initialized, if not, something bigger wrong
-    let first_link_index = first_link_index.unwrap();
-    let last_link_index = last_link_index.unwrap();
 
-    let first_cell = &mut linked_table.table[row_idx][first_link_index];
+    let mut given_rows = given_rows.to_vec();
+    given_rows.sort_unstable();
 
-    match first_cell {
-      Link::EmptyLink => (),
-      Link::ColumnHeader(cell_head) => cell_head.left = Some(last_link_index),
-      Link::Cell(cell) => cell.left = Some(last_link_index),
+    Ok(Self {
+      linked_table,
+      decisions: vec![],
+      given_rows,
+      decision_strategy,
+      column_heuristic,
+      rng,
+      pruners: vec![],
+      selected_buffer: vec![],
+      pruned_buffer: vec![],
+      at_solution: false,
+      exhausted: false,
+    })
+  }
+
+  /// Adds a pruner to the search, which must not have started yet. The
+  /// rows it rules out given the given rows are hidden for good, and the
+  /// search has no solutions if it rejects them.
+  pub(crate) fn add_pruner(&mut self, pruner: Box<dyn Prune>) {
+    debug_assert!(self.decisions.is_empty() && !self.at_solution);
+
+    let is_row_visible = |row_idx| self.linked_table.is_row_visible(row_idx);
+    match pruner.prune(&self.given_rows, &is_row_visible) {
+      Some(pruned_rows) => {
+        for row_idx in pruned_rows {
+          if self.linked_table.is_row_visible(row_idx) {
+            self.linked_table.hide_row(row_idx);
+          }
+        }
+      }
+      None => self.exhausted = true,
     }
 
-    let last_cell = &mut linked_table.table[row_idx][last_link_index];
-    match last_cell {
-      Link::EmptyLink => (),
-      Link::ColumnHeader(cell_head) => cell_head.right = Some(first_link_index),
-      Link::Cell(cell) => cell.right = Some(first_link_index),
+    self.pruners.push(pruner);
+  }
+
+  /// Counts the remaining solutions, stopping once `limit` have been found
+  pub(crate) fn count_up_to(&mut self, limit: usize) -> usize {
+    let mut count = 0;
+    while count < limit && self.advance() {
+      count += 1;
+    }
+
+    count
+  }
+
+  /// Searches for the next solution, returns false once there are none
+  /// left. The solution found is left in the decision stack.
+  pub(crate) fn advance(&mut self) -> bool {
+    if self.exhausted {
+      return false;
+    }
+    if self.at_solution {
+      self.at_solution = false;
+      if !self.backtrack() {
+        return false;
+      }
+    }
+
+    loop {
+      // Step 1: Pick an unsatisfied constraint, if there are none we have
+      // found a solution
+      let Some(column) = self
+        .linked_table
+        .choose_column(self.column_heuristic, &mut self.rng)
+      else {
+        self.at_solution = true;
+        return true;
+      };
+
+      // Step 2: Pick one of the rows that satisfy the constraint, if there
+      // are none a previous decision was wrong
+      let Some(selected_row) = self.linked_table.pick_row(
+        column,
+        0,
+        self.decision_strategy,
+        &mut self.rng,
+      ) else {
+        if !self.backtrack() {
+          return false;
+        }
+        continue;
+      };
+
+      // Step 3: Hide every row that conflicts with the selected row
+      self.linked_table.cover(column);
+      self.linked_table.cover_row(selected_row, column);
+
+      self.decisions.push(Decision {
+        column,
+        selected_row,
+        set_aside_count: 0,
+        pruned_count: 0,
+      });
+
+      // Step 4: Hide the rows the pruners rule out, if they reject the
+      // selected row try another one
+      if !self.prune_last_decision() && !self.backtrack() {
+        return false;
+      }
     }
   }
-  todo!()
-}
 
-fn generate_linked_table() -> LinkedTable {
-  let constraint_table = generate_constraint_table();
-  let mut linked_table = LinkedTable::default();
-  linked_table.table[0] = generate_column_headers(&constraint_table);
-  linked_table.table[1..]
-    .clone_from_slice(&*generate_linked_rows(&constraint_table));
+  /// Undoes decisions until one has another row to try that the pruners
+  /// accept, marking the search exhausted once none do
+  fn backtrack(&mut self) -> bool {
+    loop {
+      if !backtrack(
+        &mut self.linked_table,
+        &mut self.decisions,
+        self.decision_strategy,
+        &mut self.rng,
+      ) {
+        self.exhausted = true;
+        return false;
+      }
 
-  todo!();
+      if self.prune_last_decision() {
+        return true;
+      }
+    }
+  }
+
+  /// Every row selected so far, given rows first
+  fn selected_rows(&self) -> impl Iterator<Item = usize> + '_ {
+    let decided_rows = self.decisions.iter().map(|d| d.selected_row);
+
+    self.given_rows.iter().copied().chain(decided_rows)
+  }
+
+  /// Hides the rows the pruners rule out after the last decision, recording
+  /// them on it. Returns false, hiding nothing, if a pruner rejects the
+  /// selected rows.
+  fn prune_last_decision(&mut self) -> bool {
+    if self.pruners.is_empty() {
+      return true;
+    }
+
+    let mut selected_rows = std::mem::take(&mut self.selected_buffer);
+    selected_rows.clear();
+    selected_rows.extend(self.selected_rows());
+
+    let is_row_visible = |row_idx| self.linked_table.is_row_visible(row_idx);
+    let mut pruned_rows = std::mem::take(&mut self.pruned_buffer);
+    pruned_rows.clear();
+    let mut accepted = true;
+    for pruner in &self.pruners {
+      match pruner.prune(&selected_rows, &is_row_visible) {
+        Some(rows) => pruned_rows.extend(rows),
+        None => {
+          accepted = false;
+          break;
+        }
+      }
+    }
+
+    if accepted {
+      let mut pruned_count = 0;
+      for &row_idx in &pruned_rows {
+        if self.linked_table.is_row_visible(row_idx) {
+          self.linked_table.prune_row(row_idx);
+          pruned_count += 1;
+        }
+      }
+      self
+        .decisions
+        .last_mut()
+        .expect("Pruning follows a decision")
+        .pruned_count = pruned_count;
+    }
+
+    self.selected_buffer = selected_rows;
+    self.pruned_buffer = pruned_rows;
+
+    accepted
+  }
 }
 
-pub fn launch_dancing_links() {
-  let linked_tablre = generate_linked_table();
+impl Iterator for ExactCoverIter {
+  type Item = Vec<usize>;
+
+  fn next(&mut self) -> Option<Vec<usize>> {
+    if !self.advance() {
+      return None;
+    }
+
+    let mut solution: Vec<usize> = self.selected_rows().collect();
+    solution.sort_unstable();
+
+    Some(solution)
+  }
 }
@@ -1,56 +1,442 @@
 use super::*;
+use crate::algorithm_x::{generate_exact_cover, solver_rng};
+use crate::board::Board;
+use std::collections::HashSet;
+use std::mem::size_of;
 
 #[test]
 fn test_generate_column_headers() {
-  let constraint_table = generate_constraint_table();
-  let headers = generate_column_headers(&constraint_table);
-  let correct_header = Link::ColumnHeader(ColumnHeader {
-    cell_count: 9,
-    up: None,
-    down: None,
-    left: None,
-    right: None,
-  });
+  let headers = generate_column_headers(324, 0);
+  assert_eq!(headers.len(), 325);
 
-  for header in headers {
-    assert_eq!(header, correct_header);
+  assert_eq!(headers[0].left, 324);
+  assert_eq!(headers[0].right, 1);
+  assert_eq!(headers[324].right, ROOT);
+
+  for (index, header) in headers.iter().enumerate().skip(1) {
+    assert_eq!(header.row_index, HEADER_ROW);
+    assert_eq!(header.column_index as usize, index - 1);
+    assert_eq!(header.up as usize, index);
+    assert_eq!(header.down as usize, index);
+  }
+}
+
+#[test]
+fn test_secondary_column_headers() {
+  let headers = generate_column_headers(2, 2);
+  assert_eq!(headers.len(), 5);
+
+  // The root only links the primary headers
+  assert_eq!(headers[0].left, 2);
+  assert_eq!(headers[2].right, ROOT);
+  for (index, header) in headers.iter().enumerate().skip(3) {
+    assert_eq!(header.left as usize, index);
+    assert_eq!(header.right as usize, index);
+    assert_eq!(header.column_index as usize, index - 1);
   }
 }
 
 #[test]
-fn test_generate_linked_rows() {
-  let constraint_table = generate_constraint_table();
-  let rows = generate_linked_rows(&constraint_table);
+fn test_generate_linked_table() {
+  let mut rng = solver_rng(Some(0));
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let linked_table = generate_linked_table(&exact_cover);
+
+  // One node per true entry in the constraint table, plus the headers
+  assert_eq!(linked_table.nodes.len(), 1 + 324 + 729 * 4);
+  assert!(linked_table.cell_counts.iter().all(|count| *count == 9));
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::FirstUnsatisfied, &mut rng),
+    Some(0)
+  );
+
+  // Placing a 1 at 0,0 satisfies columns 0, 81, 162 and 243
+  let row_start = linked_table.row_starts[0];
+  let mut columns = vec![];
+  let mut node = row_start;
+  loop {
+    columns.push(linked_table.node(node).column_index);
+    node = linked_table.node(node).right;
+    if node == row_start {
+      break;
+    }
+  }
+  assert_eq!(columns, vec![0, 81, 162, 243]);
+  assert_eq!(linked_table.node(row_start).left, row_start + 3);
+
+  // "Row 0 has a 1" is satisfied by placing a 1 in any of the first 9 cells
+  assert_eq!(
+    linked_table.visible_rows(0).collect::<Vec<_>>(),
+    vec![0, 9, 18, 27, 36, 45, 54, 63, 72]
+  );
+  let bottom = linked_table.node(header_node(0)).up;
+  assert_eq!(linked_table.node(bottom).row_index, 72);
+  assert_eq!(linked_table.node(bottom).down, header_node(0));
+
+  assert_eq!(linked_table.visible_rows(323).count(), 9);
+}
+
+#[test]
+fn test_cover_and_uncover() {
+  let mut rng = solver_rng(Some(0));
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let original = generate_linked_table(&exact_cover);
+  let mut linked_table = original.clone();
 
-  let num_cells_first_row =
-    rows[0].iter().filter(|x| **x != Link::EmptyLink).count();
+  linked_table.cover(0);
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::FirstUnsatisfied, &mut rng),
+    Some(1)
+  );
+  // Placing a 1 at 1,0 satisfies column 0, so it is hidden from column 90
+  assert_eq!(
+    linked_table.visible_rows(90).collect::<Vec<_>>(),
+    vec![90, 171, 252, 333, 414, 495, 576, 657]
+  );
+  assert_eq!(linked_table.cell_counts[90], 8);
 
-  assert_eq!(num_cells_first_row, 4);
+  linked_table.cover(100);
+  linked_table.uncover(100);
+  linked_table.uncover(0);
+  assert!(linked_table == original);
+}
+
+#[test]
+fn test_select_given_rows() {
+  // This was tested by first running the algorithm
+  // organically through 150 iterations, then
+  // recording the solution set and the rows it hid.
+  //
+  // Once the given rows are selected, every other row
+  // must still be visible.
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let mut linked_table = generate_linked_table(&exact_cover);
+
+  let given_rows = HashSet::from([
+    107, 305, 128, 360, 118, 205, 401, 236, 87, 80, 264, 226, 10, 70, 148, 50,
+    156, 140, 216, 252, 346, 97, 20, 215, 175, 30, 108, 185, 60, 272, 165, 322,
+    352, 326, 390, 292, 40, 244, 195, 284, 312, 0,
+  ]);
+  let hidden_rows = HashSet::from([
+    432, 29, 596, 278, 633, 398, 247, 492, 34, 306, 211, 338, 75, 476, 26, 184,
+    92, 196, 684, 499, 676, 577, 327, 359, 363, 697, 489, 425, 4, 361, 658,
+    228, 396, 255, 418, 378, 111, 114, 210, 671, 485, 290, 275, 529, 366, 11,
+    441, 288, 162, 267, 595, 329, 8, 274, 723, 459, 136, 251, 427, 235, 539,
+    455, 385, 367, 552, 121, 402, 315, 458, 42, 266, 39, 132, 707, 616, 129,
+    349, 380, 190, 523, 311, 382, 56, 442, 509, 405, 257, 393, 89, 403, 119,
+    634, 52, 207, 57, 695, 641, 253, 32, 546, 364, 356, 636, 443, 590, 53, 117,
+    137, 282, 212, 610, 76, 436, 220, 152, 664, 84, 454, 677, 516, 71, 113,
+    421, 462, 59, 74, 238, 553, 287, 362, 17, 482, 12, 246, 109, 139, 388, 19,
+    567, 303, 151, 90, 506, 145, 573, 570, 434, 295, 88, 300, 452, 342, 373,
+    689, 27, 314, 678, 654, 55, 383, 717, 276, 263, 431, 626, 45, 78, 104, 642,
+    54, 100, 112, 219, 103, 221, 213, 188, 245, 150, 583, 670, 249, 68, 391,
+    576, 519, 535, 258, 158, 394, 293, 384, 313, 168, 317, 72, 438, 386, 101,
+    445, 13, 110, 323, 187, 149, 631, 146, 28, 301, 6, 270, 357, 16, 234, 231,
+    600, 372, 85, 513, 155, 484, 649, 472, 153, 202, 254, 354, 728, 340, 637,
+    138, 483, 157, 669, 608, 161, 561, 681, 644, 243, 91, 347, 351, 343, 299,
+    120, 548, 397, 614, 374, 487, 550, 469, 515, 522, 514, 344, 83, 597, 224,
+    332, 166, 125, 540, 381, 467, 399, 309, 63, 18, 122, 471, 387, 594, 714,
+    480, 179, 433, 48, 556, 406, 73, 7, 289, 201, 651, 414, 486, 256, 404, 49,
+    98, 331, 533, 66, 62, 159, 222, 334, 426, 163, 508, 144, 675, 135, 423, 58,
+    698, 512, 192, 61, 281, 265, 727, 336, 674, 328, 1, 198, 99, 229, 408, 93,
+    350, 174, 134, 116, 147, 569, 194, 371, 102, 330, 368, 67, 232, 428, 37,
+    589, 2, 131, 22, 31, 79, 286, 241, 563, 106, 46, 164, 181, 603, 280, 44,
+    320, 277, 127, 718, 657, 248, 648, 588, 474, 337, 379, 172, 333, 646, 218,
+    262, 64, 607, 502, 171, 23, 464, 446, 369, 173, 725, 593, 587, 230, 691,
+    41, 496, 617, 3, 715, 279, 450, 395, 479, 297, 465, 204, 142, 35, 9, 495,
+    269, 209, 566, 712, 21, 400, 177, 273, 176, 160, 123, 186, 415, 82, 86,
+    661, 217, 214, 560, 197, 298, 291, 365, 650, 555, 437, 77, 407, 95, 193,
+    124, 51, 417, 259, 182, 302, 304, 668, 94, 318, 527, 261, 271, 14, 701,
+    199, 488, 36, 702, 283, 296, 451, 627, 688, 377, 621, 69, 180, 96, 237,
+    409, 141, 189, 169, 200, 310, 389, 448, 370, 466, 208, 308, 629, 545, 24,
+    115, 321, 565, 353, 227, 507, 203, 416, 647, 206, 65, 604, 335, 536, 710,
+    435, 15, 294, 355, 475, 183, 580, 392, 47, 722, 154, 191, 126, 223, 268,
+    239, 170, 319, 81, 260, 348, 242, 130, 233, 324, 250, 411, 133, 307, 325,
+    473, 316, 240, 358, 38, 43, 143, 167, 33, 708, 105, 620, 25, 285, 526, 568,
+    5, 178, 424, 345, 225, 685,
+  ]);
 
-  let mut num_cells_last_column = 0;
-  for index in 0..729 {
-    if rows[index][323] != Link::EmptyLink {
-      num_cells_last_column += 1;
+  let mut satisfied_columns: HashSet<usize> = HashSet::new();
+  for given_row in &given_rows {
+    satisfied_columns.extend(exact_cover.row(*given_row));
+    linked_table.select_given_row(*given_row);
+  }
+
+  let mut visible_rows: HashSet<usize> = HashSet::new();
+  for col_idx in 0..324 {
+    if !satisfied_columns.contains(&col_idx) {
+      visible_rows.extend(linked_table.visible_rows(col_idx));
     }
   }
 
-  assert_eq!(num_cells_last_column, 9);
+  let expected_visible_rows: HashSet<usize> = (0..729)
+    .filter(|row| !given_rows.contains(row) && !hidden_rows.contains(row))
+    .collect();
+  assert_eq!(visible_rows, expected_visible_rows);
+}
+
+#[test]
+fn test_conflicting_given_rows() {
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+
+  // Placing a 1 at 0,0 and a 1 at 1,0 both satisfy "Row 0 has a 1"
+  let result = ExactCoverIter::new(
+    &exact_cover,
+    &[0, 9],
+    DecisionStrategy::First,
+    ColumnHeuristic::FirstUnsatisfied,
+    solver_rng(Some(0)),
+  );
+  assert!(matches!(
+    result,
+    Err(SolveError::ContradictoryGivens(conflicts)) if conflicts.is_empty()
+  ));
+
+  let result = ExactCoverIter::new(
+    &exact_cover,
+    &[729],
+    DecisionStrategy::First,
+    ColumnHeuristic::FirstUnsatisfied,
+    solver_rng(Some(0)),
+  );
+  assert!(matches!(result, Err(SolveError::InvalidRow { row: 729 })));
+}
+
+#[test]
+fn test_backtracking() {
+  let mut rng = solver_rng(Some(0));
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let original = generate_linked_table(&exact_cover);
+  let mut linked_table = original.clone();
+
+  linked_table.cover(0);
+  let selected_row = linked_table
+    .pick_row(0, 0, DecisionStrategy::First, &mut rng)
+    .unwrap();
+  assert_eq!(selected_row, 0);
+  linked_table.cover_row(selected_row, 0);
+  let mut decisions = vec![Decision {
+    column: 0,
+    selected_row,
+    set_aside_count: 0,
+    pruned_count: 0,
+  }];
+
+  assert!(backtrack(
+    &mut linked_table,
+    &mut decisions,
+    DecisionStrategy::First,
+    &mut rng
+  ));
+  // First tries the top row, then the rest from the bottom up
+  assert_eq!(
+    decisions,
+    vec![Decision {
+      column: 0,
+      selected_row: 72,
+      set_aside_count: 1,
+      pruned_count: 0,
+    }]
+  );
+  // The row tried first is set aside in the covered column
+  assert_eq!(
+    linked_table.visible_rows(0).collect::<Vec<_>>(),
+    vec![9, 18, 27, 36, 45, 54, 63, 72]
+  );
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::FirstUnsatisfied, &mut rng),
+    Some(1)
+  );
+
+  let mut selected_rows = vec![];
+  while backtrack(
+    &mut linked_table,
+    &mut decisions,
+    DecisionStrategy::First,
+    &mut rng,
+  ) {
+    selected_rows.push(decisions[0].selected_row);
+  }
+  assert_eq!(selected_rows, vec![63, 54, 45, 36, 27, 18, 9]);
+  assert!(decisions.is_empty());
+  assert!(linked_table == original);
+}
+
+#[test]
+fn test_resumes_the_search() {
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let mut solutions = ExactCoverIter::new(
+    &exact_cover,
+    &[0, 89],
+    DecisionStrategy::First,
+    ColumnHeuristic::MinimumRemaining,
+    solver_rng(Some(0)),
+  )
+  .unwrap();
+
+  let first = solutions.next().unwrap();
+  let second = solutions.next().unwrap();
+  assert_ne!(first, second);
+  for solution in [&first, &second] {
+    assert_eq!(solution.len(), 81);
+    assert!(solution.contains(&0));
+    assert!(solution.contains(&89));
+    assert!(solution.windows(2).all(|pair| pair[0] < pair[1]));
+  }
+}
+
+/// Hides its rows once the trigger row has been selected, or from the
+/// start without one. Rejects any selection that includes the rejected row.
+struct TestPruner {
+  trigger_row: Option<usize>,
+  hidden_rows: Vec<usize>,
+  rejected_row: Option<usize>,
+}
+
+impl Prune for TestPruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    _is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    if matches!(self.rejected_row, Some(row) if selected_rows.contains(&row)) {
+      return None;
+    }
+
+    match self.trigger_row {
+      Some(row) if !selected_rows.contains(&row) => Some(vec![]),
+      _ => Some(self.hidden_rows.clone()),
+    }
+  }
 }
 
-use std::mem::{align_of, size_of, size_of_val};
+#[test]
+fn test_pruners() {
+  // A quarter of the 288 4x4 boards have a 1 in the top left corner, row 0,
+  // and a quarter have a 1 in the cell diagonally below it, row 20
+  let exact_cover =
+    generate_exact_cover(&Board::with_box_size(2).unwrap(), &[], &[]).unwrap();
+  let search = |given_rows: &[usize], pruner: TestPruner| {
+    let mut solutions = exact_cover.solutions(given_rows).unwrap();
+    solutions.add_pruner(Box::new(pruner));
+    solutions
+  };
+
+  let hiding = search(
+    &[],
+    TestPruner {
+      trigger_row: None,
+      hidden_rows: vec![0],
+      rejected_row: None,
+    },
+  );
+  assert_eq!(hiding.count(), 216);
+
+  let mut rejecting = search(
+    &[],
+    TestPruner {
+      trigger_row: None,
+      hidden_rows: vec![],
+      rejected_row: Some(20),
+    },
+  );
+  let original = rejecting.linked_table.clone();
+  assert_eq!(rejecting.count_up_to(1000), 216);
+  assert!(rejecting.linked_table == original);
+
+  // With a 1 in the corner, the cell diagonally below it holds a 2, row 21,
+  // on a third of the boards. Hiding it is undone with the decision.
+  let mut triggered = search(
+    &[],
+    TestPruner {
+      trigger_row: Some(0),
+      hidden_rows: vec![21],
+      rejected_row: None,
+    },
+  );
+  let original = triggered.linked_table.clone();
+  assert_eq!(triggered.count_up_to(1000), 264);
+  assert!(triggered.linked_table == original);
+
+  // Rejecting a given row leaves no solutions
+  let rejecting_givens = search(
+    &[0],
+    TestPruner {
+      trigger_row: None,
+      hidden_rows: vec![],
+      rejected_row: Some(0),
+    },
+  );
+  assert_eq!(rejecting_givens.count(), 0);
+}
 
 #[test]
 fn test_mem() {
-  let linked_row = [Link::EmptyLink; 324];
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let linked_table = generate_linked_table(&exact_cover);
+
+  println!("size_of::<Node>() = {}", size_of::<Node>());
+  println!(
+    "size_of(nodes) = {}",
+    linked_table.nodes.len() * size_of::<Node>()
+  );
 
-  println!("size_of::<Link>() = {}", size_of::<Link>());
-  println!("align_of::<Link>() = {}", align_of::<Link>());
+  assert_eq!(size_of::<Node>(), 24);
+  assert!(linked_table.nodes.len() * size_of::<Node>() < 80_000);
+}
+
+#[test]
+fn test_solves_on_a_small_stack() {
+  let solution = std::thread::Builder::new()
+    .stack_size(64 * 1024)
+    .spawn(|| {
+      generate_exact_cover(&Board::new(), &[], &[])
+        .unwrap()
+        .first_solution(&[])
+        .unwrap()
+        .unwrap()
+    })
+    .unwrap()
+    .join()
+    .unwrap();
+
+  assert_eq!(solution.len(), 81);
+}
+
+#[test]
+fn test_choose_column() {
+  let mut rng = solver_rng(Some(0));
+  let exact_cover = generate_exact_cover(&Board::new(), &[], &[]).unwrap();
+  let mut linked_table = generate_linked_table(&exact_cover);
 
-  println!("size_of(linked_row) = {}", size_of_val(&linked_row));
+  // Every column starts with 9 rows, so ties go to the lowest index
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::MinimumRemaining, &mut rng),
+    Some(0)
+  );
 
-  let boxed = Box::new(linked_row);
-  println!("size_of(boxed_linked_row) = {}", size_of_val(&boxed));
-  println!("size_of(*boxed_linked_row) = {}", size_of_val(&*boxed));
+  // Placing a 1 at 0,0 leaves "Row 1 has a 1" with 6 rows, the 1 can't go
+  // in column 0 or in the top left sub grid
+  linked_table.select_given_row(0);
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::FirstUnsatisfied, &mut rng),
+    Some(1)
+  );
+  assert_eq!(
+    linked_table.choose_column(ColumnHeuristic::MinimumRemaining, &mut rng),
+    Some(9)
+  );
+  assert_eq!(linked_table.cell_counts[9], 6);
+  // The covered columns' counts are no longer kept up to date
+  assert!(linked_table
+    .cell_counts
+    .iter()
+    .enumerate()
+    .filter(|(col_idx, _)| ![0, 81, 162, 243].contains(col_idx))
+    .all(|(_, count)| *count >= 6));
 
-  todo!()
+  let random_column = linked_table
+    .choose_column(ColumnHeuristic::MinimumRemainingRandom, &mut rng)
+    .unwrap();
+  assert_eq!(linked_table.cell_counts[random_column], 6);
 }
@@ -0,0 +1,174 @@
+use crate::algorithm_x::{solver_rng, ColumnHeuristic, DecisionStrategy};
+use crate::dancing_links::ExactCoverIter;
+use crate::solve_error::SolveError;
+use wasm_bindgen::prelude::*;
+
+#[cfg(test)]
+#[path = "exact_cover_test.rs"]
+mod exact_cover_test;
+
+/// A general exact cover problem: a set of rows over a number of columns,
+/// where a solution is a set of rows that covers every column exactly once.
+/// Sudoku is one such problem, but nothing here knows about boards.
+///
+/// Secondary columns come after the primary ones, and only have to be
+/// covered at most once. A solution may leave them uncovered.
+///
+/// Solutions are searched for with Dancing Links, using the same decision
+/// strategies and column heuristics as `launch_algorithm_x`. They are
+/// returned as the indexes of their rows, smallest first.
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct ExactCover {
+  primary_column_count: usize,
+  secondary_column_count: usize,
+  /// The columns of every row, one row after another
+  cells: Vec<usize>,
+  /// Where every row starts in `cells`, followed by where the last row ends
+  row_offsets: Vec<usize>,
+  decision_strategy: DecisionStrategy,
+  column_heuristic: ColumnHeuristic,
+  seed: Option<u64>,
+}
+
+#[wasm_bindgen]
+impl ExactCover {
+  /// Creates a problem with the given number of columns and no rows. The
+  /// search picks the first row and the column with the fewest remaining
+  /// rows until told otherwise.
+  #[wasm_bindgen(constructor)]
+  pub fn new(column_count: usize) -> Self {
+    Self::with_secondary_columns(column_count, 0)
+  }
+
+  /// Creates a problem with primary columns `0..primary_column_count`,
+  /// followed by secondary columns that may be covered at most once
+  pub fn with_secondary_columns(
+    primary_column_count: usize,
+    secondary_column_count: usize,
+  ) -> Self {
+    Self {
+      primary_column_count,
+      secondary_column_count,
+      cells: vec![],
+      row_offsets: vec![0],
+      decision_strategy: DecisionStrategy::First,
+      column_heuristic: ColumnHeuristic::MinimumRemaining,
+      seed: None,
+    }
+  }
+
+  /// The number of primary and secondary columns together
+  pub fn column_count(&self) -> usize {
+    self.primary_column_count + self.secondary_column_count
+  }
+
+  pub fn primary_column_count(&self) -> usize {
+    self.primary_column_count
+  }
+
+  pub fn row_count(&self) -> usize {
+    self.row_offsets.len() - 1
+  }
+
+  /// Adds a row covering the given columns and returns its index. A column
+  /// listed more than once is only covered once.
+  pub fn add_row(&mut self, columns: &[usize]) -> Result<usize, SolveError> {
+    let column_count = self.column_count();
+    if let Some(&column) = columns.iter().find(|&&c| c >= column_count) {
+      return Err(SolveError::InvalidColumn { column });
+    }
+
+    let mut columns = columns.to_vec();
+    columns.sort_unstable();
+    columns.dedup();
+    self.cells.extend(columns);
+    self.row_offsets.push(self.cells.len());
+
+    Ok(self.row_count() - 1)
+  }
+
+  pub fn set_decision_strategy(&mut self, decision_strategy: DecisionStrategy) {
+    self.decision_strategy = decision_strategy;
+  }
+
+  pub fn set_column_heuristic(&mut self, column_heuristic: ColumnHeuristic) {
+    self.column_heuristic = column_heuristic;
+  }
+
+  /// Seeds the random choices, so the same seed always finds the same
+  /// solutions. Without a seed every search is seeded at random.
+  pub fn set_seed(&mut self, seed: Option<u64>) {
+    self.seed = seed;
+  }
+
+  /// Returns the first solution that includes every given row, or `None`
+  /// if there is no such solution
+  pub fn first_solution(
+    &self,
+    given_rows: &[usize],
+  ) -> Result<Option<Vec<usize>>, SolveError> {
+    Ok(self.solutions(given_rows)?.next())
+  }
+
+  /// Counts the solutions that include every given row, stopping once
+  /// `limit` have been found. No solution is collected while counting.
+  pub fn count_solutions(
+    &self,
+    given_rows: &[usize],
+    limit: usize,
+  ) -> Result<usize, SolveError> {
+    Ok(self.solutions(given_rows)?.count_up_to(limit))
+  }
+
+  /// Like `all_solutions`, with each solution as a `Uint32Array`
+  #[wasm_bindgen(js_name = all_solutions)]
+  pub fn all_solutions_js(
+    &self,
+    given_rows: &[usize],
+    limit: usize,
+  ) -> Result<js_sys::Array, SolveError> {
+    let solutions = self.solutions(given_rows)?.take(limit);
+
+    Ok(
+      solutions
+        .map(|solution| {
+          let rows: Vec<u32> = solution.iter().map(|&row| row as u32).collect();
+          js_sys::Uint32Array::from(rows.as_slice())
+        })
+        .collect(),
+    )
+  }
+}
+
+impl ExactCover {
+  /// Returns the columns the row covers, smallest first
+  pub fn row(&self, row_idx: usize) -> &[usize] {
+    &self.cells[self.row_offsets[row_idx]..self.row_offsets[row_idx + 1]]
+  }
+
+  /// Lazily searches for the solutions that include every given row.
+  /// Returns an error if a given row does not exist, or if two given rows
+  /// cover the same column.
+  pub fn solutions(
+    &self,
+    given_rows: &[usize],
+  ) -> Result<ExactCoverIter, SolveError> {
+    ExactCoverIter::new(
+      self,
+      given_rows,
+      self.decision_strategy,
+      self.column_heuristic,
+      solver_rng(self.seed),
+    )
+  }
+
+  /// Returns up to `limit` solutions that include every given row
+  pub fn all_solutions(
+    &self,
+    given_rows: &[usize],
+    limit: usize,
+  ) -> Result<Vec<Vec<usize>>, SolveError> {
+    Ok(self.solutions(given_rows)?.take(limit).collect())
+  }
+}
@@ -0,0 +1,158 @@
+use super::*;
+use std::collections::HashSet;
+
+/// The example from Knuth's Dancing Links paper, rows B, D and F are its
+/// only solution
+fn knuth_example() -> ExactCover {
+  let mut exact_cover = ExactCover::new(7);
+  for columns in [
+    vec![0, 3, 6],
+    vec![0, 3],
+    vec![3, 4, 6],
+    vec![2, 4, 5],
+    vec![1, 2, 5, 6],
+    vec![1, 6],
+  ] {
+    exact_cover.add_row(&columns).unwrap();
+  }
+
+  exact_cover
+}
+
+#[test]
+fn test_add_row() {
+  let mut exact_cover = ExactCover::new(3);
+  assert_eq!(exact_cover.add_row(&[2, 0, 2]), Ok(0));
+  assert_eq!(exact_cover.add_row(&[]), Ok(1));
+  assert_eq!(exact_cover.row(0), &[0, 2]);
+  assert!(exact_cover.row(1).is_empty());
+
+  assert_eq!(
+    exact_cover.add_row(&[1, 3]),
+    Err(SolveError::InvalidColumn { column: 3 })
+  );
+  assert_eq!(exact_cover.row_count(), 2);
+}
+
+#[test]
+fn test_solves_knuths_example() {
+  for decision_strategy in [DecisionStrategy::First, DecisionStrategy::Random] {
+    for column_heuristic in [
+      ColumnHeuristic::FirstUnsatisfied,
+      ColumnHeuristic::MinimumRemaining,
+      ColumnHeuristic::MinimumRemainingRandom,
+    ] {
+      let mut exact_cover = knuth_example();
+      exact_cover.set_decision_strategy(decision_strategy);
+      exact_cover.set_column_heuristic(column_heuristic);
+
+      assert_eq!(exact_cover.first_solution(&[]), Ok(Some(vec![1, 3, 5])));
+      assert_eq!(exact_cover.all_solutions(&[], 10), Ok(vec![vec![1, 3, 5]]));
+      assert_eq!(exact_cover.count_solutions(&[], 10), Ok(1));
+    }
+  }
+}
+
+#[test]
+fn test_given_rows() {
+  let exact_cover = knuth_example();
+  assert_eq!(exact_cover.first_solution(&[5, 1]), Ok(Some(vec![1, 3, 5])));
+  assert_eq!(exact_cover.first_solution(&[0]), Ok(None));
+  assert_eq!(exact_cover.count_solutions(&[0], 10), Ok(0));
+
+  assert!(matches!(
+    exact_cover.first_solution(&[1, 0]),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+  assert_eq!(
+    exact_cover.count_solutions(&[6], 10),
+    Err(SolveError::InvalidRow { row: 6 })
+  );
+}
+
+#[test]
+fn test_all_and_count_modes() {
+  // Both columns are covered by rows 0 and 1 together, or by row 2 alone.
+  // Row 3 covers nothing, so it is never needed.
+  let mut exact_cover = ExactCover::new(2);
+  for columns in [vec![0], vec![1], vec![0, 1], vec![]] {
+    exact_cover.add_row(&columns).unwrap();
+  }
+
+  let solutions: HashSet<Vec<usize>> = exact_cover
+    .all_solutions(&[], 10)
+    .unwrap()
+    .into_iter()
+    .collect();
+  assert_eq!(solutions, HashSet::from([vec![0, 1], vec![2]]));
+  assert_eq!(exact_cover.all_solutions(&[], 1).unwrap().len(), 1);
+  assert_eq!(exact_cover.count_solutions(&[], 10), Ok(2));
+  assert_eq!(exact_cover.count_solutions(&[], 1), Ok(1));
+  assert_eq!(exact_cover.count_solutions(&[3], 10), Ok(2));
+  assert_eq!(exact_cover.first_solution(&[3, 2]), Ok(Some(vec![2, 3])));
+
+  // With no columns, choosing no rows covers everything
+  assert_eq!(ExactCover::new(0).all_solutions(&[], 10), Ok(vec![vec![]]));
+}
+
+#[test]
+fn test_seed_reproduces_solutions() {
+  let mut exact_cover = ExactCover::new(2);
+  for columns in [vec![0], vec![1], vec![0, 1], vec![0], vec![1]] {
+    exact_cover.add_row(&columns).unwrap();
+  }
+  exact_cover.set_decision_strategy(DecisionStrategy::Random);
+  exact_cover.set_seed(Some(5));
+
+  let solutions = exact_cover.all_solutions(&[], 10).unwrap();
+  assert_eq!(solutions.len(), 5);
+  assert_eq!(exact_cover.all_solutions(&[], 10).unwrap(), solutions);
+}
+
+/// The n queens puzzle: every rank and file holds exactly one queen, every
+/// diagonal at most one
+fn queens(n: usize) -> ExactCover {
+  let diagonal_count = 2 * n - 1;
+  let mut exact_cover =
+    ExactCover::with_secondary_columns(2 * n, 2 * diagonal_count);
+
+  for rank in 0..n {
+    for file in 0..n {
+      let diagonal = 2 * n + rank + file;
+      let anti_diagonal = 2 * n + diagonal_count + rank + n - 1 - file;
+      exact_cover
+        .add_row(&[rank, n + file, diagonal, anti_diagonal])
+        .unwrap();
+    }
+  }
+
+  exact_cover
+}
+
+#[test]
+fn test_secondary_columns() {
+  assert_eq!(queens(4).count_solutions(&[], 100), Ok(2));
+  assert_eq!(queens(8).count_solutions(&[], 100), Ok(92));
+
+  let exact_cover = queens(4);
+  assert_eq!(exact_cover.column_count(), 22);
+  assert_eq!(exact_cover.primary_column_count(), 8);
+  let solutions = exact_cover.all_solutions(&[], 10).unwrap();
+  assert_eq!(solutions, vec![vec![2, 4, 11, 13], vec![1, 7, 8, 14]]);
+
+  // Queens at a1 and b2 share a diagonal, which may be covered only once
+  assert!(matches!(
+    exact_cover.first_solution(&[0, 5]),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+
+  // A secondary column can be left uncovered
+  let mut exact_cover = ExactCover::with_secondary_columns(1, 1);
+  exact_cover.add_row(&[0]).unwrap();
+  exact_cover.add_row(&[0, 1]).unwrap();
+  assert_eq!(exact_cover.count_solutions(&[], 10), Ok(2));
+  assert_eq!(
+    exact_cover.add_row(&[2]),
+    Err(SolveError::InvalidColumn { column: 2 })
+  );
+}
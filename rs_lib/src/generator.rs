@@ -0,0 +1,441 @@
+use crate::algorithm_x::{
+  count_solutions, launch_algorithm_x, random_index, solver_rng,
+  ColumnHeuristic, DecisionStrategy,
+};
+use crate::board::{Board, Cell};
+use crate::solve_error::SolveError;
+use crate::variants::Variants;
+use rand::Rng;
+use wasm_bindgen::prelude::*;
+
+/// The symmetry of a puzzle's clues. Clues are removed an orbit at a time,
+/// the cells the symmetry's turns and mirrors map onto each other.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+  /// Each cell is its own orbit
+  None,
+  /// The clues look the same after half a turn
+  HalfTurn,
+  /// The clues look the same after a quarter turn
+  QuarterTurn,
+  /// The clues mirror across the middle row, top to bottom
+  HorizontalMirror,
+  /// The clues mirror across the middle column, left to right
+  VerticalMirror,
+  /// The clues mirror across the main diagonal, from the top left corner
+  Diagonal,
+  /// Every turn and mirror of the board at once
+  Dihedral,
+}
+
+impl Symmetry {
+  /// The cells the symmetry maps a cell to on boards with `size` cells on
+  /// each side, the cell itself included
+  fn images(self, cell: Cell, size: usize) -> Vec<Cell> {
+    let Cell { x, y } = cell;
+    let last = size - 1;
+    let half_turn = Cell {
+      x: last - x,
+      y: last - y,
+    };
+    let quarter_turns = [
+      Cell { x: last - y, y: x },
+      half_turn,
+      Cell { x: y, y: last - x },
+    ];
+    let horizontal_mirror = Cell { x, y: last - y };
+    let vertical_mirror = Cell { x: last - x, y };
+    let diagonal = Cell { x: y, y: x };
+    let anti_diagonal = Cell {
+      x: last - y,
+      y: last - x,
+    };
+
+    let mut images = vec![cell];
+    match self {
+      Symmetry::None => {}
+      Symmetry::HalfTurn => images.push(half_turn),
+      Symmetry::QuarterTurn => images.extend(quarter_turns),
+      Symmetry::HorizontalMirror => images.push(horizontal_mirror),
+      Symmetry::VerticalMirror => images.push(vertical_mirror),
+      Symmetry::Diagonal => images.push(diagonal),
+      Symmetry::Dihedral => {
+        images.extend(quarter_turns);
+        images.extend([
+          horizontal_mirror,
+          vertical_mirror,
+          diagonal,
+          anti_diagonal,
+        ]);
+      }
+    }
+
+    images
+  }
+}
+
+/// Every orbit of the symmetry on boards with `size` cells on each side, in
+/// the row order of their first cells. A cell on an axis of a mirror, or in
+/// the centre of a turn, shares its orbit with fewer cells.
+fn orbits(symmetry: Symmetry, size: usize) -> Vec<Vec<Cell>> {
+  let mut in_orbit = vec![false; size * size];
+  let mut orbits = vec![];
+
+  for y in 0..size {
+    for x in 0..size {
+      if in_orbit[y * size + x] {
+        continue;
+      }
+
+      let mut orbit = vec![];
+      for image in symmetry.images(Cell { x, y }, size) {
+        if !in_orbit[image.y * size + image.x] {
+          in_orbit[image.y * size + image.x] = true;
+          orbit.push(image);
+        }
+      }
+      orbits.push(orbit);
+    }
+  }
+
+  orbits
+}
+
+/// What `generate_puzzle` generates. By default a classic 9x9 puzzle from a
+/// fresh random seed, with no symmetry and as few clues as it can get to.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratorOptions {
+  starting_board: Board,
+  variants: Variants,
+  seed: Option<u64>,
+  symmetry: Symmetry,
+  target_clues: Option<usize>,
+}
+
+impl Default for GeneratorOptions {
+  fn default() -> Self {
+    Self {
+      starting_board: Board::default(),
+      variants: Variants::default(),
+      seed: None,
+      symmetry: Symmetry::None,
+      target_clues: None,
+    }
+  }
+}
+
+#[wasm_bindgen]
+impl GeneratorOptions {
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// The board the puzzle is generated on. Its size, boxes and region map
+  /// shape the puzzle, and its givens are kept as givens of the puzzle.
+  pub fn starting_board(&self) -> Board {
+    self.starting_board.clone()
+  }
+
+  pub fn set_starting_board(&mut self, starting_board: Board) {
+    self.starting_board = starting_board;
+  }
+
+  /// The rules the puzzle's solution follows on top of classic Sudoku
+  pub fn variants(&self) -> Variants {
+    self.variants.clone()
+  }
+
+  pub fn set_variants(&mut self, variants: Variants) {
+    self.variants = variants;
+  }
+
+  /// The same seed always gives the same puzzle
+  pub fn seed(&self) -> Option<u64> {
+    self.seed
+  }
+
+  pub fn set_seed(&mut self, seed: Option<u64>) {
+    self.seed = seed;
+  }
+
+  /// The symmetry of the puzzle's clues. An orbit with a cell given on the
+  /// starting board keeps all of its clues.
+  pub fn symmetry(&self) -> Symmetry {
+    self.symmetry
+  }
+
+  pub fn set_symmetry(&mut self, symmetry: Symmetry) {
+    self.symmetry = symmetry;
+  }
+
+  /// The number of clues to stop removing clues at. Orbits of several cells
+  /// can take the puzzle a few clues below it.
+  pub fn target_clues(&self) -> Option<usize> {
+    self.target_clues
+  }
+
+  pub fn set_target_clues(&mut self, target_clues: Option<usize>) {
+    self.target_clues = target_clues;
+  }
+}
+
+/// A generated puzzle with the only solution its givens lead to
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+  puzzle: Board,
+  solution: Board,
+}
+
+#[wasm_bindgen]
+impl Puzzle {
+  pub fn puzzle(&self) -> Board {
+    self.puzzle.clone()
+  }
+
+  pub fn solution(&self) -> Board {
+    self.solution.clone()
+  }
+}
+
+/// Generates a puzzle with exactly one solution. Starts from a random
+/// solution of the starting board and empties the orbits of the symmetry in
+/// random order, keeping an orbit empty only if the puzzle still has one
+/// solution under the variants. Without a target, every orbit of givens
+/// left is needed, emptying any one of them would let the puzzle be solved
+/// another way. With one, returns an error if no more orbits can be emptied
+/// before the puzzle is down to the target.
+#[wasm_bindgen]
+pub fn generate_puzzle(
+  options: GeneratorOptions,
+) -> Result<Puzzle, SolveError> {
+  let mut rng = solver_rng(options.seed);
+  let solution = launch_algorithm_x(
+    Some(options.starting_board.clone()),
+    Some(DecisionStrategy::Random),
+    Some(1),
+    Some(ColumnHeuristic::MinimumRemaining),
+    Some(rng.gen()),
+    Some(options.variants.clone()),
+  )?
+  .remove(0);
+
+  let size = solution.size();
+  let starting_board = &options.starting_board;
+  let mut orbits: Vec<Vec<Cell>> = orbits(options.symmetry, size)
+    .into_iter()
+    .filter(|orbit| {
+      orbit
+        .iter()
+        .all(|cell| starting_board.get(cell.x, cell.y) == 0)
+    })
+    .collect();
+
+  let mut puzzle = solution.clone();
+  let mut clues = size * size;
+  let reached_target =
+    |clues: usize| options.target_clues.is_some_and(|target| clues <= target);
+  while !orbits.is_empty() && !reached_target(clues) {
+    let orbit = orbits.swap_remove(random_index(&mut rng, orbits.len()));
+    for cell in &orbit {
+      puzzle.set(cell.x, cell.y, 0);
+    }
+
+    if count_solutions(&puzzle, 2, Some(options.variants.clone()))? == 1 {
+      clues -= orbit.len();
+    } else {
+      for cell in &orbit {
+        puzzle.set(cell.x, cell.y, solution.get(cell.x, cell.y));
+      }
+    }
+  }
+
+  if let Some(target) = options.target_clues {
+    if clues > target {
+      return Err(SolveError::UnreachableClueCount { target, clues });
+    }
+  }
+
+  Ok(Puzzle { puzzle, solution })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Asserts the puzzle has only its solution, and that emptying any of its
+  /// givens but the starting board's gives it another
+  fn assert_minimal(generated: &Puzzle, options: &GeneratorOptions) {
+    let (puzzle, solution) = (generated.puzzle(), generated.solution());
+    let variants = options.variants();
+    assert_eq!(count_solutions(&solution, 2, Some(variants.clone())), Ok(1));
+    assert_eq!(count_solutions(&puzzle, 2, Some(variants.clone())), Ok(1));
+
+    let size = puzzle.size();
+    for y in 0..size {
+      for x in 0..size {
+        let (given, value) =
+          (options.starting_board.get(x, y), puzzle.get(x, y));
+        assert!(value == 0 || value == solution.get(x, y));
+        if given != 0 {
+          assert_eq!(value, given);
+        } else if value != 0 {
+          let mut fewer_givens = puzzle.clone();
+          fewer_givens.set(x, y, 0);
+          assert_eq!(
+            count_solutions(&fewer_givens, 2, Some(variants.clone())),
+            Ok(2)
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn generates_minimal_puzzles() {
+    let mut options = GeneratorOptions::new();
+    options.set_seed(Some(4));
+    let generated = generate_puzzle(options.clone()).unwrap();
+    assert_minimal(&generated, &options);
+    // Far fewer givens than cells
+    let givens = generated.puzzle().differences(&Board::new()).len();
+    assert!(givens < 40);
+    assert_eq!(generate_puzzle(options.clone()), Ok(generated));
+
+    // Other sizes, variants and givens of the starting board
+    let mut starting_board = Board::with_box_size(2).unwrap();
+    starting_board.set(0, 0, 1);
+    starting_board.set(3, 3, 4);
+    let mut variants = Variants::new();
+    variants.set_diagonals(true);
+    options.set_starting_board(starting_board);
+    options.set_variants(variants);
+    for seed in 0..5 {
+      options.set_seed(Some(seed));
+      assert_minimal(&generate_puzzle(options.clone()).unwrap(), &options);
+    }
+  }
+
+  const SYMMETRIES: [Symmetry; 7] = [
+    Symmetry::None,
+    Symmetry::HalfTurn,
+    Symmetry::QuarterTurn,
+    Symmetry::HorizontalMirror,
+    Symmetry::VerticalMirror,
+    Symmetry::Diagonal,
+    Symmetry::Dihedral,
+  ];
+
+  #[test]
+  fn orbits_cover_every_cell_once() {
+    let orbit_counts = [81, 41, 21, 45, 45, 45, 15];
+    for (symmetry, orbit_count) in SYMMETRIES.into_iter().zip(orbit_counts) {
+      let orbits = orbits(symmetry, 9);
+      assert_eq!(orbits.len(), orbit_count);
+      let mut cells: Vec<(usize, usize)> = orbits
+        .iter()
+        .flatten()
+        .map(|cell| (cell.x, cell.y))
+        .collect();
+      cells.sort_unstable();
+      cells.dedup();
+      assert_eq!(cells.len(), 81);
+    }
+
+    // A quarter turn takes the corners around the board
+    assert_eq!(
+      orbits(Symmetry::QuarterTurn, 4)[0],
+      vec![
+        Cell { x: 0, y: 0 },
+        Cell { x: 3, y: 0 },
+        Cell { x: 3, y: 3 },
+        Cell { x: 0, y: 3 }
+      ]
+    );
+  }
+
+  #[test]
+  fn clues_follow_the_symmetry() {
+    for symmetry in SYMMETRIES {
+      let mut options = GeneratorOptions::new();
+      options.set_symmetry(symmetry);
+      options.set_seed(Some(1));
+      let generated = generate_puzzle(options).unwrap();
+      let puzzle = generated.puzzle();
+      assert_eq!(count_solutions(&puzzle, 2, None), Ok(1));
+
+      // Every orbit is all clues or all empty, and no orbit of clues can be
+      // emptied
+      for orbit in orbits(symmetry, 9) {
+        let clues = orbit
+          .iter()
+          .filter(|cell| puzzle.get(cell.x, cell.y) != 0)
+          .count();
+        assert!(clues == 0 || clues == orbit.len());
+        if clues != 0 {
+          let mut fewer_clues = puzzle.clone();
+          for cell in &orbit {
+            fewer_clues.set(cell.x, cell.y, 0);
+          }
+          assert_eq!(count_solutions(&fewer_clues, 2, None), Ok(2));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn orbits_with_a_given_keep_their_clues() {
+    let mut starting_board = Board::new();
+    starting_board.set(0, 0, 5);
+    let mut options = GeneratorOptions::new();
+    options.set_starting_board(starting_board);
+    options.set_symmetry(Symmetry::HalfTurn);
+    for seed in 0..3 {
+      options.set_seed(Some(seed));
+      let puzzle = generate_puzzle(options.clone()).unwrap().puzzle();
+      assert_eq!(puzzle.get(0, 0), 5);
+      assert_ne!(puzzle.get(8, 8), 0);
+    }
+  }
+
+  #[test]
+  fn stops_at_the_target_clue_count() {
+    let clue_count =
+      |puzzle: &Puzzle| puzzle.puzzle().differences(&Board::new()).len();
+    let mut options = GeneratorOptions::new();
+    options.set_seed(Some(2));
+    options.set_target_clues(Some(40));
+    let generated = generate_puzzle(options.clone()).unwrap();
+    assert_eq!(clue_count(&generated), 40);
+    assert_eq!(count_solutions(&generated.puzzle(), 2, None), Ok(1));
+
+    // Orbits of two cells can step past the target
+    options.set_symmetry(Symmetry::HalfTurn);
+    options.set_target_clues(Some(36));
+    let clues = clue_count(&generate_puzzle(options.clone()).unwrap());
+    assert!(clues == 35 || clues == 36);
+
+    options.set_symmetry(Symmetry::Dihedral);
+    options.set_target_clues(Some(17));
+    assert!(matches!(
+      generate_puzzle(options),
+      Err(SolveError::UnreachableClueCount { target: 17, clues }) if clues > 17
+    ));
+  }
+
+  #[test]
+  fn contradictory_starting_boards_are_reported() {
+    let mut starting_board = Board::new();
+    starting_board.set(0, 0, 1);
+    starting_board.set(1, 1, 1);
+    let mut options = GeneratorOptions::new();
+    options.set_starting_board(starting_board);
+    assert!(matches!(
+      generate_puzzle(options),
+      Err(SolveError::ContradictoryGivens(_))
+    ));
+  }
+}
@@ -0,0 +1,7 @@
+use wasm_bindgen::JsValue;
+
+/// Sets a property on an object built to be handed to JS
+pub(crate) fn set_property(object: &js_sys::Object, key: &str, value: JsValue) {
+  js_sys::Reflect::set(object, &key.into(), &value)
+    .expect("Setting a property on a new object cannot fail");
+}
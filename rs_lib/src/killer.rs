@@ -0,0 +1,269 @@
+use crate::algorithm_x::ConstraintType;
+use crate::board::{Board, Cell};
+use crate::dancing_links::Prune;
+use crate::solve_error::SolveError;
+use crate::variants::Region;
+use wasm_bindgen::prelude::*;
+
+/// The largest board size killer cages work on, values are kept as bits of
+/// a `u64`
+const MAX_KILLER_SIZE: usize = 64;
+
+/// A killer cage, its cells must add up to the sum without repeating a
+/// value
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cage {
+  cells: Vec<Cell>,
+  sum: u32,
+}
+
+#[wasm_bindgen]
+impl Cage {
+  #[wasm_bindgen(constructor)]
+  pub fn new(cells: Vec<Cell>, sum: u32) -> Self {
+    Self { cells, sum }
+  }
+
+  pub fn cells(&self) -> Vec<Cell> {
+    self.cells.clone()
+  }
+
+  pub fn sum(&self) -> u32 {
+    self.sum
+  }
+}
+
+impl Cage {
+  /// Returns true if every cell is filled and the values repeat or don't add
+  /// up to the sum
+  pub(crate) fn is_broken(&self, board: &Board) -> bool {
+    let mut values: Vec<i32> = self
+      .cells
+      .iter()
+      .map(|cell| board.get(cell.x, cell.y))
+      .collect();
+    let sum: i32 = values.iter().sum();
+    values.sort_unstable();
+    values.dedup();
+
+    !values.contains(&0)
+      && (values.len() < self.cells.len() || sum != self.sum as i32)
+  }
+}
+
+/// Checks every cage fits on boards shaped like `shape`: it has cells, they
+/// are on the board, there are no more of them than values, no cell is in
+/// two cages or twice in one, and distinct values can add up to its sum
+pub(crate) fn check_cages(
+  cages: &[Cage],
+  shape: &Board,
+) -> Result<(), SolveError> {
+  let size = shape.size();
+  let invalid = |reason: String| Err(SolveError::InvalidCage(reason));
+
+  if !cages.is_empty() && size > MAX_KILLER_SIZE {
+    return invalid(format!(
+      "Killer cages work on boards of up to {} values",
+      MAX_KILLER_SIZE
+    ));
+  }
+
+  let mut caged = vec![false; size * size];
+  for (cage_idx, cage) in cages.iter().enumerate() {
+    if cage.cells.is_empty() {
+      return invalid(format!("Cage {} has no cells", cage_idx));
+    }
+    if cage.cells.len() > size {
+      return invalid(format!(
+        "Cage {} has {} cells, but there are only {} values",
+        cage_idx,
+        cage.cells.len(),
+        size
+      ));
+    }
+
+    for &Cell { x, y } in &cage.cells {
+      if x >= size || y >= size {
+        return invalid(format!(
+          "Cage {} has the cell {}, {}, which is not on the board",
+          cage_idx, x, y
+        ));
+      }
+      if caged[y * size + x] {
+        return invalid(format!(
+          "The cell {}, {} is in cage {} and another cage",
+          x, y, cage_idx
+        ));
+      }
+      caged[y * size + x] = true;
+    }
+
+    // Every sum between the smallest and the largest values is reachable
+    let cell_count = cage.cells.len() as u32;
+    let smallest_sum = cell_count * (cell_count + 1) / 2;
+    let largest_sum = cell_count * (2 * size as u32 - cell_count + 1) / 2;
+    if !(smallest_sum..=largest_sum).contains(&cage.sum) {
+      return invalid(format!(
+        "Cage {} sums to {}, but {} different values add up to {} to {}",
+        cage_idx, cage.sum, cell_count, smallest_sum, largest_sum
+      ));
+    }
+  }
+
+  Ok(())
+}
+
+/// The cells of the cage must not repeat a value, they must hold every
+/// value once when there are as many cells as values
+pub(crate) fn cage_region(cage: &Cage) -> Region {
+  Region {
+    constraint_type: ConstraintType::Cage,
+    cells: cage.cells.clone(),
+  }
+}
+
+/// Every set of `cell_count` different values from 1 up to `size` that adds
+/// up to `sum`. Bit `v - 1` of a set stands for the value `v`.
+fn value_combinations(cell_count: usize, sum: u32, size: usize) -> Vec<u64> {
+  fn extend(
+    combinations: &mut Vec<u64>,
+    combination: u64,
+    lowest_value: u32,
+    cells_left: usize,
+    sum_left: u32,
+    size: u32,
+  ) {
+    if cells_left == 0 {
+      if sum_left == 0 {
+        combinations.push(combination);
+      }
+      return;
+    }
+
+    // Values only grow from here, so stop once the value is too big
+    for value in lowest_value..=size.min(sum_left) {
+      extend(
+        combinations,
+        combination | 1 << (value - 1),
+        value + 1,
+        cells_left - 1,
+        sum_left - value,
+        size,
+      );
+    }
+  }
+
+  let mut combinations = vec![];
+  extend(&mut combinations, 0, 1, cell_count, sum, size as u32);
+
+  combinations
+}
+
+/// A cage with the value combinations that could fill it
+struct CageCombinations {
+  /// The cells of the cage as indexes in row order
+  cells: Vec<usize>,
+  combinations: Vec<u64>,
+}
+
+/// Prunes values that can't add up to the sums of the cages. After every
+/// decision a cage keeps the combinations that include all of its values,
+/// and its empty cells may only take values from one of them.
+pub(crate) struct CagePruner {
+  size: usize,
+  cages: Vec<CageCombinations>,
+  /// The cage each cell belongs to, if any
+  cell_cages: Vec<Option<usize>>,
+}
+
+impl CagePruner {
+  /// The cages must have passed `check_cages`
+  pub(crate) fn new(cages: &[Cage], shape: &Board) -> Self {
+    let size = shape.size();
+    let mut cell_cages = vec![None; size * size];
+
+    let cages = cages
+      .iter()
+      .enumerate()
+      .map(|(cage_idx, cage)| {
+        let cells: Vec<usize> = cage
+          .cells
+          .iter()
+          .map(|&Cell { x, y }| y * size + x)
+          .collect();
+        for &cell in &cells {
+          cell_cages[cell] = Some(cage_idx);
+        }
+
+        CageCombinations {
+          combinations: value_combinations(cells.len(), cage.sum, size),
+          cells,
+        }
+      })
+      .collect();
+
+    Self {
+      size,
+      cages,
+      cell_cages,
+    }
+  }
+}
+
+impl Prune for CagePruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    _is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    // Every row is a value in a cell, `cell * size + value - 1`
+    let mut placed_values = vec![0u64; self.cages.len()];
+    let mut filled = vec![false; self.size * self.size];
+    for &row_idx in selected_rows {
+      let cell = row_idx / self.size;
+      filled[cell] = true;
+      if let Some(cage_idx) = self.cell_cages[cell] {
+        placed_values[cage_idx] |= 1 << (row_idx % self.size);
+      }
+    }
+
+    let mut pruned_rows = vec![];
+    for (cage, placed) in self.cages.iter().zip(placed_values) {
+      let mut possible = cage
+        .combinations
+        .iter()
+        .filter(|&&combination| combination & placed == placed)
+        .peekable();
+      possible.peek()?;
+
+      let allowed =
+        possible.fold(0, |allowed, combination| allowed | combination);
+      for &cell in cage.cells.iter().filter(|&&cell| !filled[cell]) {
+        pruned_rows.extend(
+          (0..self.size)
+            .filter(|value_idx| allowed & 1 << value_idx == 0)
+            .map(|value_idx| cell * self.size + value_idx),
+        );
+      }
+    }
+
+    Some(pruned_rows)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn value_combinations_add_up_to_the_sum() {
+    assert_eq!(value_combinations(2, 3, 9), vec![0b11]);
+    assert_eq!(value_combinations(3, 24, 9), vec![0b111_000_000]);
+    // 1 + 5, 2 + 4
+    assert_eq!(value_combinations(2, 6, 9), vec![0b10001, 0b1010]);
+    assert_eq!(value_combinations(9, 45, 9), vec![0b111_111_111]);
+    assert_eq!(value_combinations(2, 18, 9), vec![]);
+    assert_eq!(value_combinations(2, 18, 16).len(), 7);
+  }
+}
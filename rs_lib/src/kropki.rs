@@ -0,0 +1,297 @@
+use crate::algorithm_x::{candidates, placed_values, ConstraintType};
+use crate::board::{Board, Cell, Conflict};
+use crate::dancing_links::Prune;
+use crate::solve_error::SolveError;
+use wasm_bindgen::prelude::*;
+
+/// What a kropki dot says about the values of its two cells
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DotColor {
+  /// The values are consecutive, they differ by 1
+  White,
+  /// One value is double the other
+  Black,
+}
+
+impl DotColor {
+  fn allows(self, first: u32, second: u32) -> bool {
+    match self {
+      DotColor::White => first.abs_diff(second) == 1,
+      DotColor::Black => first == 2 * second || second == 2 * first,
+    }
+  }
+}
+
+/// Returns true if a dot of either colour could sit between the values. 1
+/// and 2 take either dot.
+fn is_dot_pair(first: u32, second: u32) -> bool {
+  DotColor::White.allows(first, second) || DotColor::Black.allows(first, second)
+}
+
+/// A kropki dot on the edge between two cells that share a side
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dot {
+  color: DotColor,
+  first: Cell,
+  second: Cell,
+}
+
+#[wasm_bindgen]
+impl Dot {
+  /// A white dot, the cells hold consecutive values
+  pub fn white(first: Cell, second: Cell) -> Self {
+    Self {
+      color: DotColor::White,
+      first,
+      second,
+    }
+  }
+
+  /// A black dot, one cell holds double the value of the other
+  pub fn black(first: Cell, second: Cell) -> Self {
+    Self {
+      color: DotColor::Black,
+      first,
+      second,
+    }
+  }
+
+  pub fn color(&self) -> DotColor {
+    self.color
+  }
+
+  pub fn first(&self) -> Cell {
+    self.first
+  }
+
+  pub fn second(&self) -> Cell {
+    self.second
+  }
+}
+
+impl Dot {
+  /// Returns true if both cells hold values from 1 to the board's size and
+  /// the values don't fit the dot
+  pub(crate) fn is_broken(&self, board: &Board) -> bool {
+    match (
+      cell_value(board, self.first),
+      cell_value(board, self.second),
+    ) {
+      (Some(first), Some(second)) => !self.color.allows(first, second),
+      _ => false,
+    }
+  }
+
+  /// Returns true if the dot sits between the two cells, in either order
+  fn joins(&self, first: Cell, second: Cell) -> bool {
+    (self.first, self.second) == (first, second)
+      || (self.first, self.second) == (second, first)
+  }
+}
+
+/// The value of the cell, `None` if it is empty or outside of 1 to the
+/// board's size
+fn cell_value(board: &Board, cell: Cell) -> Option<u32> {
+  match board.get(cell.x, cell.y) {
+    value @ 1.. if value <= board.size() as i32 => Some(value as u32),
+    _ => None,
+  }
+}
+
+/// Checks every dot fits on boards shaped like `shape`: both cells are on
+/// the board, they share a side, and no other dot sits between them
+pub(crate) fn check_dots(
+  dots: &[Dot],
+  shape: &Board,
+) -> Result<(), SolveError> {
+  let size = shape.size();
+
+  for (dot_idx, dot) in dots.iter().enumerate() {
+    let invalid = |reason: String| {
+      Err(SolveError::InvalidDot(format!(
+        "Dot {} {}",
+        dot_idx, reason
+      )))
+    };
+
+    for cell in [dot.first, dot.second] {
+      if cell.x >= size || cell.y >= size {
+        return invalid(format!(
+          "has the cell {}, {}, which is not on the board",
+          cell.x, cell.y
+        ));
+      }
+    }
+    if dot.first.x.abs_diff(dot.second.x) + dot.first.y.abs_diff(dot.second.y)
+      != 1
+    {
+      return invalid(format!(
+        "joins {}, {} and {}, {}, which don't share a side",
+        dot.first.x, dot.first.y, dot.second.x, dot.second.y
+      ));
+    }
+    if let Some(other_idx) = dots[..dot_idx]
+      .iter()
+      .position(|other| other.joins(dot.first, dot.second))
+    {
+      return invalid(format!("joins the same cells as dot {}", other_idx));
+    }
+  }
+
+  Ok(())
+}
+
+/// Every pair of cells that share a side, each with the cell to its right
+/// then the cell below it, in row order
+fn side_pairs(size: usize) -> Vec<(Cell, Cell)> {
+  let mut pairs = vec![];
+  for y in 0..size {
+    for x in 0..size {
+      if x + 1 < size {
+        pairs.push((Cell { x, y }, Cell { x: x + 1, y }));
+      }
+      if y + 1 < size {
+        pairs.push((Cell { x, y }, Cell { x, y: y + 1 }));
+      }
+    }
+  }
+
+  pairs
+}
+
+/// Every pair of cells the dots constrain, with the dot's colour. When
+/// every dot is given, the pairs without a dot follow with no colour. The
+/// dots must have passed `check_dots`.
+fn dot_pairs(
+  dots: &[Dot],
+  all_dots_given: bool,
+  size: usize,
+) -> Vec<(Cell, Cell, Option<DotColor>)> {
+  let mut pairs: Vec<(Cell, Cell, Option<DotColor>)> = dots
+    .iter()
+    .map(|dot| (dot.first, dot.second, Some(dot.color)))
+    .collect();
+  if all_dots_given {
+    pairs.extend(
+      side_pairs(size)
+        .into_iter()
+        .filter(|&(first, second)| {
+          !dots.iter().any(|dot| dot.joins(first, second))
+        })
+        .map(|(first, second)| (first, second, None)),
+    );
+  }
+
+  pairs
+}
+
+/// Returns true if the values fit the pair's dot, or fit no dot when the
+/// pair has none
+fn pair_allows(color: Option<DotColor>, first: u32, second: u32) -> bool {
+  match color {
+    Some(color) => color.allows(first, second),
+    None => !is_dot_pair(first, second),
+  }
+}
+
+/// Returns every pair of cells whose values break their dot, and when every
+/// dot is given, every pair of cells that share a side without a dot but
+/// hold values a dot could join. Like `Board::validate`, values outside of 1
+/// to the board's size are not compared.
+pub(crate) fn dot_conflicts(
+  dots: &[Dot],
+  all_dots_given: bool,
+  board: &Board,
+) -> Vec<Conflict> {
+  dot_pairs(dots, all_dots_given, board.size())
+    .into_iter()
+    .filter(|&(first, second, color)| {
+      match (cell_value(board, first), cell_value(board, second)) {
+        (Some(first), Some(second)) => !pair_allows(color, first, second),
+        _ => false,
+      }
+    })
+    .map(|(first, second, _)| Conflict {
+      first_x: first.x,
+      first_y: first.y,
+      second_x: second.x,
+      second_y: second.y,
+      constraint_type: ConstraintType::Dot,
+    })
+    .collect()
+}
+
+/// Prunes values that can't fit the dots. After every decision, each cell
+/// of a pair keeps only the values the other cell can still hold a partner
+/// for: a consecutive value across a white dot, a double or half across a
+/// black dot, and neither when every dot is given and the pair has none.
+pub(crate) struct DotPruner {
+  size: usize,
+  /// The two cells as indexes in row order, and the colour of their dot
+  pairs: Vec<(usize, usize, Option<DotColor>)>,
+}
+
+impl DotPruner {
+  /// The dots must have passed `check_dots`
+  pub(crate) fn new(dots: &[Dot], all_dots_given: bool, shape: &Board) -> Self {
+    let size = shape.size();
+    let index = |cell: Cell| cell.y * size + cell.x;
+
+    Self {
+      size,
+      pairs: dot_pairs(dots, all_dots_given, size)
+        .into_iter()
+        .map(|(first, second, color)| (index(first), index(second), color))
+        .collect(),
+    }
+  }
+}
+
+impl Prune for DotPruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    let values = placed_values(selected_rows, self.size);
+    let cell_candidates = |cell: usize| match values[cell] {
+      0 => candidates(cell, self.size, is_row_visible),
+      value => vec![value],
+    };
+    let mut pruned_rows = vec![];
+
+    for &(first, second, color) in &self.pairs {
+      let (first_candidates, second_candidates) =
+        (cell_candidates(first), cell_candidates(second));
+
+      // Both rules read the same either way round
+      for (cell, candidates, partners) in [
+        (first, &first_candidates, &second_candidates),
+        (second, &second_candidates, &first_candidates),
+      ] {
+        let has_partner = |value: u32| {
+          partners
+            .iter()
+            .any(|&partner| pair_allows(color, value, partner))
+        };
+
+        if values[cell] != 0 {
+          if !has_partner(values[cell]) {
+            return None;
+          }
+          continue;
+        }
+        pruned_rows.extend(
+          candidates
+            .iter()
+            .filter(|&&value| !has_partner(value))
+            .map(|&value| cell * self.size + value as usize - 1),
+        );
+      }
+    }
+
+    Some(pruned_rows)
+  }
+}
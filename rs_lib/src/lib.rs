@@ -1,5 +1,41 @@
 mod algorithm_x;
+mod bitset;
 mod board;
 mod dancing_links;
-pub use algorithm_x::launch_algorithm_x;
-pub use board::Board;
+mod exact_cover;
+mod generator;
+mod js_object;
+mod killer;
+mod kropki;
+mod lines;
+mod rule;
+mod samurai;
+mod sandwich;
+mod solution_iter;
+mod solve_error;
+mod uniqueness;
+mod variants;
+pub use algorithm_x::{
+  check_uniqueness, count_solutions, launch_algorithm_x, ColumnHeuristic,
+  ConstraintType, DecisionStrategy,
+};
+pub use board::{Board, BrokenConstraint, Cell, Conflict};
+pub use dancing_links::ExactCoverIter;
+pub use exact_cover::ExactCover;
+pub use generator::{generate_puzzle, GeneratorOptions, Puzzle, Symmetry};
+pub use killer::Cage;
+pub use kropki::{Dot, DotColor};
+pub use lines::{Arrow, Thermometer};
+pub use rule::{
+  rule_exact_cover, ColumnRule, ExistenceRule, Placement, RowRule, SearchState,
+  SubGridRule, VariantRule,
+};
+pub use samurai::{
+  count_samurai_solutions, generate_samurai_puzzle, solve_samurai,
+  SamuraiBoard, SamuraiPuzzle,
+};
+pub use sandwich::{generate_sandwich_puzzle, Sandwich, SandwichPuzzle};
+pub use solution_iter::SolutionIter;
+pub use solve_error::SolveError;
+pub use uniqueness::Uniqueness;
+pub use variants::Variants;
@@ -0,0 +1,360 @@
+use crate::algorithm_x::{candidates, placed_values};
+use crate::board::{Board, Cell};
+use crate::dancing_links::Prune;
+use crate::solve_error::SolveError;
+use wasm_bindgen::prelude::*;
+
+/// A thermometer, its values must strictly increase from the bulb, the
+/// first cell, to the end of the tube
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Thermometer {
+  cells: Vec<Cell>,
+}
+
+#[wasm_bindgen]
+impl Thermometer {
+  #[wasm_bindgen(constructor)]
+  pub fn new(cells: Vec<Cell>) -> Self {
+    Self { cells }
+  }
+
+  pub fn cells(&self) -> Vec<Cell> {
+    self.cells.clone()
+  }
+}
+
+impl Thermometer {
+  /// Returns true if every cell is filled and the values don't strictly
+  /// increase
+  pub(crate) fn is_broken(&self, board: &Board) -> bool {
+    let values = cell_values(board, &self.cells);
+
+    !values.contains(&0) && values.windows(2).any(|pair| pair[0] >= pair[1])
+  }
+}
+
+/// An arrow, the value in its circle must be the sum of the values along it.
+/// Values on the arrow may repeat unless another rule forbids it.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Arrow {
+  circle: Cell,
+  cells: Vec<Cell>,
+}
+
+#[wasm_bindgen]
+impl Arrow {
+  /// The arrow starts next to the circle and runs through the cells in
+  /// order
+  #[wasm_bindgen(constructor)]
+  pub fn new(circle: Cell, cells: Vec<Cell>) -> Self {
+    Self { circle, cells }
+  }
+
+  pub fn circle(&self) -> Cell {
+    self.circle
+  }
+
+  pub fn cells(&self) -> Vec<Cell> {
+    self.cells.clone()
+  }
+}
+
+impl Arrow {
+  /// Returns true if the circle and every cell are filled and the values
+  /// along the arrow don't add up to the circle
+  pub(crate) fn is_broken(&self, board: &Board) -> bool {
+    let circle = board.get(self.circle.x, self.circle.y);
+    let values = cell_values(board, &self.cells);
+
+    circle != 0 && !values.contains(&0) && values.iter().sum::<i32>() != circle
+  }
+}
+
+fn cell_values(board: &Board, cells: &[Cell]) -> Vec<i32> {
+  cells.iter().map(|cell| board.get(cell.x, cell.y)).collect()
+}
+
+/// Checks every thermometer fits on boards shaped like `shape`: it has at
+/// least one cell and no more than there are values, its cells are on the
+/// board and each one touches the one before it, by a side or a corner
+pub(crate) fn check_thermometers(
+  thermometers: &[Thermometer],
+  shape: &Board,
+) -> Result<(), SolveError> {
+  let size = shape.size();
+
+  for (thermometer_idx, thermometer) in thermometers.iter().enumerate() {
+    let invalid = |reason: String| {
+      Err(SolveError::InvalidThermometer(format!(
+        "Thermometer {} {}",
+        thermometer_idx, reason
+      )))
+    };
+
+    if thermometer.cells.is_empty() {
+      return invalid("has no cells".to_string());
+    }
+    if thermometer.cells.len() > size {
+      return invalid(format!(
+        "has {} cells, but there are only {} values",
+        thermometer.cells.len(),
+        size
+      ));
+    }
+    if let Err(reason) = check_path(&thermometer.cells, size) {
+      return invalid(reason);
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks every arrow fits on boards shaped like `shape`: it has at least
+/// one cell, its circle and cells are on the board, it starts next to the
+/// circle and each cell touches the one before it, by a side or a corner
+pub(crate) fn check_arrows(
+  arrows: &[Arrow],
+  shape: &Board,
+) -> Result<(), SolveError> {
+  let size = shape.size();
+
+  for (arrow_idx, arrow) in arrows.iter().enumerate() {
+    let invalid = |reason: String| {
+      Err(SolveError::InvalidArrow(format!(
+        "Arrow {} {}",
+        arrow_idx, reason
+      )))
+    };
+
+    if arrow.cells.is_empty() {
+      return invalid("has no cells".to_string());
+    }
+    let path: Vec<Cell> = std::iter::once(arrow.circle)
+      .chain(arrow.cells.clone())
+      .collect();
+    if let Err(reason) = check_path(&path, size) {
+      return invalid(reason);
+    }
+  }
+
+  Ok(())
+}
+
+/// Checks the cells are on a board with `size` cells on each side, that no
+/// cell is visited twice and that each one touches the one before it
+fn check_path(cells: &[Cell], size: usize) -> Result<(), String> {
+  for (cell_idx, cell) in cells.iter().enumerate() {
+    if cell.x >= size || cell.y >= size {
+      return Err(format!(
+        "has the cell {}, {}, which is not on the board",
+        cell.x, cell.y
+      ));
+    }
+    if cells[..cell_idx].contains(cell) {
+      return Err(format!("has the cell {}, {} twice", cell.x, cell.y));
+    }
+    if let Some(previous) = cell_idx.checked_sub(1).map(|idx| cells[idx]) {
+      if previous.x.abs_diff(cell.x) > 1 || previous.y.abs_diff(cell.y) > 1 {
+        return Err(format!(
+          "jumps from {}, {} to {}, {}",
+          previous.x, previous.y, cell.x, cell.y
+        ));
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// The rows that put a candidate outside of `low` up to `high` in the cell
+fn rows_outside(
+  cell: usize,
+  candidates: &[u32],
+  low: i64,
+  high: i64,
+  size: usize,
+) -> Vec<usize> {
+  candidates
+    .iter()
+    .filter(|&&value| (value as i64) < low || (value as i64) > high)
+    .map(|&value| cell * size + value as usize - 1)
+    .collect()
+}
+
+/// Prunes values that can't keep the thermometers increasing. After every
+/// decision each cell of a thermometer must be above the values its cells
+/// before it can take, and below the values its cells after it can take.
+pub(crate) struct ThermometerPruner {
+  size: usize,
+  /// The cells of each thermometer as indexes in row order, from the bulb
+  thermometers: Vec<Vec<usize>>,
+}
+
+impl ThermometerPruner {
+  /// The thermometers must have passed `check_thermometers`
+  pub(crate) fn new(thermometers: &[Thermometer], shape: &Board) -> Self {
+    let size = shape.size();
+
+    Self {
+      size,
+      thermometers: thermometers
+        .iter()
+        .map(|thermometer| cell_indexes(&thermometer.cells, size))
+        .collect(),
+    }
+  }
+}
+
+impl Prune for ThermometerPruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    let values = placed_values(selected_rows, self.size);
+    let mut pruned_rows = vec![];
+
+    for cells in &self.thermometers {
+      // What each cell can hold, a placed value counts as its only one
+      let cell_candidates: Vec<Vec<u32>> = cells
+        .iter()
+        .map(|&cell| match values[cell] {
+          0 => candidates(cell, self.size, is_row_visible),
+          value => vec![value],
+        })
+        .collect();
+
+      // The lowest value each cell can hold, going up from the bulb
+      let mut lows = Vec::with_capacity(cells.len());
+      let mut low = 0;
+      for candidates in &cell_candidates {
+        low = *candidates.iter().find(|&&value| value > low)?;
+        lows.push(low);
+      }
+
+      // The highest value each cell can hold, going down from the end
+      let mut high = self.size as u32 + 1;
+      for ((&cell, candidates), &low) in
+        cells.iter().zip(&cell_candidates).zip(&lows).rev()
+      {
+        high = *candidates.iter().rev().find(|&&value| value < high)?;
+        if low > high {
+          return None;
+        }
+
+        if values[cell] == 0 {
+          pruned_rows.extend(rows_outside(
+            cell,
+            candidates,
+            low as i64,
+            high as i64,
+            self.size,
+          ));
+        }
+      }
+    }
+
+    Some(pruned_rows)
+  }
+}
+
+/// An arrow with its circle and cells as indexes in row order
+struct ArrowCells {
+  circle: usize,
+  cells: Vec<usize>,
+}
+
+/// Prunes values that can't make the arrows add up. After every decision
+/// the circle must be within the smallest and largest sums the arrow can
+/// still reach, and each empty cell on the arrow must leave the others room
+/// to reach the circle.
+pub(crate) struct ArrowPruner {
+  size: usize,
+  arrows: Vec<ArrowCells>,
+}
+
+impl ArrowPruner {
+  /// The arrows must have passed `check_arrows`
+  pub(crate) fn new(arrows: &[Arrow], shape: &Board) -> Self {
+    let size = shape.size();
+
+    Self {
+      size,
+      arrows: arrows
+        .iter()
+        .map(|arrow| ArrowCells {
+          circle: cell_indexes(&[arrow.circle], size)[0],
+          cells: cell_indexes(&arrow.cells, size),
+        })
+        .collect(),
+    }
+  }
+}
+
+impl Prune for ArrowPruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    let values = placed_values(selected_rows, self.size);
+    let range = |cell: usize| -> Option<(Vec<u32>, i64, i64)> {
+      let candidates = match values[cell] {
+        0 => candidates(cell, self.size, is_row_visible),
+        value => vec![value],
+      };
+      let (&low, &high) = (candidates.first()?, candidates.last()?);
+
+      Some((candidates, low as i64, high as i64))
+    };
+    let mut pruned_rows = vec![];
+
+    for arrow in &self.arrows {
+      let ranges = arrow
+        .cells
+        .iter()
+        .map(|&cell| range(cell))
+        .collect::<Option<Vec<_>>>()?;
+      let min_sum: i64 = ranges.iter().map(|(_, low, _)| low).sum();
+      let max_sum: i64 = ranges.iter().map(|(_, _, high)| high).sum();
+
+      // The circle can only hold a sum the arrow can reach
+      let (circle_candidates, circle_low, circle_high) = range(arrow.circle)?;
+      let (circle_low, circle_high) =
+        (circle_low.max(min_sum), circle_high.min(max_sum));
+      if circle_low > circle_high {
+        return None;
+      }
+      if values[arrow.circle] == 0 {
+        pruned_rows.extend(rows_outside(
+          arrow.circle,
+          &circle_candidates,
+          circle_low,
+          circle_high,
+          self.size,
+        ));
+      }
+
+      // Each empty cell must leave the rest of the arrow a reachable sum
+      for (&cell, (candidates, low, high)) in arrow.cells.iter().zip(&ranges) {
+        if values[cell] == 0 {
+          pruned_rows.extend(rows_outside(
+            cell,
+            candidates,
+            circle_low - (max_sum - high),
+            circle_high - (min_sum - low),
+            self.size,
+          ));
+        }
+      }
+    }
+
+    Some(pruned_rows)
+  }
+}
+
+fn cell_indexes(cells: &[Cell], size: usize) -> Vec<usize> {
+  cells.iter().map(|&Cell { x, y }| y * size + x).collect()
+}
@@ -0,0 +1,555 @@
+use crate::algorithm_x::{candidates, choice_count, placed_values};
+use crate::board::{Board, Cell};
+use crate::dancing_links::Prune;
+use crate::exact_cover::ExactCover;
+use crate::solve_error::SolveError;
+use std::fmt;
+use std::rc::Rc;
+
+/// A value in a cell, one the search has placed or one it could still place
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Placement {
+  pub cell: Cell,
+  pub value: u32,
+}
+
+impl Placement {
+  /// The placement's row of the exact cover problem, `None` if the cell is
+  /// off a board with `size` cells on each side or the value is outside 1 up
+  /// to `size`
+  fn row(&self, size: usize) -> Option<usize> {
+    let Cell { x, y } = self.cell;
+    let value = self.value as usize;
+    (x < size && y < size && (1..=size).contains(&value))
+      .then(|| (y * size + x) * size + value - 1)
+  }
+
+  /// The placement of a row of the exact cover problem
+  fn of_row(row_idx: usize, size: usize) -> Self {
+    let cell = row_idx / size;
+    Self {
+      cell: Cell {
+        x: cell % size,
+        y: cell / size,
+      },
+      value: (row_idx % size + 1) as u32,
+    }
+  }
+}
+
+/// The placements the search has made so far, given values included, and
+/// the values each empty cell could still hold
+pub struct SearchState<'a> {
+  size: usize,
+  values: Vec<u32>,
+  is_row_visible: &'a dyn Fn(usize) -> bool,
+}
+
+impl SearchState<'_> {
+  /// The number of cells on each side of the board
+  pub fn size(&self) -> usize {
+    self.size
+  }
+
+  /// The value placed in the cell, 0 while it is empty
+  pub fn value(&self, cell: Cell) -> u32 {
+    self.values[cell.y * self.size + cell.x]
+  }
+
+  /// The values the cell could still hold from smallest to largest, only
+  /// its value once one is placed
+  pub fn candidates(&self, cell: Cell) -> Vec<u32> {
+    match self.value(cell) {
+      0 => {
+        candidates(cell.y * self.size + cell.x, self.size, self.is_row_visible)
+      }
+      value => vec![value],
+    }
+  }
+}
+
+/// A rule the solver follows, added to a search with `Variants::add_rule`.
+/// The exact cover problem of a board has a row for every value in every
+/// cell, and a rule can add columns those rows cover, forbid pairs of them
+/// from being in a solution together, and rule them out as the search goes.
+/// Every method adds nothing by default, so a rule only implements the ones
+/// it needs. The rows, columns, boxes and cells of classic Sudoku are rules
+/// too, see `RowRule`, `ColumnRule`, `SubGridRule` and `ExistenceRule`.
+pub trait VariantRule {
+  /// The number of columns every solution covers exactly once
+  fn primary_columns(&self, _shape: &Board) -> usize {
+    0
+  }
+
+  /// The number of columns every solution covers at most once
+  fn secondary_columns(&self, _shape: &Board) -> usize {
+    0
+  }
+
+  /// The columns of the rule the placement covers. Primary columns are
+  /// numbered from 0, the secondary columns follow them.
+  fn columns(&self, _shape: &Board, _placement: Placement) -> Vec<usize> {
+    vec![]
+  }
+
+  /// Pairs of placements no solution holds both of. Each pair becomes a
+  /// secondary column that both of its placements cover.
+  fn forbidden_pairs(&self, _shape: &Board) -> Vec<(Placement, Placement)> {
+    vec![]
+  }
+
+  /// Called with the given values before the search starts, then every
+  /// time a decision is pushed onto the search, including when a decision
+  /// is retried with another value. Returns the placements no solution can
+  /// hold any more, or `None` if no solution can follow from the state.
+  /// Placements off the board are ignored.
+  fn after_decision(&self, _state: &SearchState) -> Option<Vec<Placement>> {
+    Some(vec![])
+  }
+}
+
+/// Every value once in each row
+pub struct RowRule;
+
+impl VariantRule for RowRule {
+  fn primary_columns(&self, shape: &Board) -> usize {
+    shape.size() * shape.size()
+  }
+
+  fn columns(&self, shape: &Board, placement: Placement) -> Vec<usize> {
+    vec![placement.cell.y * shape.size() + placement.value as usize - 1]
+  }
+}
+
+/// Every value once in each column
+pub struct ColumnRule;
+
+impl VariantRule for ColumnRule {
+  fn primary_columns(&self, shape: &Board) -> usize {
+    shape.size() * shape.size()
+  }
+
+  fn columns(&self, shape: &Board, placement: Placement) -> Vec<usize> {
+    vec![placement.cell.x * shape.size() + placement.value as usize - 1]
+  }
+}
+
+/// Every value once in each box, or each region of a board with a region
+/// map
+pub struct SubGridRule;
+
+impl VariantRule for SubGridRule {
+  fn primary_columns(&self, shape: &Board) -> usize {
+    shape.size() * shape.size()
+  }
+
+  fn columns(&self, shape: &Board, placement: Placement) -> Vec<usize> {
+    let Cell { x, y } = placement.cell;
+    vec![shape.box_index(x, y) * shape.size() + placement.value as usize - 1]
+  }
+}
+
+/// A value in every cell
+pub struct ExistenceRule;
+
+impl VariantRule for ExistenceRule {
+  fn primary_columns(&self, shape: &Board) -> usize {
+    shape.size() * shape.size()
+  }
+
+  fn columns(&self, shape: &Board, placement: Placement) -> Vec<usize> {
+    vec![placement.cell.y * shape.size() + placement.cell.x]
+  }
+}
+
+/// The columns a list of rules adds to the exact cover problem of boards
+/// shaped like `shape`, each rule's after the ones before it
+pub(crate) struct RuleColumns {
+  pub primary_count: usize,
+  pub secondary_count: usize,
+  /// The primary then the secondary columns of every row, each numbered
+  /// from 0
+  pub rows: Vec<(Vec<usize>, Vec<usize>)>,
+}
+
+/// Collects the columns of the rules. Returns an error if a rule covers a
+/// column it doesn't have, or forbids a placement off the board.
+pub(crate) fn rule_columns(
+  shape: &Board,
+  rules: &[Rc<dyn VariantRule>],
+) -> Result<RuleColumns, SolveError> {
+  let size = shape.size();
+  let mut rule_columns = RuleColumns {
+    primary_count: 0,
+    secondary_count: 0,
+    rows: vec![(vec![], vec![]); choice_count(size)],
+  };
+
+  for (rule_idx, rule) in rules.iter().enumerate() {
+    let unsupported = |reason: String| {
+      Err(SolveError::UnsupportedVariant(format!(
+        "Rule {} {}",
+        rule_idx, reason
+      )))
+    };
+    let primary_count = rule.primary_columns(shape);
+    let secondary_count = rule.secondary_columns(shape);
+
+    for (row_idx, (primary, secondary)) in
+      rule_columns.rows.iter_mut().enumerate()
+    {
+      for column in rule.columns(shape, Placement::of_row(row_idx, size)) {
+        if column < primary_count {
+          primary.push(rule_columns.primary_count + column);
+        } else if column < primary_count + secondary_count {
+          secondary.push(rule_columns.secondary_count + column - primary_count);
+        } else {
+          return unsupported(format!(
+            "covers column {}, but has only {}",
+            column,
+            primary_count + secondary_count
+          ));
+        }
+      }
+    }
+    rule_columns.primary_count += primary_count;
+    rule_columns.secondary_count += secondary_count;
+
+    for (first, second) in rule.forbidden_pairs(shape) {
+      for placement in [first, second] {
+        let Some(row_idx) = placement.row(size) else {
+          return unsupported(format!(
+            "forbids {} at {}, {}, which is not on the board",
+            placement.value, placement.cell.x, placement.cell.y
+          ));
+        };
+        rule_columns.rows[row_idx]
+          .1
+          .push(rule_columns.secondary_count);
+      }
+      rule_columns.secondary_count += 1;
+    }
+  }
+
+  Ok(rule_columns)
+}
+
+/// Builds the exact cover problem of boards shaped like `shape` from the
+/// rules alone, with a row for every value in every cell: the value `v` in
+/// the cell at `x, y` is row `(y * size + x) * size + v - 1`. Nothing of
+/// classic Sudoku applies unless its rules are passed. Returns an error if
+/// a rule covers a column it doesn't have, or forbids a placement off the
+/// board.
+pub fn rule_exact_cover(
+  shape: &Board,
+  rules: &[Rc<dyn VariantRule>],
+) -> Result<ExactCover, SolveError> {
+  let rule_columns = rule_columns(shape, rules)?;
+
+  let primary_count = rule_columns.primary_count;
+  let mut exact_cover = ExactCover::with_secondary_columns(
+    primary_count,
+    rule_columns.secondary_count,
+  );
+  for (primary, secondary) in rule_columns.rows {
+    let columns: Vec<usize> = primary
+      .into_iter()
+      .chain(secondary.into_iter().map(|column| primary_count + column))
+      .collect();
+    exact_cover.add_row(&columns)?;
+  }
+
+  Ok(exact_cover)
+}
+
+/// The rules `Variants` holds. Two sets are equal when they hold the same
+/// rules in the same order, not just rules that behave the same.
+#[derive(Clone, Default)]
+pub(crate) struct Rules(pub Vec<Rc<dyn VariantRule>>);
+
+impl PartialEq for Rules {
+  fn eq(&self, other: &Self) -> bool {
+    self.0.len() == other.0.len()
+      && self.0.iter().zip(&other.0).all(|(a, b)| Rc::ptr_eq(a, b))
+  }
+}
+
+impl Eq for Rules {}
+
+impl fmt::Debug for Rules {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "Rules({})", self.0.len())
+  }
+}
+
+/// Calls a rule's `after_decision` from the search
+pub(crate) struct RulePruner {
+  size: usize,
+  rule: Rc<dyn VariantRule>,
+}
+
+impl RulePruner {
+  pub(crate) fn new(rule: Rc<dyn VariantRule>, shape: &Board) -> Self {
+    Self {
+      size: shape.size(),
+      rule,
+    }
+  }
+}
+
+impl Prune for RulePruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    let state = SearchState {
+      size: self.size,
+      values: placed_values(selected_rows, self.size),
+      is_row_visible,
+    };
+    let placements = self.rule.after_decision(&state)?;
+
+    Some(
+      placements
+        .iter()
+        .filter_map(|placement| placement.row(self.size))
+        .collect(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algorithm_x::{
+    count_solutions, generate_exact_cover, launch_algorithm_x,
+  };
+  use crate::variants::Variants;
+
+  fn classic_rules() -> Vec<Rc<dyn VariantRule>> {
+    vec![
+      Rc::new(RowRule),
+      Rc::new(ColumnRule),
+      Rc::new(SubGridRule),
+      Rc::new(ExistenceRule),
+    ]
+  }
+
+  fn rows(exact_cover: &ExactCover) -> Vec<Vec<usize>> {
+    (0..exact_cover.row_count())
+      .map(|row_idx| exact_cover.row(row_idx).to_vec())
+      .collect()
+  }
+
+  /// The main diagonal from the top left holds every value once
+  struct DiagonalRule;
+
+  impl VariantRule for DiagonalRule {
+    fn primary_columns(&self, shape: &Board) -> usize {
+      shape.size()
+    }
+
+    fn columns(&self, _shape: &Board, placement: Placement) -> Vec<usize> {
+      match placement.cell.x == placement.cell.y {
+        true => vec![placement.value as usize - 1],
+        false => vec![],
+      }
+    }
+  }
+
+  /// The four corners hold different values
+  struct CornersRule;
+
+  impl VariantRule for CornersRule {
+    fn forbidden_pairs(&self, shape: &Board) -> Vec<(Placement, Placement)> {
+      let last = shape.size() - 1;
+      let corners = [(0, 0), (last, 0), (0, last), (last, last)];
+      let mut pairs = vec![];
+      for (corner_idx, &(x, y)) in corners.iter().enumerate() {
+        for &(other_x, other_y) in &corners[corner_idx + 1..] {
+          for value in 1..=shape.size() as u32 {
+            let cell = Cell { x, y };
+            let other = Cell {
+              x: other_x,
+              y: other_y,
+            };
+            pairs.push((
+              Placement { cell, value },
+              Placement { cell: other, value },
+            ));
+          }
+        }
+      }
+
+      pairs
+    }
+  }
+
+  /// The top left and bottom right corners add up to the board's size + 1
+  struct CornerSumRule;
+
+  impl VariantRule for CornerSumRule {
+    fn after_decision(&self, state: &SearchState) -> Option<Vec<Placement>> {
+      let size = state.size() as u32;
+      let corners = [Cell { x: 0, y: 0 }, Cell { x: 3, y: 3 }];
+      let mut ruled_out = vec![];
+      for (cell, other) in [(corners[0], corners[1]), (corners[1], corners[0])]
+      {
+        let partners = state.candidates(other);
+        for value in state.candidates(cell) {
+          if !partners.contains(&(size + 1 - value)) {
+            if state.value(cell) != 0 {
+              return None;
+            }
+            ruled_out.push(Placement { cell, value });
+          }
+        }
+      }
+
+      Some(ruled_out)
+    }
+  }
+
+  #[test]
+  fn classic_rules_build_the_classic_cover() {
+    let mut jigsaw = Board::with_box_size(2).unwrap();
+    jigsaw
+      .set_region_map(vec![0, 0, 0, 1, 2, 0, 1, 1, 2, 3, 3, 1, 2, 2, 3, 3])
+      .unwrap();
+
+    for shape in [Board::new(), Board::with_box_size(2).unwrap(), jigsaw] {
+      let classic = generate_exact_cover(&shape, &[], &[]).unwrap();
+      let from_rules = rule_exact_cover(&shape, &classic_rules()).unwrap();
+      assert_eq!(from_rules.column_count(), classic.column_count());
+      assert_eq!(rows(&from_rules), rows(&classic));
+    }
+  }
+
+  #[test]
+  fn rules_restrict_the_search() {
+    let board = Board::with_box_size(2).unwrap();
+    let classic_solutions = launch_algorithm_x(
+      Some(board.clone()),
+      None,
+      Some(1000),
+      None,
+      None,
+      None,
+    )
+    .unwrap();
+
+    let rules: [(Rc<dyn VariantRule>, usize); 3] = [
+      (
+        Rc::new(DiagonalRule),
+        classic_solutions
+          .iter()
+          .filter(|solution| {
+            let mut diagonal: Vec<i32> =
+              (0..4).map(|i| solution.get(i, i)).collect();
+            diagonal.sort_unstable();
+            diagonal == [1, 2, 3, 4]
+          })
+          .count(),
+      ),
+      (
+        Rc::new(CornersRule),
+        classic_solutions
+          .iter()
+          .filter(|solution| {
+            let mut corners: Vec<i32> = [(0, 0), (3, 0), (0, 3), (3, 3)]
+              .iter()
+              .map(|&(x, y)| solution.get(x, y))
+              .collect();
+            corners.sort_unstable();
+            corners == [1, 2, 3, 4]
+          })
+          .count(),
+      ),
+      (
+        Rc::new(CornerSumRule),
+        classic_solutions
+          .iter()
+          .filter(|solution| solution.get(0, 0) + solution.get(3, 3) == 5)
+          .count(),
+      ),
+    ];
+
+    for (rule, expected) in rules {
+      assert!(expected > 0 && expected < classic_solutions.len());
+      let mut variants = Variants::new();
+      variants.add_rule(rule);
+      assert_eq!(
+        count_solutions(&board, 1000, Some(variants.clone())),
+        Ok(expected)
+      );
+    }
+
+    // Givens the rule rejects leave no solution
+    let mut variants = Variants::new();
+    variants.add_rule(Rc::new(CornerSumRule));
+    let mut corners = board.clone();
+    corners.set(0, 0, 1);
+    corners.set(3, 3, 1);
+    assert_eq!(count_solutions(&corners, 1, Some(variants.clone())), Ok(0));
+
+    // Rules stack with each other and with the built in variants
+    variants.add_rule(Rc::new(CornersRule));
+    variants.set_windows(true);
+    let solutions = launch_algorithm_x(
+      Some(board),
+      None,
+      Some(1000),
+      None,
+      None,
+      Some(variants.clone()),
+    )
+    .unwrap();
+    assert!(!solutions.is_empty());
+    for solution in &solutions {
+      assert_eq!(solution.get(0, 0) + solution.get(3, 3), 5);
+      assert_ne!(solution.get(3, 0), solution.get(0, 3));
+      assert_eq!(solution.validate_with(&variants), Ok(vec![]));
+    }
+  }
+
+  #[test]
+  fn rule_columns_must_fit() {
+    struct Overflowing;
+    impl VariantRule for Overflowing {
+      fn primary_columns(&self, _shape: &Board) -> usize {
+        1
+      }
+
+      fn columns(&self, _shape: &Board, _placement: Placement) -> Vec<usize> {
+        vec![1]
+      }
+    }
+
+    struct OffTheBoard;
+    impl VariantRule for OffTheBoard {
+      fn forbidden_pairs(&self, _shape: &Board) -> Vec<(Placement, Placement)> {
+        let placement = |x| Placement {
+          cell: Cell { x, y: 0 },
+          value: 1,
+        };
+        vec![(placement(0), placement(9))]
+      }
+    }
+
+    let rules: [Rc<dyn VariantRule>; 2] =
+      [Rc::new(Overflowing), Rc::new(OffTheBoard)];
+    for rule in rules {
+      let mut variants = Variants::new();
+      variants.add_rule(rule.clone());
+      assert!(matches!(
+        count_solutions(&Board::new(), 1, Some(variants)),
+        Err(SolveError::UnsupportedVariant(_))
+      ));
+      assert!(matches!(
+        rule_exact_cover(&Board::new(), &[rule]),
+        Err(SolveError::UnsupportedVariant(_))
+      ));
+    }
+  }
+}
@@ -0,0 +1,620 @@
+use crate::algorithm_x::{
+  constraint_count, count_solutions, generate_constraint_table,
+  launch_algorithm_x, random_index, solver_rng, ColumnHeuristic,
+  DecisionStrategy, SolverRng,
+};
+use crate::board::{Board, Conflict};
+use crate::dancing_links::ExactCoverIter;
+use crate::exact_cover::ExactCover;
+use crate::solve_error::SolveError;
+use rand::Rng;
+use wasm_bindgen::prelude::*;
+
+/// The number of cells on each side of the layout the five grids fit in
+const LAYOUT_SIZE: usize = 21;
+
+/// The number of cells on each side of every grid
+const GRID_SIZE: usize = 9;
+
+/// The top left cell of each grid on the layout, left to right then top to
+/// bottom. The centre grid, the third, shares a corner box with each of the
+/// others.
+const GRID_OFFSETS: [(usize, usize); 5] =
+  [(0, 0), (12, 0), (6, 6), (0, 12), (12, 12)];
+
+/// A Samurai board: five 9x9 grids laid out on 21x21 cells, one in each
+/// corner and one in the centre overlapping a corner box of each of the
+/// others. The cells of an overlapping box belong to both grids. The cells
+/// of the layout outside every grid always hold 0.
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SamuraiBoard {
+  /// Every cell of the layout in row order
+  cells: Vec<i32>,
+}
+
+impl Default for SamuraiBoard {
+  fn default() -> Self {
+    Self {
+      cells: vec![0; LAYOUT_SIZE * LAYOUT_SIZE],
+    }
+  }
+}
+
+#[wasm_bindgen]
+impl SamuraiBoard {
+  /// Creates an empty Samurai board
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Reads a board from 21 lines of 21 characters, one per cell of the
+  /// layout. Cells of the grids hold a digit, or `.` or `0` when empty.
+  /// Cells outside every grid are spaces, `.` or `0`, and lines may end
+  /// early when the rest of their cells are outside every grid.
+  pub fn from_layout(layout: &str) -> Result<SamuraiBoard, SolveError> {
+    let invalid = |reason: String| Err(SolveError::InvalidLayout(reason));
+    let lines: Vec<&str> = layout.lines().collect();
+    if lines.len() != LAYOUT_SIZE {
+      return invalid(format!(
+        "The layout has {} lines, it needs {}",
+        lines.len(),
+        LAYOUT_SIZE
+      ));
+    }
+
+    let mut board = Self::new();
+    for (y, line) in lines.into_iter().enumerate() {
+      let characters: Vec<char> = line.chars().collect();
+      if characters.len() > LAYOUT_SIZE {
+        return invalid(format!(
+          "Line {} has {} characters, it can have at most {}",
+          y,
+          characters.len(),
+          LAYOUT_SIZE
+        ));
+      }
+
+      for x in 0..LAYOUT_SIZE {
+        let character = characters.get(x).copied().unwrap_or(' ');
+        let value = match (character, is_on_grid(x, y)) {
+          ('.' | '0', _) | (' ', false) => 0,
+          ('1'..='9', true) => character as i32 - '0' as i32,
+          _ => {
+            return invalid(format!(
+              "The cell at {}, {} holds '{}'",
+              x, y, character
+            ))
+          }
+        };
+        board.set(x, y, value);
+      }
+    }
+
+    Ok(board)
+  }
+
+  /// Writes the board as 21 lines of 21 characters, the inverse of
+  /// `from_layout`. Empty cells of the grids are `.`, and cells outside
+  /// every grid are spaces.
+  pub fn to_layout(&self) -> String {
+    (0..LAYOUT_SIZE)
+      .map(|y| {
+        (0..LAYOUT_SIZE)
+          .map(|x| match self.get(x, y) {
+            _ if !is_on_grid(x, y) => ' ',
+            value @ 1..=9 => char::from(b'0' + value as u8),
+            _ => '.',
+          })
+          .collect::<String>()
+      })
+      .collect::<Vec<String>>()
+      .join("\n")
+  }
+
+  /// Returns true if the cell of the layout is on one of the grids. The top
+  /// left corner of the layout is 0,0
+  pub fn is_on_grid(&self, x: usize, y: usize) -> bool {
+    is_on_grid(x, y)
+  }
+
+  pub fn set(&mut self, x: usize, y: usize, value: i32) {
+    self.cells[y * LAYOUT_SIZE + x] = value;
+  }
+
+  pub fn get(&self, x: usize, y: usize) -> i32 {
+    self.cells[y * LAYOUT_SIZE + x]
+  }
+
+  /// Returns a copy of one of the grids as a 9x9 board. Grids are numbered
+  /// left to right then top to bottom, so the centre grid is 2. Returns an
+  /// error if there is no such grid.
+  pub fn grid(&self, grid_idx: usize) -> Result<Board, SolveError> {
+    check_grid_idx(grid_idx)?;
+
+    Ok(self.grid_board(grid_idx))
+  }
+
+  /// Copies a 9x9 board into one of the grids, overwriting the cells it
+  /// shares with the centre grid or the outer grids. Returns an error and
+  /// leaves the board unchanged if there is no such grid or the board is
+  /// not 9x9.
+  pub fn set_grid(
+    &mut self,
+    grid_idx: usize,
+    grid: &Board,
+  ) -> Result<(), SolveError> {
+    check_grid_idx(grid_idx)?;
+    if grid.size() != GRID_SIZE {
+      return Err(SolveError::InvalidGrid(format!(
+        "Grids are {}x{}, the board is {}x{}",
+        GRID_SIZE,
+        GRID_SIZE,
+        grid.size(),
+        grid.size()
+      )));
+    }
+    self.set_grid_board(grid_idx, grid);
+
+    Ok(())
+  }
+
+  /// Returns every pair of cells that hold the same value in a row, column
+  /// or sub grid of one of the grids, at their positions on the layout. A
+  /// pair in a box two grids share is reported once.
+  pub fn validate(&self) -> Vec<Conflict> {
+    let mut conflicts: Vec<Conflict> = vec![];
+
+    for (grid_idx, &(left, top)) in GRID_OFFSETS.iter().enumerate() {
+      for conflict in self.grid_board(grid_idx).validate() {
+        let conflict = Conflict {
+          first_x: conflict.first_x + left,
+          first_y: conflict.first_y + top,
+          second_x: conflict.second_x + left,
+          second_y: conflict.second_y + top,
+          ..conflict
+        };
+        if !conflicts.contains(&conflict) {
+          conflicts.push(conflict);
+        }
+      }
+    }
+
+    conflicts
+  }
+}
+
+impl SamuraiBoard {
+  /// A copy of one of the grids as a 9x9 board, the grid must exist
+  fn grid_board(&self, grid_idx: usize) -> Board {
+    let (left, top) = GRID_OFFSETS[grid_idx];
+    let mut grid = Board::new();
+    for y in 0..GRID_SIZE {
+      for x in 0..GRID_SIZE {
+        grid.set(x, y, self.get(left + x, top + y));
+      }
+    }
+
+    grid
+  }
+
+  /// Copies a 9x9 board into one of the grids, the grid must exist
+  fn set_grid_board(&mut self, grid_idx: usize, grid: &Board) {
+    let (left, top) = GRID_OFFSETS[grid_idx];
+    for y in 0..GRID_SIZE {
+      for x in 0..GRID_SIZE {
+        self.set(left + x, top + y, grid.get(x, y));
+      }
+    }
+  }
+}
+
+/// Returns an error if there is no grid with the index
+fn check_grid_idx(grid_idx: usize) -> Result<(), SolveError> {
+  if grid_idx >= GRID_OFFSETS.len() {
+    return Err(SolveError::InvalidGrid(format!(
+      "Grid {} is not one of the {} grids, they are numbered from 0",
+      grid_idx,
+      GRID_OFFSETS.len()
+    )));
+  }
+
+  Ok(())
+}
+
+/// Returns true if the cell of the layout is on one of the grids
+fn is_on_grid(x: usize, y: usize) -> bool {
+  grids_of(x, y).next().is_some()
+}
+
+/// The grids a cell of the layout is on, two for a cell of a shared box
+fn grids_of(x: usize, y: usize) -> impl Iterator<Item = usize> {
+  (0..GRID_OFFSETS.len()).filter(move |&grid_idx| {
+    let (left, top) = GRID_OFFSETS[grid_idx];
+    (left..left + GRID_SIZE).contains(&x) && (top..top + GRID_SIZE).contains(&y)
+  })
+}
+
+/// The cells of the layout on one of the grids, in row order. Each one has
+/// a row of the exact cover problem for every value.
+fn grid_cells() -> Vec<(usize, usize)> {
+  (0..LAYOUT_SIZE)
+    .flat_map(|y| (0..LAYOUT_SIZE).map(move |x| (x, y)))
+    .filter(|&(x, y)| is_on_grid(x, y))
+    .collect()
+}
+
+/// The exact cover problem of a Samurai board: five copies of the
+/// constraint table of a 9x9 board, one after another. A value in a cell
+/// covers the columns it covers in every grid the cell is on, so a cell two
+/// grids share is a single cell of both.
+fn samurai_exact_cover(cells: &[(usize, usize)]) -> ExactCover {
+  let grid_table = generate_constraint_table(&Board::new(), &[]).table;
+  let grid_column_count = constraint_count(GRID_SIZE);
+  let mut exact_cover = ExactCover::new(GRID_OFFSETS.len() * grid_column_count);
+
+  for &(x, y) in cells {
+    for value_idx in 0..GRID_SIZE {
+      let mut columns = vec![];
+      for (grid_idx, &(left, top)) in GRID_OFFSETS.iter().enumerate() {
+        if (left..left + GRID_SIZE).contains(&x)
+          && (top..top + GRID_SIZE).contains(&y)
+        {
+          let grid_row = ((y - top) * GRID_SIZE + x - left) * GRID_SIZE;
+          columns.extend(
+            grid_table[grid_row + value_idx]
+              .iter()
+              .map(|column| grid_idx * grid_column_count + column),
+          );
+        }
+      }
+
+      exact_cover
+        .add_row(&columns)
+        .expect("Grid columns are below the column count");
+    }
+  }
+
+  exact_cover
+}
+
+/// The exact cover problem of a Samurai board, with the cells of the layout
+/// its rows put values in
+struct SamuraiCover {
+  exact_cover: ExactCover,
+  /// The cell of the layout each group of `GRID_SIZE` rows puts a value in
+  cells: Vec<(usize, usize)>,
+}
+
+impl SamuraiCover {
+  fn new() -> Self {
+    let cells = grid_cells();
+
+    Self {
+      exact_cover: samurai_exact_cover(&cells),
+      cells,
+    }
+  }
+
+  /// Searches for the solutions of the board, after checking every cell of
+  /// the grids holds 0 up to 9, cells outside them hold 0 and no two values
+  /// conflict
+  fn search(
+    &mut self,
+    board: &SamuraiBoard,
+    decision_strategy: DecisionStrategy,
+    seed: Option<u64>,
+  ) -> Result<ExactCoverIter, SolveError> {
+    for y in 0..LAYOUT_SIZE {
+      for x in 0..LAYOUT_SIZE {
+        let value = board.get(x, y);
+        let valid_values = if is_on_grid(x, y) { 0..=9 } else { 0..=0 };
+        if !valid_values.contains(&value) {
+          return Err(SolveError::InvalidValue { x, y, value });
+        }
+      }
+    }
+
+    let conflicts = board.validate();
+    if !conflicts.is_empty() {
+      return Err(SolveError::ContradictoryGivens(conflicts));
+    }
+
+    let given_rows: Vec<usize> = (self.cells.iter().enumerate())
+      .filter(|&(_, &(x, y))| board.get(x, y) != 0)
+      .map(|(cell_idx, &(x, y))| {
+        cell_idx * GRID_SIZE + board.get(x, y) as usize - 1
+      })
+      .collect();
+
+    self.exact_cover.set_decision_strategy(decision_strategy);
+    self.exact_cover.set_seed(seed);
+    self.exact_cover.solutions(&given_rows)
+  }
+
+  /// Maps the exact cover rows of a solution back to a Samurai board
+  fn board(&self, solution: &[usize]) -> SamuraiBoard {
+    let mut board = SamuraiBoard::new();
+    for &row_idx in solution {
+      let (x, y) = self.cells[row_idx / GRID_SIZE];
+      board.set(x, y, (row_idx % GRID_SIZE + 1) as i32);
+    }
+
+    board
+  }
+
+  fn count_solutions(
+    &mut self,
+    board: &SamuraiBoard,
+    limit: usize,
+  ) -> Result<usize, SolveError> {
+    let mut solutions = self.search(board, DecisionStrategy::First, None)?;
+
+    Ok(solutions.count_up_to(limit))
+  }
+}
+
+/// Finds up to `desired_solutions` solutions of a Samurai board (1 by
+/// default), starting from an empty board by default. Like
+/// `launch_algorithm_x`, values are tried at random unless told otherwise,
+/// and the same seed always gives the same boards. Searching a board with
+/// few givens at random can take a long time, `generate_samurai_puzzle`
+/// fills its solutions one grid at a time instead.
+#[wasm_bindgen]
+pub fn solve_samurai(
+  starting_board: Option<SamuraiBoard>,
+  decision_strategy: Option<DecisionStrategy>,
+  desired_solutions: Option<usize>,
+  seed: Option<u64>,
+) -> Result<Vec<SamuraiBoard>, SolveError> {
+  let mut cover = SamuraiCover::new();
+  let solutions: Vec<SamuraiBoard> = cover
+    .search(
+      &starting_board.unwrap_or_default(),
+      decision_strategy.unwrap_or(DecisionStrategy::Random),
+      seed,
+    )?
+    .take(desired_solutions.unwrap_or(1))
+    .map(|solution| cover.board(&solution))
+    .collect();
+
+  if solutions.is_empty() {
+    return Err(SolveError::Unsolvable);
+  }
+
+  Ok(solutions)
+}
+
+/// Counts the solutions of a Samurai board, stopping once `limit` have been
+/// found
+#[wasm_bindgen]
+pub fn count_samurai_solutions(
+  board: &SamuraiBoard,
+  limit: usize,
+) -> Result<usize, SolveError> {
+  SamuraiCover::new().count_solutions(board, limit)
+}
+
+/// A generated Samurai puzzle with the only solution its givens lead to
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SamuraiPuzzle {
+  puzzle: SamuraiBoard,
+  solution: SamuraiBoard,
+}
+
+#[wasm_bindgen]
+impl SamuraiPuzzle {
+  pub fn puzzle(&self) -> SamuraiBoard {
+    self.puzzle.clone()
+  }
+
+  pub fn solution(&self) -> SamuraiBoard {
+    self.solution.clone()
+  }
+}
+
+/// A random solved Samurai board, filled one grid at a time. Searching the
+/// whole board at once can wander for a long time before it finds out two
+/// grids disagree on the box they share. Once the centre grid is filled,
+/// each outer grid only has to complete its shared box, which any full box
+/// can be.
+fn random_solution(rng: &mut SolverRng) -> Result<SamuraiBoard, SolveError> {
+  let mut solution = SamuraiBoard::new();
+
+  for grid_idx in [2, 0, 1, 3, 4] {
+    let grid = launch_algorithm_x(
+      Some(solution.grid_board(grid_idx)),
+      Some(DecisionStrategy::Random),
+      Some(1),
+      Some(ColumnHeuristic::MinimumRemaining),
+      Some(rng.gen()),
+      None,
+    )?
+    .remove(0);
+    solution.set_grid_board(grid_idx, &grid);
+  }
+
+  Ok(solution)
+}
+
+/// Generates a Samurai puzzle with exactly one solution. Starts from a
+/// random solution and empties its cells in random order, keeping a cell
+/// empty only if every grid it is on still has one solution by itself.
+/// Grids that can each be solved alone can only be solved one way together,
+/// and checking a 9x9 grid stays quick where searching the whole board can
+/// take minutes once few givens are left. The puzzles keep a few more
+/// givens than a search of the whole board would leave.
+#[wasm_bindgen]
+pub fn generate_samurai_puzzle(
+  seed: Option<u64>,
+) -> Result<SamuraiPuzzle, SolveError> {
+  let mut rng = solver_rng(seed);
+  let solution = random_solution(&mut rng)?;
+
+  let mut puzzle = solution.clone();
+  let mut cells = grid_cells();
+  while !cells.is_empty() {
+    let (x, y) = cells.swap_remove(random_index(&mut rng, cells.len()));
+    puzzle.set(x, y, 0);
+    for grid_idx in grids_of(x, y) {
+      if count_solutions(&puzzle.grid_board(grid_idx), 2, None)? != 1 {
+        puzzle.set(x, y, solution.get(x, y));
+        break;
+      }
+    }
+  }
+
+  Ok(SamuraiPuzzle { puzzle, solution })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_solved(board: &SamuraiBoard) {
+    for grid_idx in 0..GRID_OFFSETS.len() {
+      let grid = board.grid(grid_idx).unwrap();
+      assert!(grid.validate().is_empty());
+      assert!((0..GRID_SIZE * GRID_SIZE)
+        .all(|cell| grid.get(cell % GRID_SIZE, cell / GRID_SIZE) != 0));
+    }
+  }
+
+  #[test]
+  fn layouts_round_trip() {
+    let solution = random_solution(&mut solver_rng(Some(3))).unwrap();
+    assert_solved(&solution);
+
+    let layout = solution.to_layout();
+    let lines: Vec<&str> = layout.lines().collect();
+    assert_eq!(lines.len(), LAYOUT_SIZE);
+    assert!(lines.iter().all(|line| line.len() == LAYOUT_SIZE));
+    // The gaps between the outer grids, above and below the centre grid
+    assert_eq!(&lines[0][9..12], "   ");
+    assert_ne!(&lines[6][9..12], "   ");
+    assert_eq!(SamuraiBoard::from_layout(&layout), Ok(solution.clone()));
+
+    // Empty cells, and trailing spaces left out
+    let mut puzzle = solution.clone();
+    puzzle.set(0, 0, 0);
+    let layout = puzzle.to_layout();
+    assert!(layout.starts_with('.'));
+    let trimmed: Vec<&str> = layout.lines().map(str::trim_end).collect();
+    assert_eq!(
+      SamuraiBoard::from_layout(&trimmed.join("\n")),
+      Ok(puzzle.clone())
+    );
+
+    // Digits off the grids, spaces and missing cells on them
+    let mut lines: Vec<String> = layout.lines().map(String::from).collect();
+    for (y, x, character) in [(0, 10, '5'), (0, 0, ' '), (20, 20, 'x')] {
+      let mut broken_lines = lines.clone();
+      broken_lines[y].replace_range(x..x + 1, &character.to_string());
+      assert!(matches!(
+        SamuraiBoard::from_layout(&broken_lines.join("\n")),
+        Err(SolveError::InvalidLayout(_))
+      ));
+    }
+    lines[10].truncate(12);
+    assert!(matches!(
+      SamuraiBoard::from_layout(&lines.join("\n")),
+      Err(SolveError::InvalidLayout(_))
+    ));
+    assert!(matches!(
+      SamuraiBoard::from_layout(&lines[1..].join("\n")),
+      Err(SolveError::InvalidLayout(_))
+    ));
+  }
+
+  #[test]
+  fn grids_share_the_corner_boxes_of_the_centre_grid() {
+    let solution = random_solution(&mut solver_rng(Some(5))).unwrap();
+    let centre = solution.grid(2).unwrap();
+
+    // The bottom right box of the top left grid is the top left box of
+    // the centre grid, and so on around it
+    let shared_boxes = [(0, 6, 6, 0, 0), (1, 0, 6, 6, 0), (3, 6, 0, 0, 6)];
+    for (grid_idx, left, top, centre_left, centre_top) in shared_boxes {
+      let grid = solution.grid(grid_idx).unwrap();
+      for y in 0..3 {
+        for x in 0..3 {
+          assert_eq!(
+            grid.get(left + x, top + y),
+            centre.get(centre_left + x, centre_top + y)
+          );
+        }
+      }
+    }
+    let grid = solution.grid(4).unwrap();
+    assert_eq!(grid.get(0, 0), centre.get(6, 6));
+
+    // The shared cells are merged, so copying a grid over keeps the board
+    let mut copy = solution.clone();
+    copy.set_grid(2, &centre).unwrap();
+    assert_eq!(copy, solution);
+
+    // There are only five grids, all of them 9x9
+    assert!(matches!(solution.grid(5), Err(SolveError::InvalidGrid(_))));
+    assert!(matches!(
+      copy.set_grid(5, &centre),
+      Err(SolveError::InvalidGrid(_))
+    ));
+    assert!(matches!(
+      copy.set_grid(0, &Board::with_box_size(2).unwrap()),
+      Err(SolveError::InvalidGrid(_))
+    ));
+    assert_eq!(copy, solution);
+  }
+
+  #[test]
+  fn solves_and_generates_samurai_puzzles() {
+    let generated = generate_samurai_puzzle(Some(1)).unwrap();
+    let (puzzle, solution) = (generated.puzzle(), generated.solution());
+    assert_solved(&solution);
+    assert_eq!(count_samurai_solutions(&puzzle, 10), Ok(1));
+    assert_eq!(
+      solve_samurai(Some(puzzle.clone()), None, Some(2), None),
+      Ok(vec![solution.clone()])
+    );
+
+    // Every given is needed for one of its grids to have one solution
+    for (x, y) in grid_cells() {
+      if puzzle.get(x, y) != 0 {
+        let mut fewer_givens = puzzle.clone();
+        fewer_givens.set(x, y, 0);
+        assert!(grids_of(x, y).any(|grid_idx| {
+          count_solutions(&fewer_givens.grid(grid_idx).unwrap(), 2, None)
+            == Ok(2)
+        }));
+      }
+    }
+
+    // A value in a shared box conflicts in both grids, but is reported once
+    let mut board = SamuraiBoard::new();
+    board.set(6, 6, 4);
+    board.set(8, 8, 4);
+    assert_eq!(
+      solve_samurai(Some(board.clone()), None, None, None),
+      Err(SolveError::ContradictoryGivens(vec![Conflict {
+        first_x: 6,
+        first_y: 6,
+        second_x: 8,
+        second_y: 8,
+        constraint_type: crate::algorithm_x::ConstraintType::SubGrid,
+      }]))
+    );
+
+    let mut board = SamuraiBoard::new();
+    board.set(10, 0, 4);
+    assert_eq!(
+      count_samurai_solutions(&board, 1),
+      Err(SolveError::InvalidValue {
+        x: 10,
+        y: 0,
+        value: 4
+      })
+    );
+  }
+}
@@ -0,0 +1,370 @@
+use crate::algorithm_x::{
+  candidates, check_uniqueness, launch_algorithm_x, placed_values,
+  random_index, solver_rng, ColumnHeuristic, ConstraintType, DecisionStrategy,
+};
+use crate::board::{Board, Cell};
+use crate::dancing_links::Prune;
+use crate::solve_error::SolveError;
+use crate::uniqueness::Uniqueness;
+use crate::variants::Variants;
+use rand::Rng;
+use wasm_bindgen::prelude::*;
+
+/// A sandwich clue outside a row or column, the sum of the values between
+/// its smallest and largest values. On a 9x9 board those are the 1 and the
+/// 9, wherever they are in the line.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Sandwich {
+  constraint_type: ConstraintType,
+  index: usize,
+  sum: u32,
+}
+
+#[wasm_bindgen]
+impl Sandwich {
+  /// The clue left of the row `index`, counting rows from the top
+  pub fn row(index: usize, sum: u32) -> Self {
+    Self {
+      constraint_type: ConstraintType::Row,
+      index,
+      sum,
+    }
+  }
+
+  /// The clue above the column `index`, counting columns from the left
+  pub fn column(index: usize, sum: u32) -> Self {
+    Self {
+      constraint_type: ConstraintType::Column,
+      index,
+      sum,
+    }
+  }
+
+  /// `Row` or `Column`
+  pub fn constraint_type(&self) -> ConstraintType {
+    self.constraint_type
+  }
+
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  pub fn sum(&self) -> u32 {
+    self.sum
+  }
+}
+
+impl Sandwich {
+  /// The cells of the clue's row or column on boards with `size` cells on
+  /// each side, from the clue inwards
+  fn cells(&self, size: usize) -> Vec<Cell> {
+    (0..size)
+      .map(|i| match self.constraint_type {
+        ConstraintType::Row => Cell {
+          x: i,
+          y: self.index,
+        },
+        _ => Cell {
+          x: self.index,
+          y: i,
+        },
+      })
+      .collect()
+  }
+
+  /// Returns true if every cell of the line is filled and the values
+  /// between its smallest and largest values don't add up to the sum
+  pub(crate) fn is_broken(&self, board: &Board) -> bool {
+    let values: Vec<i32> = self
+      .cells(board.size())
+      .iter()
+      .map(|cell| board.get(cell.x, cell.y))
+      .collect();
+
+    !values.contains(&0) && sandwich_sum(&values) != Some(self.sum)
+  }
+}
+
+/// The sum of the values between the smallest and largest values of a
+/// filled line, `None` if either is missing
+fn sandwich_sum(values: &[i32]) -> Option<u32> {
+  let size = values.len() as i32;
+  let smallest = values.iter().position(|&value| value == 1)?;
+  let largest = values.iter().position(|&value| value == size)?;
+  // On a board with a single value it is both the smallest and the largest
+  if smallest == largest {
+    return Some(0);
+  }
+  let between = smallest.min(largest) + 1..smallest.max(largest);
+
+  Some(values[between].iter().sum::<i32>() as u32)
+}
+
+/// The sandwich clues of every row, then every column, of a filled board
+pub(crate) fn sandwiches_of(board: &Board) -> Vec<Sandwich> {
+  let size = board.size();
+
+  (0..size)
+    .map(|y| (Sandwich::row(y, 0), board.get_row(y).to_vec()))
+    .chain((0..size).map(|x| (Sandwich::column(x, 0), board.get_column(x))))
+    .map(|(sandwich, values)| Sandwich {
+      sum: sandwich_sum(&values).unwrap_or(0),
+      ..sandwich
+    })
+    .collect()
+}
+
+/// Checks every sandwich clue is outside a row or column of boards shaped
+/// like `shape`, which need a smallest and a largest value to sandwich
+pub(crate) fn check_sandwiches(
+  sandwiches: &[Sandwich],
+  shape: &Board,
+) -> Result<(), SolveError> {
+  if !sandwiches.is_empty() && shape.size() < 2 {
+    return Err(SolveError::InvalidSandwich(format!(
+      "Sandwiches need at least 2 values, but the board has {}",
+      shape.size()
+    )));
+  }
+
+  for (sandwich_idx, sandwich) in sandwiches.iter().enumerate() {
+    if sandwich.index >= shape.size() {
+      return Err(SolveError::InvalidSandwich(format!(
+        "Sandwich {} is outside {:?} {}, which is not on the board",
+        sandwich_idx, sandwich.constraint_type, sandwich.index
+      )));
+    }
+  }
+
+  Ok(())
+}
+
+/// A sandwich clue with the cells of its line as indexes in row order
+struct SandwichLine {
+  cells: Vec<usize>,
+  sum: i64,
+}
+
+/// Prunes values that can't fill the sandwiches. After every decision each
+/// line tries every pair of cells that could still hold its smallest and
+/// largest values, keeping the pairs whose cells between can still add up
+/// to the sum. Every cell keeps the values one of those pairs allows.
+pub(crate) struct SandwichPruner {
+  size: usize,
+  lines: Vec<SandwichLine>,
+}
+
+impl SandwichPruner {
+  /// The sandwiches must have passed `check_sandwiches`
+  pub(crate) fn new(sandwiches: &[Sandwich], shape: &Board) -> Self {
+    let size = shape.size();
+
+    Self {
+      size,
+      lines: sandwiches
+        .iter()
+        .map(|sandwich| SandwichLine {
+          cells: sandwich
+            .cells(size)
+            .iter()
+            .map(|&Cell { x, y }| y * size + x)
+            .collect(),
+          sum: sandwich.sum as i64,
+        })
+        .collect(),
+    }
+  }
+}
+
+impl Prune for SandwichPruner {
+  fn prune(
+    &self,
+    selected_rows: &[usize],
+    is_row_visible: &dyn Fn(usize) -> bool,
+  ) -> Option<Vec<usize>> {
+    let values = placed_values(selected_rows, self.size);
+    let largest = self.size as u32;
+    let mut pruned_rows = vec![];
+
+    for line in &self.lines {
+      // What each cell can hold, a placed value counts as its only one
+      let cell_candidates: Vec<Vec<u32>> = line
+        .cells
+        .iter()
+        .map(|&cell| match values[cell] {
+          0 => candidates(cell, self.size, is_row_visible),
+          value => vec![value],
+        })
+        .collect();
+      // The smallest and largest values each cell can hold between the
+      // bread, which are neither the smallest nor the largest value
+      let fillings: Vec<Option<(i64, i64)>> = cell_candidates
+        .iter()
+        .map(|candidates| {
+          let mut filling = candidates
+            .iter()
+            .filter(|&&value| value != 1 && value != largest);
+          let low = *filling.next()? as i64;
+          let high = filling.next_back().map_or(low, |&value| value as i64);
+          Some((low, high))
+        })
+        .collect();
+
+      let can_hold =
+        |cell_idx: usize, value| cell_candidates[cell_idx].contains(&value);
+      let mut allowed = vec![vec![false; self.size + 1]; line.cells.len()];
+      let mut fits = false;
+
+      for smallest_idx in (0..line.cells.len()).filter(|&i| can_hold(i, 1)) {
+        for largest_idx in (0..line.cells.len())
+          .filter(|&i| i != smallest_idx && can_hold(i, largest))
+        {
+          let first = smallest_idx.min(largest_idx);
+          let last = smallest_idx.max(largest_idx);
+          let Some(between) = fillings[first + 1..last]
+            .iter()
+            .copied()
+            .collect::<Option<Vec<(i64, i64)>>>()
+          else {
+            continue;
+          };
+
+          // The cells between hold different values from 2 up to one
+          // below the largest value
+          let (count, size) = (between.len() as i64, self.size as i64);
+          let low_sum: i64 = between.iter().map(|(low, _)| low).sum();
+          let high_sum: i64 = between.iter().map(|(_, high)| high).sum();
+          let lowest = low_sum.max((count + 1) * (count + 2) / 2 - 1);
+          let highest = high_sum.min(count * (2 * size - count - 1) / 2);
+          if line.sum < lowest || line.sum > highest {
+            continue;
+          }
+
+          fits = true;
+          allowed[smallest_idx][1] = true;
+          allowed[largest_idx][largest as usize] = true;
+          for (cell_idx, candidates) in cell_candidates.iter().enumerate() {
+            let (low, high) = if cell_idx > first && cell_idx < last {
+              let (low, high) = between[cell_idx - first - 1];
+              (line.sum - (high_sum - high), line.sum - (low_sum - low))
+            } else {
+              (1, size)
+            };
+
+            for &value in candidates {
+              if value != 1
+                && value != largest
+                && (low..=high).contains(&(value as i64))
+              {
+                allowed[cell_idx][value as usize] = true;
+              }
+            }
+          }
+        }
+      }
+
+      if !fits {
+        return None;
+      }
+      for (cell_idx, &cell) in line.cells.iter().enumerate() {
+        if values[cell] == 0 {
+          pruned_rows.extend(
+            cell_candidates[cell_idx]
+              .iter()
+              .filter(|&&value| !allowed[cell_idx][value as usize])
+              .map(|&value| cell * self.size + value as usize - 1),
+          );
+        }
+      }
+    }
+
+    Some(pruned_rows)
+  }
+}
+
+/// A generated puzzle: the givens, the variants holding its clues, and the
+/// only solution they lead to
+#[wasm_bindgen]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SandwichPuzzle {
+  puzzle: Board,
+  variants: Variants,
+  solution: Board,
+}
+
+#[wasm_bindgen]
+impl SandwichPuzzle {
+  pub fn puzzle(&self) -> Board {
+    self.puzzle.clone()
+  }
+
+  /// The sandwich clue of every row and column
+  pub fn variants(&self) -> Variants {
+    self.variants.clone()
+  }
+
+  pub fn solution(&self) -> Board {
+    self.solution.clone()
+  }
+}
+
+/// Generates a sandwich puzzle from a random solution of the starting board
+/// (an empty 9x9 board by default). Every row and column gets its sandwich
+/// clue, then givens from the solution are added where the clues leave a
+/// choice, until the solution is unique. Givens that turn out not to be
+/// needed are taken away again, so only a few are left.
+#[wasm_bindgen]
+pub fn generate_sandwich_puzzle(
+  starting_board: Option<Board>,
+  seed: Option<u64>,
+) -> Result<SandwichPuzzle, SolveError> {
+  let mut rng = solver_rng(seed);
+  let starting_board = starting_board.unwrap_or_default();
+  let solution = launch_algorithm_x(
+    Some(starting_board.clone()),
+    Some(DecisionStrategy::Random),
+    Some(1),
+    Some(ColumnHeuristic::MinimumRemaining),
+    Some(rng.gen()),
+    None,
+  )?
+  .remove(0);
+
+  let mut variants = Variants::new();
+  for sandwich in sandwiches_of(&solution) {
+    variants.add_sandwich(sandwich);
+  }
+
+  let is_unique = |puzzle: &Board| -> Result<bool, SolveError> {
+    let uniqueness = check_uniqueness(puzzle, Some(variants.clone()))?;
+    Ok(matches!(uniqueness, Uniqueness::Unique(_)))
+  };
+
+  // Each given rules out the second solution found
+  let mut puzzle = starting_board;
+  let mut added_givens = vec![];
+  while let Uniqueness::Multiple { differences, .. } =
+    check_uniqueness(&puzzle, Some(variants.clone()))?
+  {
+    let Cell { x, y } = differences[random_index(&mut rng, differences.len())];
+    puzzle.set(x, y, solution.get(x, y));
+    added_givens.push(Cell { x, y });
+  }
+
+  // Givens added early may be implied by the ones added after them
+  while !added_givens.is_empty() {
+    let Cell { x, y } =
+      added_givens.swap_remove(random_index(&mut rng, added_givens.len()));
+    puzzle.set(x, y, 0);
+    if !is_unique(&puzzle)? {
+      puzzle.set(x, y, solution.get(x, y));
+    }
+  }
+
+  Ok(SandwichPuzzle {
+    puzzle,
+    variants,
+    solution,
+  })
+}
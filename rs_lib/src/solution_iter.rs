@@ -0,0 +1,79 @@
+use crate::algorithm_x::{
+  map_solution_set_to_board, search_board, ColumnHeuristic, DecisionStrategy,
+};
+use crate::board::Board;
+use crate::dancing_links::ExactCoverIter;
+use crate::js_object::set_property;
+use crate::solve_error::SolveError;
+use crate::variants::Variants;
+use wasm_bindgen::prelude::*;
+
+/// Lazily yields the solutions of a board. The search pauses after every
+/// solution and resumes on the next call, so only the solution being looked
+/// at is held in memory and the caller can stop at any point.
+#[wasm_bindgen]
+pub struct SolutionIter {
+  solutions: ExactCoverIter,
+  /// An empty board the size and shape of the solutions
+  shape: Board,
+}
+
+#[wasm_bindgen]
+impl SolutionIter {
+  /// Takes the same options as `launch_algorithm_x`, with the same defaults
+  #[wasm_bindgen(constructor)]
+  pub fn new(
+    starting_board: Option<Board>,
+    decision_strategy: Option<DecisionStrategy>,
+    column_heuristic: Option<ColumnHeuristic>,
+    seed: Option<u64>,
+    variants: Option<Variants>,
+  ) -> Result<SolutionIter, SolveError> {
+    let decision_strategy =
+      decision_strategy.unwrap_or(DecisionStrategy::Random);
+    let column_heuristic =
+      column_heuristic.unwrap_or(ColumnHeuristic::FirstUnsatisfied);
+
+    let starting_board = starting_board.unwrap_or_default();
+    let solutions = search_board(
+      &starting_board,
+      variants,
+      decision_strategy,
+      column_heuristic,
+      seed,
+    )?;
+
+    Ok(Self {
+      solutions,
+      shape: starting_board.cleared(),
+    })
+  }
+
+  /// Follows JS's iterator protocol, returns `{ value: Board, done: false }`
+  /// for every solution and then `{ value: undefined, done: true }`
+  #[wasm_bindgen(js_name = next)]
+  pub fn next_js(&mut self) -> JsValue {
+    let next = self.next();
+
+    let js_result = js_sys::Object::new();
+    set_property(&js_result, "done", next.is_none().into());
+    set_property(
+      &js_result,
+      "value",
+      next.map_or(JsValue::UNDEFINED, Into::into),
+    );
+
+    js_result.into()
+  }
+}
+
+impl Iterator for SolutionIter {
+  type Item = Board;
+
+  fn next(&mut self) -> Option<Board> {
+    self
+      .solutions
+      .next()
+      .map(|solution| map_solution_set_to_board(&solution, &self.shape))
+  }
+}
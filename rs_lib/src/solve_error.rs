@@ -0,0 +1,144 @@
+use crate::board::Conflict;
+use crate::js_object::set_property;
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+/// Why the solver could not return a solution for a board or exact cover
+/// problem
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+  /// The search ran out of decisions to undo without finding a solution
+  Unsolvable,
+  /// Values on the starting board share a row, column, sub grid or region.
+  /// Holds every conflicting pair the board validation found, it is empty
+  /// when the conflict was only caught by the exact cover search
+  ContradictoryGivens(Vec<Conflict>),
+  /// A cell on the starting board holds a value outside of 0 up to the
+  /// board's size
+  InvalidValue { x: usize, y: usize, value: i32 },
+  /// A board's boxes are 0 cells high or wide
+  InvalidBoxSize { box_height: usize, box_width: usize },
+  /// An exact cover row covers a column the problem does not have
+  InvalidColumn { column: usize },
+  /// A given exact cover row does not exist
+  InvalidRow { row: usize },
+  /// A variant cannot be played on the board, holds why
+  UnsupportedVariant(String),
+  /// A region map does not split the board into regions of `size` joined
+  /// cells, holds why
+  InvalidRegionMap(String),
+  /// A killer cage does not fit on the board, holds why
+  InvalidCage(String),
+  /// A thermometer does not fit on the board, holds why
+  InvalidThermometer(String),
+  /// An arrow does not fit on the board, holds why
+  InvalidArrow(String),
+  /// A sandwich clue is outside a row or column the board doesn't have,
+  /// holds why
+  InvalidSandwich(String),
+  /// A Samurai layout can't be read, holds why
+  InvalidLayout(String),
+  /// A Samurai board has no grid with the index, or a board copied into a
+  /// grid is not 9x9, holds why
+  InvalidGrid(String),
+  /// A kropki dot is not between two cells of the board that share a side,
+  /// or shares its cells with another dot, holds why
+  InvalidDot(String),
+  /// The generator ran out of clues it could remove, in the orbits of its
+  /// symmetry, before the puzzle was down to the target number of clues
+  UnreachableClueCount { target: usize, clues: usize },
+}
+
+impl SolveError {
+  /// The name of the variant, exposed to JS as the error's `kind`
+  pub fn kind(&self) -> &'static str {
+    match self {
+      SolveError::Unsolvable => "Unsolvable",
+      SolveError::ContradictoryGivens(_) => "ContradictoryGivens",
+      SolveError::InvalidValue { .. } => "InvalidValue",
+      SolveError::InvalidBoxSize { .. } => "InvalidBoxSize",
+      SolveError::InvalidColumn { .. } => "InvalidColumn",
+      SolveError::InvalidRow { .. } => "InvalidRow",
+      SolveError::UnsupportedVariant(_) => "UnsupportedVariant",
+      SolveError::InvalidRegionMap(_) => "InvalidRegionMap",
+      SolveError::InvalidCage(_) => "InvalidCage",
+      SolveError::InvalidThermometer(_) => "InvalidThermometer",
+      SolveError::InvalidArrow(_) => "InvalidArrow",
+      SolveError::InvalidSandwich(_) => "InvalidSandwich",
+      SolveError::InvalidLayout(_) => "InvalidLayout",
+      SolveError::InvalidGrid(_) => "InvalidGrid",
+      SolveError::InvalidDot(_) => "InvalidDot",
+      SolveError::UnreachableClueCount { .. } => "UnreachableClueCount",
+    }
+  }
+}
+
+impl fmt::Display for SolveError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      SolveError::Unsolvable => write!(f, "The board has no solution"),
+      SolveError::ContradictoryGivens(conflicts) => write!(
+        f,
+        "The board has equal values in the same row, column, sub grid or \
+         region ({} conflicts)",
+        conflicts.len()
+      ),
+      SolveError::InvalidValue { x, y, value } => write!(
+        f,
+        "The cell at {}, {} holds {}, cells must hold 0 up to the board's \
+         size",
+        x, y, value
+      ),
+      SolveError::InvalidBoxSize {
+        box_height,
+        box_width,
+      } => write!(
+        f,
+        "Boxes of {}x{} cells are too small, boxes must be at least 1 cell \
+         high and wide",
+        box_height, box_width
+      ),
+      SolveError::InvalidColumn { column } => {
+        write!(f, "Column {} is not one of the problem's columns", column)
+      }
+      SolveError::InvalidRow { row } => {
+        write!(f, "Row {} is not one of the problem's rows", row)
+      }
+      SolveError::UnreachableClueCount { target, clues } => write!(
+        f,
+        "No more clues can be removed at {} clues, the target is {}",
+        clues, target
+      ),
+      SolveError::UnsupportedVariant(reason)
+      | SolveError::InvalidRegionMap(reason)
+      | SolveError::InvalidCage(reason)
+      | SolveError::InvalidThermometer(reason)
+      | SolveError::InvalidArrow(reason)
+      | SolveError::InvalidSandwich(reason)
+      | SolveError::InvalidLayout(reason)
+      | SolveError::InvalidGrid(reason)
+      | SolveError::InvalidDot(reason) => write!(f, "{}", reason),
+    }
+  }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Thrown in JS as an `Error` named `SolveError`, with the variant's name
+/// in its `kind` property. Contradictory givens also carry a `conflicts`
+/// array.
+impl From<SolveError> for JsValue {
+  fn from(error: SolveError) -> Self {
+    let js_error = js_sys::Error::new(&error.to_string());
+    js_error.set_name("SolveError");
+    set_property(&js_error, "kind", error.kind().into());
+
+    if let SolveError::ContradictoryGivens(conflicts) = error {
+      let js_conflicts: js_sys::Array =
+        conflicts.into_iter().map(JsValue::from).collect();
+      set_property(&js_error, "conflicts", js_conflicts.into());
+    }
+
+    js_error.into()
+  }
+}
@@ -0,0 +1,74 @@
+use crate::algorithm_x::check_uniqueness;
+use crate::board::{Board, Cell};
+use crate::js_object::set_property;
+use crate::solve_error::SolveError;
+use crate::variants::Variants;
+use wasm_bindgen::prelude::*;
+
+/// How many solutions a board has, with the solutions that prove it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Uniqueness {
+  /// The board has exactly one solution
+  Unique(Box<Board>),
+  /// The board has at least two solutions. Holds the first two found and
+  /// every cell where they differ, a clue in any of those cells rules out
+  /// one of them
+  Multiple {
+    first: Box<Board>,
+    second: Box<Board>,
+    differences: Vec<Cell>,
+  },
+  /// The board has no solution
+  NoSolution,
+}
+
+impl Uniqueness {
+  /// The name of the variant, exposed to JS as the result's `kind`
+  pub fn kind(&self) -> &'static str {
+    match self {
+      Uniqueness::Unique(_) => "Unique",
+      Uniqueness::Multiple { .. } => "Multiple",
+      Uniqueness::NoSolution => "NoSolution",
+    }
+  }
+}
+
+/// Returned to JS as a plain object with the variant's name in its `kind`
+/// property. Unique results carry a `solution`, multiple results carry
+/// `first`, `second` and `differences`.
+impl From<Uniqueness> for JsValue {
+  fn from(uniqueness: Uniqueness) -> Self {
+    let js_result = js_sys::Object::new();
+    set_property(&js_result, "kind", uniqueness.kind().into());
+
+    match uniqueness {
+      Uniqueness::Unique(solution) => {
+        set_property(&js_result, "solution", (*solution).into());
+      }
+      Uniqueness::Multiple {
+        first,
+        second,
+        differences,
+      } => {
+        let js_differences: js_sys::Array =
+          differences.into_iter().map(JsValue::from).collect();
+        set_property(&js_result, "first", (*first).into());
+        set_property(&js_result, "second", (*second).into());
+        set_property(&js_result, "differences", js_differences.into());
+      }
+      Uniqueness::NoSolution => {}
+    }
+
+    js_result.into()
+  }
+}
+
+// wasm_bindgen cannot return enums that hold data, so JS gets the object
+// built above instead
+#[wasm_bindgen(js_name = check_uniqueness)]
+pub fn check_uniqueness_js(
+  board: &Board,
+  variants: Option<Variants>,
+) -> Result<JsValue, SolveError> {
+  check_uniqueness(board, variants).map(JsValue::from)
+}
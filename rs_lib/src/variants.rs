@@ -0,0 +1,408 @@
+use crate::algorithm_x::ConstraintType;
+use crate::board::{Board, BrokenConstraint, Cell, Conflict};
+use crate::dancing_links::Prune;
+use crate::killer::{cage_region, check_cages, Cage, CagePruner};
+use crate::kropki::{check_dots, dot_conflicts, Dot, DotPruner};
+use crate::lines::{
+  check_arrows, check_thermometers, Arrow, ArrowPruner, Thermometer,
+  ThermometerPruner,
+};
+use crate::rule::{RulePruner, Rules, VariantRule};
+use crate::sandwich::{check_sandwiches, Sandwich, SandwichPruner};
+use crate::solve_error::SolveError;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+/// Rules a board follows on top of the rows, columns and boxes of classic
+/// Sudoku. Each rule adds regions that, like a box, must hold every value
+/// once. Killer cages add regions that must not repeat a value, and prune
+/// the search by their sums. Thermometers, arrows, sandwich clues and kropki
+/// dots add no regions, they only prune the search.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Variants {
+  diagonals: bool,
+  windows: bool,
+  anti_knight: bool,
+  anti_king: bool,
+  cages: Vec<Cage>,
+  thermometers: Vec<Thermometer>,
+  arrows: Vec<Arrow>,
+  sandwiches: Vec<Sandwich>,
+  dots: Vec<Dot>,
+  all_dots_given: bool,
+  rules: Rules,
+}
+
+#[wasm_bindgen]
+impl Variants {
+  /// Creates a set of variants with every rule turned off, which is classic
+  /// Sudoku
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sudoku-X, both main diagonals must hold every value once
+  pub fn diagonals(&self) -> bool {
+    self.diagonals
+  }
+
+  pub fn set_diagonals(&mut self, diagonals: bool) {
+    self.diagonals = diagonals;
+  }
+
+  /// Windoku, the windows must hold every value once. Windows are the size
+  /// of a box, with one fewer across and down than there are boxes. They
+  /// are one cell apart from each other and from the edges of the board,
+  /// so a 9x9 board has four 3x3 windows.
+  pub fn windows(&self) -> bool {
+    self.windows
+  }
+
+  pub fn set_windows(&mut self, windows: bool) {
+    self.windows = windows;
+  }
+
+  /// Anti-knight, cells a knight's move apart must not hold the same value
+  pub fn anti_knight(&self) -> bool {
+    self.anti_knight
+  }
+
+  pub fn set_anti_knight(&mut self, anti_knight: bool) {
+    self.anti_knight = anti_knight;
+  }
+
+  /// Anti-king, cells a king's move apart must not hold the same value. Only
+  /// diagonal neighbours add anything, the others share a row or column.
+  pub fn anti_king(&self) -> bool {
+    self.anti_king
+  }
+
+  pub fn set_anti_king(&mut self, anti_king: bool) {
+    self.anti_king = anti_king;
+  }
+
+  /// Killer Sudoku, the cells of each cage must add up to its sum without
+  /// repeating a value. Cages may not share cells.
+  pub fn add_cage(&mut self, cage: Cage) {
+    self.cages.push(cage);
+  }
+
+  pub fn cages(&self) -> Vec<Cage> {
+    self.cages.clone()
+  }
+
+  pub fn clear_cages(&mut self) {
+    self.cages.clear();
+  }
+
+  /// Thermo Sudoku, the values on each thermometer must strictly increase
+  /// from its bulb
+  pub fn add_thermometer(&mut self, thermometer: Thermometer) {
+    self.thermometers.push(thermometer);
+  }
+
+  pub fn thermometers(&self) -> Vec<Thermometer> {
+    self.thermometers.clone()
+  }
+
+  pub fn clear_thermometers(&mut self) {
+    self.thermometers.clear();
+  }
+
+  /// Arrow Sudoku, the value in the circle of each arrow must be the sum of
+  /// the values along it
+  pub fn add_arrow(&mut self, arrow: Arrow) {
+    self.arrows.push(arrow);
+  }
+
+  pub fn arrows(&self) -> Vec<Arrow> {
+    self.arrows.clone()
+  }
+
+  pub fn clear_arrows(&mut self) {
+    self.arrows.clear();
+  }
+
+  /// Sandwich Sudoku, the values between the smallest and largest values of
+  /// a row or column must add up to its clue
+  pub fn add_sandwich(&mut self, sandwich: Sandwich) {
+    self.sandwiches.push(sandwich);
+  }
+
+  pub fn sandwiches(&self) -> Vec<Sandwich> {
+    self.sandwiches.clone()
+  }
+
+  pub fn clear_sandwiches(&mut self) {
+    self.sandwiches.clear();
+  }
+
+  /// Kropki Sudoku, the values either side of a white dot are consecutive
+  /// and one value beside a black dot is double the other
+  pub fn add_dot(&mut self, dot: Dot) {
+    self.dots.push(dot);
+  }
+
+  pub fn dots(&self) -> Vec<Dot> {
+    self.dots.clone()
+  }
+
+  pub fn clear_dots(&mut self) {
+    self.dots.clear();
+  }
+
+  /// The negative constraint of kropki Sudoku: every dot is given, so two
+  /// cells that share a side without a dot are neither consecutive nor
+  /// double one another
+  pub fn all_dots_given(&self) -> bool {
+    self.all_dots_given
+  }
+
+  pub fn set_all_dots_given(&mut self, all_dots_given: bool) {
+    self.all_dots_given = all_dots_given;
+  }
+}
+
+impl Variants {
+  /// Adds a rule of your own, see `VariantRule`. Rules are Rust only, they
+  /// can't be passed from JS.
+  pub fn add_rule(&mut self, rule: Rc<dyn VariantRule>) {
+    self.rules.0.push(rule);
+  }
+
+  pub fn rules(&self) -> Vec<Rc<dyn VariantRule>> {
+    self.rules.0.clone()
+  }
+
+  pub fn clear_rules(&mut self) {
+    self.rules.0.clear();
+  }
+}
+
+impl Variants {
+  /// Returns the cages, thermometers, arrows, sandwich clues and dots whose
+  /// cells are all filled on the board but break their rule. The variants
+  /// must have passed `regions`.
+  pub(crate) fn broken_constraints(
+    &self,
+    board: &Board,
+  ) -> Vec<BrokenConstraint> {
+    let mut broken = vec![];
+    broken.extend(broken_indexes(&self.cages, ConstraintType::Cage, |cage| {
+      cage.is_broken(board)
+    }));
+    broken.extend(broken_indexes(
+      &self.thermometers,
+      ConstraintType::Thermometer,
+      |thermometer| thermometer.is_broken(board),
+    ));
+    broken.extend(broken_indexes(
+      &self.arrows,
+      ConstraintType::Arrow,
+      |arrow| arrow.is_broken(board),
+    ));
+    broken.extend(broken_indexes(
+      &self.sandwiches,
+      ConstraintType::Sandwich,
+      |sandwich| sandwich.is_broken(board),
+    ));
+    broken.extend(broken_indexes(&self.dots, ConstraintType::Dot, |dot| {
+      dot.is_broken(board)
+    }));
+
+    broken
+  }
+
+  /// Returns the pairs of cells whose values break a rule that adds no
+  /// region but is about two cells: the dots, and the pairs without one
+  /// when every dot is given. The variants must have passed `regions`.
+  pub(crate) fn conflicts(&self, board: &Board) -> Vec<Conflict> {
+    dot_conflicts(&self.dots, self.all_dots_given, board)
+  }
+}
+
+/// The constraints that are broken, by their index in the list
+fn broken_indexes<'a, T>(
+  constraints: &'a [T],
+  constraint_type: ConstraintType,
+  is_broken: impl Fn(&T) -> bool + 'a,
+) -> impl Iterator<Item = BrokenConstraint> + 'a {
+  constraints
+    .iter()
+    .enumerate()
+    .filter(move |(_, constraint)| is_broken(constraint))
+    .map(move |(index, _)| BrokenConstraint {
+      constraint_type,
+      index,
+    })
+}
+
+/// Cells that must not repeat a value, added to a board by a variant. A
+/// region with as many cells as the board has values must hold every value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Region {
+  pub constraint_type: ConstraintType,
+  pub cells: Vec<Cell>,
+}
+
+impl Variants {
+  /// Returns the extra regions of boards shaped like `shape`: diagonals,
+  /// windows, pairs of cells a knight's or king's move apart and cages,
+  /// with every region that must hold every value before the others.
+  /// Returns an error if the windows, cages, thermometers, arrows, sandwich
+  /// clues or dots do not fit on the board.
+  pub(crate) fn regions(
+    &self,
+    shape: &Board,
+  ) -> Result<Vec<Region>, SolveError> {
+    let mut regions = vec![];
+
+    if self.diagonals {
+      regions.extend(diagonals(shape));
+    }
+    if self.windows {
+      regions.extend(windows(shape)?);
+    }
+    if self.anti_knight {
+      let knight_moves = [(1, 2), (2, 1), (2, -1), (1, -2)];
+      regions.extend(move_pairs(shape, &knight_moves, ConstraintType::Knight));
+    }
+    if self.anti_king {
+      let king_moves = [(1, 1), (1, -1)];
+      regions.extend(move_pairs(shape, &king_moves, ConstraintType::King));
+    }
+    check_cages(&self.cages, shape)?;
+    regions.extend(self.cages.iter().map(cage_region));
+    check_thermometers(&self.thermometers, shape)?;
+    check_arrows(&self.arrows, shape)?;
+    check_sandwiches(&self.sandwiches, shape)?;
+    check_dots(&self.dots, shape)?;
+
+    regions.sort_by_key(|region| region.cells.len() < shape.size());
+
+    Ok(regions)
+  }
+
+  /// Returns the pruners that enforce what the regions can't, for boards
+  /// shaped like `shape`. The variants must have passed `regions`.
+  pub(crate) fn pruners(&self, shape: &Board) -> Vec<Box<dyn Prune>> {
+    let mut pruners: Vec<Box<dyn Prune>> = vec![];
+
+    if !self.cages.is_empty() {
+      pruners.push(Box::new(CagePruner::new(&self.cages, shape)));
+    }
+    if !self.thermometers.is_empty() {
+      pruners.push(Box::new(ThermometerPruner::new(&self.thermometers, shape)));
+    }
+    if !self.arrows.is_empty() {
+      pruners.push(Box::new(ArrowPruner::new(&self.arrows, shape)));
+    }
+    if !self.sandwiches.is_empty() {
+      pruners.push(Box::new(SandwichPruner::new(&self.sandwiches, shape)));
+    }
+    if !self.dots.is_empty() || self.all_dots_given {
+      pruners.push(Box::new(DotPruner::new(
+        &self.dots,
+        self.all_dots_given,
+        shape,
+      )));
+    }
+    for rule in &self.rules.0 {
+      pruners.push(Box::new(RulePruner::new(rule.clone(), shape)));
+    }
+
+    pruners
+  }
+}
+
+/// The main diagonal from the top left corner, then the one from the top
+/// right corner
+fn diagonals(shape: &Board) -> [Region; 2] {
+  let size = shape.size();
+
+  [
+    Region {
+      constraint_type: ConstraintType::Diagonal,
+      cells: (0..size).map(|i| Cell { x: i, y: i }).collect(),
+    },
+    Region {
+      constraint_type: ConstraintType::Diagonal,
+      cells: (0..size)
+        .map(|i| Cell {
+          x: size - 1 - i,
+          y: i,
+        })
+        .collect(),
+    },
+  ]
+}
+
+/// Every pair of cells one of the moves apart, as a region of two cells that
+/// must not repeat a value. The moves go right, so each pair is found once.
+fn move_pairs(
+  shape: &Board,
+  moves: &[(usize, isize)],
+  constraint_type: ConstraintType,
+) -> Vec<Region> {
+  let size = shape.size();
+  let mut regions = vec![];
+
+  for y in 0..size {
+    for x in 0..size {
+      for &(dx, dy) in moves {
+        let other = Cell {
+          x: x + dx,
+          y: match y.checked_add_signed(dy) {
+            Some(other_y) => other_y,
+            None => continue,
+          },
+        };
+
+        if other.x < size && other.y < size {
+          regions.push(Region {
+            constraint_type,
+            cells: vec![Cell { x, y }, other],
+          });
+        }
+      }
+    }
+  }
+
+  regions
+}
+
+/// The windows left to right then top to bottom
+fn windows(shape: &Board) -> Result<Vec<Region>, SolveError> {
+  let (box_height, box_width) = (shape.box_height(), shape.box_width());
+  // There are `box_height` boxes across the board and `box_width` down it
+  let (windows_across, windows_down) =
+    (box_height.saturating_sub(1), box_width.saturating_sub(1));
+
+  let fits = |count: usize, length: usize| count * (length + 1) < shape.size();
+  if !fits(windows_across, box_width) || !fits(windows_down, box_height) {
+    return Err(SolveError::UnsupportedVariant(format!(
+      "Windows do not fit on a board with boxes of {}x{} cells",
+      box_height, box_width
+    )));
+  }
+
+  let mut regions = vec![];
+  for window_y in 0..windows_down {
+    for window_x in 0..windows_across {
+      let left = 1 + window_x * (box_width + 1);
+      let top = 1 + window_y * (box_height + 1);
+      let cells = (top..top + box_height)
+        .flat_map(|y| (left..left + box_width).map(move |x| Cell { x, y }))
+        .collect();
+
+      regions.push(Region {
+        constraint_type: ConstraintType::Window,
+        cells,
+      });
+    }
+  }
+
+  Ok(regions)
+}
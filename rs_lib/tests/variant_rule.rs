@@ -0,0 +1,84 @@
+use rs_lib::{
+  count_solutions, launch_algorithm_x, Board, Cell, ColumnHeuristic,
+  DecisionStrategy, Placement, SearchState, SolveError, VariantRule, Variants,
+};
+use std::rc::Rc;
+
+/// Every value once on the diagonal from the top left to the bottom right
+struct DiagonalRule;
+
+impl VariantRule for DiagonalRule {
+  fn primary_columns(&self, shape: &Board) -> usize {
+    shape.size()
+  }
+
+  fn columns(&self, _shape: &Board, placement: Placement) -> Vec<usize> {
+    let Cell { x, y } = placement.cell;
+    if x == y {
+      vec![placement.value as usize - 1]
+    } else {
+      vec![]
+    }
+  }
+}
+
+/// The top left cell holds an odd value
+struct OddCornerRule;
+
+impl VariantRule for OddCornerRule {
+  fn after_decision(&self, state: &SearchState) -> Option<Vec<Placement>> {
+    let cell = Cell { x: 0, y: 0 };
+    let value = state.value(cell);
+    if value != 0 && value.is_multiple_of(2) {
+      return None;
+    }
+
+    let even_values = (2..=state.size() as u32).step_by(2);
+
+    Some(even_values.map(|value| Placement { cell, value }).collect())
+  }
+}
+
+fn solve(variants: Variants) -> Board {
+  launch_algorithm_x(
+    None,
+    Some(DecisionStrategy::Random),
+    Some(1),
+    Some(ColumnHeuristic::MinimumRemaining),
+    Some(0),
+    Some(variants),
+  )
+  .unwrap()
+  .remove(0)
+}
+
+#[test]
+fn test_adds_columns_from_outside_the_crate() {
+  let mut variants = Variants::new();
+  variants.add_rule(Rc::new(DiagonalRule));
+  let solution = solve(variants.clone());
+
+  let mut diagonal: Vec<i32> = (0..9).map(|i| solution.get(i, i)).collect();
+  diagonal.sort_unstable();
+  assert_eq!(diagonal, (1..=9).collect::<Vec<_>>());
+
+  // Givens that repeat a value on the diagonal contradict each other
+  let mut board = Board::new();
+  board.set(0, 0, 5);
+  board.set(4, 4, 5);
+  assert!(matches!(
+    count_solutions(&board, 1, Some(variants)),
+    Err(SolveError::ContradictoryGivens(_))
+  ));
+}
+
+#[test]
+fn test_rules_out_placements_from_outside_the_crate() {
+  let mut variants = Variants::new();
+  variants.add_rule(Rc::new(OddCornerRule));
+  assert_eq!(solve(variants.clone()).get(0, 0) % 2, 1);
+
+  let mut board = Board::new();
+  board.set(0, 0, 2);
+  assert_eq!(count_solutions(&board, 1, Some(variants)), Ok(0));
+}